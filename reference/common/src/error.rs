@@ -102,6 +102,14 @@ pub enum AtomicSettleError {
     /// Crypto error.
     #[error("Cryptographic error: {0}")]
     CryptoError(String),
+
+    /// No path through the participant graph had enough spare capacity to
+    /// carry the requested amount.
+    #[error("No route found from {from} to {to} with sufficient capacity")]
+    NoRouteFound {
+        from: ParticipantId,
+        to: ParticipantId,
+    },
 }
 
 impl AtomicSettleError {
@@ -154,6 +162,7 @@ impl AtomicSettleError {
             AtomicSettleError::Timeout(_) => "TIMEOUT",
             AtomicSettleError::ConfigurationError(_) => "CONFIGURATION_ERROR",
             AtomicSettleError::CryptoError(_) => "CRYPTO_ERROR",
+            AtomicSettleError::NoRouteFound { .. } => "NO_ROUTE_FOUND",
         }
     }
 }
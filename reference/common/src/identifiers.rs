@@ -1,9 +1,13 @@
 //! Identifier types for AtomicSettle protocol entities.
 
+use rand::RngCore;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::fmt;
 use uuid::Uuid;
 
+use crate::monetary::Money;
+
 /// Unique identifier for a settlement.
 /// Uses UUID v7 for time-ordered identifiers.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -29,6 +33,45 @@ impl SettlementId {
     pub fn as_uuid(&self) -> &Uuid {
         &self.0
     }
+
+    /// Canonical serialization of a settlement's content-addressable
+    /// identity. Exposed so participants can independently derive the
+    /// expected settlement ID client-side before submitting a request.
+    pub fn canonical_content(
+        idempotency_key: &str,
+        sender: &ParticipantId,
+        receiver: &ParticipantId,
+        amount: &Money,
+    ) -> String {
+        format!(
+            "{}|{}|{}|{}|{}",
+            idempotency_key, sender, receiver, amount.value, amount.currency
+        )
+    }
+
+    /// Derive a settlement ID deterministically from its content, so the
+    /// same logical request maps to the same ID on every coordinator node
+    /// without any shared state. Borrows the payment-hash-as-identity idea
+    /// from Lightning and the fixed-hash content addressing used in Ethereum
+    /// clients. The ID is the first 16 bytes of
+    /// `SHA256(canonical_content(..))`, tagged as an RFC 9562 version-8
+    /// (custom) UUID.
+    pub fn from_content(
+        idempotency_key: &str,
+        sender: &ParticipantId,
+        receiver: &ParticipantId,
+        amount: &Money,
+    ) -> Self {
+        let content = Self::canonical_content(idempotency_key, sender, receiver, amount);
+        let digest = Sha256::digest(content.as_bytes());
+
+        let mut bytes = [0u8; 16];
+        bytes.copy_from_slice(&digest[..16]);
+        bytes[6] = (bytes[6] & 0x0f) | 0x80; // version 8 (custom)
+        bytes[8] = (bytes[8] & 0x3f) | 0x80; // RFC 4122 variant
+
+        Self(Uuid::from_bytes(bytes))
+    }
 }
 
 impl Default for SettlementId {
@@ -192,6 +235,60 @@ impl fmt::Display for MessageId {
     }
 }
 
+/// A 32-byte secret revealed by the final recipient of an HTLC-style
+/// conditional settlement to claim its leg, which then propagates back
+/// upstream so every hop can verify and commit.
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PaymentPreimage(pub [u8; 32]);
+
+impl PaymentPreimage {
+    /// Generate a new random preimage.
+    pub fn random() -> Self {
+        let mut bytes = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut bytes);
+        Self(bytes)
+    }
+
+    /// Derive the payment hash this preimage satisfies.
+    pub fn hash(&self) -> PaymentHash {
+        let digest: [u8; 32] = Sha256::digest(self.0).into();
+        PaymentHash(digest)
+    }
+
+    /// Get the raw bytes.
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl fmt::Debug for PaymentPreimage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "PaymentPreimage(<redacted>)")
+    }
+}
+
+/// `SHA256(preimage)`, committing a multi-hop settlement to a single secret
+/// without revealing it. Every leg of the settlement is locked conditionally
+/// on the same payment hash.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PaymentHash(pub [u8; 32]);
+
+impl PaymentHash {
+    /// Check whether `preimage` satisfies this hash.
+    pub fn is_satisfied_by(&self, preimage: &PaymentPreimage) -> bool {
+        preimage.hash() == *self
+    }
+}
+
+impl fmt::Display for PaymentHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in &self.0 {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
 /// Unique identifier for a coordinator node.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct NodeId(String);
@@ -255,4 +352,32 @@ mod tests {
         );
         assert_eq!(account.canonical(), "JPMORGAN_NY:12345678:USD");
     }
+
+    #[test]
+    fn test_settlement_id_from_content_is_deterministic() {
+        let sender = ParticipantId::new("BANK_A");
+        let receiver = ParticipantId::new("BANK_B");
+        let amount = Money::new(rust_decimal::Decimal::from(1000), crate::monetary::Currency::usd());
+
+        let id1 = SettlementId::from_content("key-1", &sender, &receiver, &amount);
+        let id2 = SettlementId::from_content("key-1", &sender, &receiver, &amount);
+        assert_eq!(id1, id2);
+
+        let id3 = SettlementId::from_content("key-2", &sender, &receiver, &amount);
+        assert_ne!(id1, id3);
+    }
+
+    #[test]
+    fn test_payment_preimage_hash_roundtrip() {
+        let preimage = PaymentPreimage::random();
+        let hash = preimage.hash();
+        assert!(hash.is_satisfied_by(&preimage));
+    }
+
+    #[test]
+    fn test_payment_hash_rejects_wrong_preimage() {
+        let hash = PaymentPreimage::random().hash();
+        let other = PaymentPreimage::random();
+        assert!(!hash.is_satisfied_by(&other));
+    }
 }
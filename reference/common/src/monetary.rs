@@ -273,16 +273,104 @@ impl FxRate {
         ((self.ask - self.bid) / self.mid) * Decimal::from(10000)
     }
 
-    /// Convert an amount using the mid rate.
-    pub fn convert(&self, amount: &Money) -> Result<Money, CurrencyMismatchError> {
+    /// Convert an amount using the mid rate. Uses checked multiplication
+    /// so a pathologically large amount or rate overflows into an
+    /// explicit [`FxConversionError::Overflow`] rather than panicking.
+    pub fn convert(&self, amount: &Money) -> Result<Money, FxConversionError> {
         if amount.currency != self.pair.base {
-            return Err(CurrencyMismatchError {
+            return Err(FxConversionError::CurrencyMismatch {
                 expected: self.pair.base.clone(),
                 actual: amount.currency.clone(),
             });
         }
 
-        Ok(Money::new(amount.value * self.mid, self.pair.quote.clone()).round())
+        let converted = amount
+            .value
+            .checked_mul(self.mid)
+            .ok_or_else(|| FxConversionError::Overflow {
+                pair: self.pair.clone(),
+            })?;
+
+        Ok(Money::new(converted, self.pair.quote.clone()).round())
+    }
+}
+
+/// Errors converting [`Money`] through an [`FxRate`] or [`RateTable`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum FxConversionError {
+    /// The amount's currency isn't the rate's base currency.
+    #[error("Currency mismatch: expected {expected}, got {actual}")]
+    CurrencyMismatch { expected: Currency, actual: Currency },
+    /// No direct or bridged rate is known for this pair.
+    #[error("No FX rate available for {pair}")]
+    NoRate { pair: CurrencyPair },
+    /// The conversion's multiplication overflowed `Decimal`'s range.
+    #[error("FX conversion overflowed for rate {pair}")]
+    Overflow { pair: CurrencyPair },
+}
+
+/// A small, synchronous table of [`FxRate`]s keyed by [`CurrencyPair`],
+/// converting [`Money`] directly when a pair is quoted or by triangulating
+/// through a `bridge` currency (e.g. USD) when it isn't. Where
+/// `atomicsettle_fx`'s `ConversionEngine` fetches rates from live
+/// providers behind a cache, `RateTable` is the in-memory lookup this and
+/// the ledger layer reach for when a small fixed set of rates is already
+/// known -- e.g. seeded once at startup, or by a simulator scenario's
+/// `SetRate` step.
+#[derive(Debug, Clone)]
+pub struct RateTable {
+    bridge: Currency,
+    rates: std::collections::HashMap<(Currency, Currency), FxRate>,
+}
+
+impl RateTable {
+    /// Create an empty table that triangulates through `bridge` when a
+    /// requested pair isn't directly quoted.
+    pub fn new(bridge: Currency) -> Self {
+        Self {
+            bridge,
+            rates: std::collections::HashMap::new(),
+        }
+    }
+
+    /// Record (or replace) a directly-quoted rate.
+    pub fn set_rate(&mut self, rate: FxRate) {
+        let key = (rate.pair.base.clone(), rate.pair.quote.clone());
+        self.rates.insert(key, rate);
+    }
+
+    /// The directly-quoted rate for `pair`, if any.
+    pub fn get_rate(&self, pair: &CurrencyPair) -> Option<&FxRate> {
+        self.rates.get(&(pair.base.clone(), pair.quote.clone()))
+    }
+
+    /// Convert `amount` into `to`, using a direct rate if quoted or
+    /// triangulating through the table's bridge currency otherwise.
+    /// Returns the amount unchanged if it's already in `to`.
+    pub fn convert(&self, amount: &Money, to: &Currency) -> Result<Money, FxConversionError> {
+        if amount.currency == *to {
+            return Ok(amount.clone());
+        }
+
+        let direct_pair = CurrencyPair::new(amount.currency.clone(), to.clone());
+        if let Some(rate) = self.get_rate(&direct_pair) {
+            return rate.convert(amount);
+        }
+
+        if amount.currency == self.bridge || *to == self.bridge {
+            return Err(FxConversionError::NoRate { pair: direct_pair });
+        }
+
+        let to_bridge = CurrencyPair::new(amount.currency.clone(), self.bridge.clone());
+        let via_bridge = self
+            .get_rate(&to_bridge)
+            .ok_or(FxConversionError::NoRate { pair: to_bridge })?
+            .convert(amount)?;
+
+        let from_bridge = CurrencyPair::new(self.bridge.clone(), to.clone());
+        self.get_rate(&from_bridge)
+            .ok_or(FxConversionError::NoRate { pair: from_bridge })?
+            .convert(&via_bridge)
     }
 }
 
@@ -373,4 +461,67 @@ mod tests {
         assert_eq!(Currency::eur().decimal_places(), 2);
         assert_eq!(Currency::jpy().decimal_places(), 0);
     }
+
+    #[test]
+    fn test_rate_table_direct_lookup() {
+        let mut table = RateTable::new(Currency::usd());
+        table.set_rate(FxRate::new(
+            CurrencyPair::new(Currency::usd(), Currency::eur()),
+            Decimal::from_str_exact("0.91").unwrap(),
+            Decimal::from_str_exact("0.93").unwrap(),
+            30,
+            "TEST",
+        ));
+
+        let usd = Money::from_str("1000.00", Currency::usd()).unwrap();
+        let eur = table.convert(&usd, &Currency::eur()).unwrap();
+
+        assert_eq!(eur.currency, Currency::eur());
+        assert_eq!(eur.value, Decimal::from(920));
+    }
+
+    #[test]
+    fn test_rate_table_triangulates_through_bridge() {
+        let mut table = RateTable::new(Currency::usd());
+        table.set_rate(FxRate::new(
+            CurrencyPair::new(Currency::eur(), Currency::usd()),
+            Decimal::from_str_exact("1.08").unwrap(),
+            Decimal::from_str_exact("1.10").unwrap(),
+            30,
+            "TEST",
+        ));
+        table.set_rate(FxRate::new(
+            CurrencyPair::new(Currency::usd(), Currency::jpy()),
+            Decimal::from_str_exact("149.0").unwrap(),
+            Decimal::from_str_exact("151.0").unwrap(),
+            30,
+            "TEST",
+        ));
+
+        let eur = Money::from_str("100.00", Currency::eur()).unwrap();
+        let jpy = table.convert(&eur, &Currency::jpy()).unwrap();
+
+        assert_eq!(jpy.currency, Currency::jpy());
+        assert_eq!(jpy.value, Decimal::from(16350));
+    }
+
+    #[test]
+    fn test_rate_table_missing_pair_errors() {
+        let table = RateTable::new(Currency::usd());
+        let eur = Money::from_str("100.00", Currency::eur()).unwrap();
+
+        let err = table.convert(&eur, &Currency::jpy()).unwrap_err();
+        assert!(matches!(err, FxConversionError::NoRate { .. }));
+    }
+
+    #[test]
+    fn test_fx_rate_convert_overflow_is_explicit_error() {
+        let pair = CurrencyPair::new(Currency::usd(), Currency::eur());
+        let mut rate = FxRate::new(pair, Decimal::ONE, Decimal::ONE, 30, "TEST");
+        rate.mid = Decimal::MAX;
+
+        let usd = Money::from_str("1000.00", Currency::usd()).unwrap();
+        let err = rate.convert(&usd).unwrap_err();
+        assert!(matches!(err, FxConversionError::Overflow { .. }));
+    }
 }
@@ -20,14 +20,38 @@ pub enum SettlementStatus {
     Locked,
     /// Executing atomic commit.
     Committing,
+    /// All locks acquired but at least one leg's release plan hasn't yet
+    /// reduced to `Pay`; waiting on timestamp/signature witnesses.
+    PendingConditions,
     /// Committed, awaiting acknowledgments.
     Committed,
+    /// Committed and acknowledged, but the underlying ledger/RTGS posting
+    /// hasn't yet reached its confirmation threshold and could still be
+    /// reversed (a reorg, a recall).
+    PendingFinality,
+    /// Committed, waiting on a pluggable `ConfirmationMonitor` to report
+    /// that every leg actually resolved on its external settlement rail
+    /// (on-chain, RTGS, correspondent bank) before declaring victory.
+    AwaitingConfirmation,
     /// Complete, all parties acknowledged.
     Settled,
     /// Could not process request (before locking).
     Rejected,
     /// Failed after partial processing.
     Failed,
+    /// Both legs of an atomic cross-currency swap are locked behind a
+    /// shared payment hash, awaiting either a preimage reveal that
+    /// claims both legs or a timeout that refunds them.
+    HtlcLocked,
+    /// A locked leg's timeout fired before the preimage was revealed;
+    /// refunding every leg back to its sender.
+    HtlcRefunding,
+    /// Every leg of the swap was refunded after timeout; no value moved.
+    HtlcRefunded,
+    /// Unwinding locks already acquired on earlier legs after a mid-flight
+    /// abort; transitions to `Failed` once every previously-locked leg is
+    /// compensated.
+    Compensating,
 }
 
 impl SettlementStatus {
@@ -35,7 +59,10 @@ impl SettlementStatus {
     pub fn is_final(&self) -> bool {
         matches!(
             self,
-            SettlementStatus::Settled | SettlementStatus::Rejected | SettlementStatus::Failed
+            SettlementStatus::Settled
+                | SettlementStatus::Rejected
+                | SettlementStatus::Failed
+                | SettlementStatus::HtlcRefunded
         )
     }
 
@@ -57,14 +84,48 @@ impl SettlementStatus {
                 &[SettlementStatus::Validated, SettlementStatus::Rejected]
             }
             SettlementStatus::Locking => &[SettlementStatus::Locked, SettlementStatus::Failed],
-            SettlementStatus::Locked => &[SettlementStatus::Committing, SettlementStatus::Failed],
-            SettlementStatus::Committing => {
-                &[SettlementStatus::Committed, SettlementStatus::Failed]
+            SettlementStatus::Locked => &[
+                SettlementStatus::Committing,
+                SettlementStatus::PendingConditions,
+                SettlementStatus::HtlcLocked,
+                SettlementStatus::Compensating,
+                SettlementStatus::Failed,
+            ],
+            SettlementStatus::PendingConditions => &[
+                SettlementStatus::Committing,
+                SettlementStatus::Compensating,
+                SettlementStatus::Failed,
+            ],
+            SettlementStatus::Committing => &[
+                SettlementStatus::Committed,
+                SettlementStatus::Compensating,
+                SettlementStatus::Failed,
+            ],
+            SettlementStatus::Committed => &[
+                SettlementStatus::PendingFinality,
+                SettlementStatus::AwaitingConfirmation,
+            ],
+            SettlementStatus::PendingFinality => {
+                &[SettlementStatus::Settled, SettlementStatus::Failed]
+            }
+            SettlementStatus::AwaitingConfirmation => {
+                &[SettlementStatus::Settled, SettlementStatus::Failed]
             }
-            SettlementStatus::Committed => &[SettlementStatus::Settled],
             SettlementStatus::Settled => &[],
             SettlementStatus::Rejected => &[],
             SettlementStatus::Failed => &[],
+            SettlementStatus::Compensating => &[SettlementStatus::Failed],
+            // Both legs locked; the coordinator now waits for the
+            // preimage reveal (-> Committing) or a leg timeout (->
+            // HtlcRefunding).
+            SettlementStatus::HtlcLocked => &[
+                SettlementStatus::Committing,
+                SettlementStatus::HtlcRefunding,
+            ],
+            SettlementStatus::HtlcRefunding => {
+                &[SettlementStatus::HtlcRefunded, SettlementStatus::Failed]
+            }
+            SettlementStatus::HtlcRefunded => &[],
         }
     }
 
@@ -95,6 +156,11 @@ pub struct SettlementLeg {
     pub lock_id: Option<LockId>,
     /// Converted amount (after FX, if applicable).
     pub converted_amount: Option<Money>,
+    /// Escrow conditions gating this leg's release, if any. `None` means
+    /// the leg commits as soon as the settlement reaches `Committing`;
+    /// `Some` means it must first reduce to [`ReleasePlan::Pay`] via
+    /// witnessed conditions.
+    pub release_plan: Option<ReleasePlan>,
 }
 
 impl SettlementLeg {
@@ -117,13 +183,135 @@ impl SettlementLeg {
             fx_instruction: None,
             lock_id: None,
             converted_amount: None,
+            release_plan: None,
         }
     }
 
+    /// Gate this leg's release behind `plan` instead of committing
+    /// immediately.
+    pub fn with_release_plan(mut self, plan: ReleasePlan) -> Self {
+        self.release_plan = Some(plan);
+        self
+    }
+
     /// Check if this is a cross-currency leg.
     pub fn is_cross_currency(&self) -> bool {
         self.from_account.currency != self.to_account.currency
     }
+
+    /// Whether this leg is free to commit: either it has no release
+    /// plan, or its plan has fully reduced to [`ReleasePlan::Pay`].
+    pub fn is_committable(&self) -> bool {
+        self.release_plan
+            .as_ref()
+            .map(|plan| plan.is_committable())
+            .unwrap_or(true)
+    }
+}
+
+/// A single condition an escrowed leg's release plan can be gated on.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Condition {
+    /// Satisfied by a timestamp witness from the named trusted time
+    /// oracle, no earlier than `not_before`.
+    Timestamp {
+        not_before: DateTime<Utc>,
+        source: ParticipantId,
+    },
+    /// Satisfied by a signature witness from the named authorizing
+    /// party.
+    Signature { signer: ParticipantId },
+}
+
+impl Condition {
+    /// Whether `witness` satisfies this condition. A timestamp condition
+    /// only accepts a timestamp witness from its named `source`, at or
+    /// after `not_before`; a signature condition only accepts a
+    /// signature witness from its named `signer`.
+    fn is_satisfied_by(&self, witness: &Witness) -> bool {
+        match (self, witness) {
+            (
+                Condition::Timestamp { not_before, source },
+                Witness::Timestamp {
+                    at,
+                    source: witness_source,
+                },
+            ) => witness_source == source && at >= not_before,
+            (
+                Condition::Signature { signer },
+                Witness::Signature {
+                    signer: witness_signer,
+                },
+            ) => witness_signer == signer,
+            _ => false,
+        }
+    }
+}
+
+/// Evidence that a [`Condition`] has been met.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Witness {
+    /// A trusted time oracle attesting to the current time.
+    Timestamp {
+        at: DateTime<Utc>,
+        source: ParticipantId,
+    },
+    /// An authorizing party's signature (verification happens upstream;
+    /// this just names the signer the plan is waiting on).
+    Signature { signer: ParticipantId },
+}
+
+/// A reducible escrow plan gating when a leg becomes committable.
+/// Applying a satisfying [`Witness`] collapses the plan one step closer
+/// to [`ReleasePlan::Pay`]; a leg is committable once its plan has fully
+/// reduced.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReleasePlan {
+    /// The leg commits as-is; fully reduced.
+    Pay,
+    /// Waits on a single condition before continuing as `inner`.
+    After(Condition, Box<ReleasePlan>),
+    /// Either branch can fire; the first branch whose condition is
+    /// witnessed is taken and the other is abandoned (e.g.
+    /// release-on-signature OR refund-after-timeout).
+    Or((Condition, Box<ReleasePlan>), (Condition, Box<ReleasePlan>)),
+    /// Waits on every condition in the set (in any order) before
+    /// continuing as `inner`.
+    And(Vec<Condition>, Box<ReleasePlan>),
+}
+
+impl ReleasePlan {
+    /// Whether this plan has fully reduced to `Pay`, i.e. the leg it
+    /// gates is now committable.
+    pub fn is_committable(&self) -> bool {
+        matches!(self, ReleasePlan::Pay)
+    }
+
+    /// Reduce the plan by one step if `witness` satisfies a condition it
+    /// is currently waiting on. A no-op if the witness doesn't match.
+    pub fn apply_witness(&mut self, witness: &Witness) {
+        match self {
+            ReleasePlan::Pay => {}
+            ReleasePlan::After(condition, inner) => {
+                if condition.is_satisfied_by(witness) {
+                    *self = (**inner).clone();
+                }
+            }
+            ReleasePlan::Or((cond_a, inner_a), (cond_b, inner_b)) => {
+                if cond_a.is_satisfied_by(witness) {
+                    *self = (**inner_a).clone();
+                } else if cond_b.is_satisfied_by(witness) {
+                    *self = (**inner_b).clone();
+                }
+            }
+            ReleasePlan::And(conditions, inner) => {
+                conditions.retain(|condition| !condition.is_satisfied_by(witness));
+                if conditions.is_empty() {
+                    *self = (**inner).clone();
+                }
+            }
+        }
+    }
 }
 
 /// FX instruction specifying how currency conversion should be handled.
@@ -189,6 +377,149 @@ pub struct Address {
     pub country: String,
 }
 
+/// Reference to a specific block on an external ledger, used to prove a
+/// settlement's on-chain anchoring actually resolved there rather than
+/// merely that a transaction was broadcast (e.g. reading back the Router
+/// contract's `InInstructions` event at this block, not just its receipt).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockRef {
+    /// Hash of the block the confirming event was read from.
+    pub block_hash: String,
+    /// Height of that block.
+    pub block_number: u64,
+}
+
+impl BlockRef {
+    /// Create a new block reference.
+    pub fn new(block_hash: impl Into<String>, block_number: u64) -> Self {
+        Self {
+            block_hash: block_hash.into(),
+            block_number,
+        }
+    }
+}
+
+/// A single entry in a settlement's append-only event log, each carrying
+/// the sequence number and timestamp it was recorded at. `Settlement`'s
+/// `status`/`timing`/`metadata`/`fx_details`/`failure` fields are a
+/// materialized view folded from this log by [`Settlement::apply`] --
+/// the log itself, not the fields, is the durable source of truth a
+/// crashed coordinator replays from.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum SettlementEvent {
+    /// The settlement was created. Always the first event; recorded by
+    /// [`Settlement::new`].
+    Initiated { seq: u64, at: DateTime<Utc> },
+    /// Validation checks passed.
+    Validated { seq: u64, at: DateTime<Utc> },
+    /// Routed to manual compliance review instead of locking.
+    ReviewRequired { seq: u64, at: DateTime<Utc> },
+    /// All legs' locks were acquired; carries the lock assigned to each
+    /// leg, in leg order.
+    LocksAcquired {
+        seq: u64,
+        at: DateTime<Utc>,
+        lock_ids: Vec<LockId>,
+    },
+    /// Atomic commit began.
+    CommitStarted { seq: u64, at: DateTime<Utc> },
+    /// Atomic commit completed.
+    Committed { seq: u64, at: DateTime<Utc> },
+    /// Every party acknowledged; terminal success.
+    Settled { seq: u64, at: DateTime<Utc> },
+    /// Rejected before locking, with the reason.
+    Rejected {
+        seq: u64,
+        at: DateTime<Utc>,
+        code: FailureCode,
+    },
+    /// Failed after partial processing, with full failure detail.
+    Failed {
+        seq: u64,
+        at: DateTime<Utc>,
+        failure: SettlementFailure,
+    },
+    /// Any other status transition (e.g. `Locking`, `PendingFinality`, the
+    /// HTLC swap states) that has no dedicated event of its own.
+    Transitioned {
+        seq: u64,
+        at: DateTime<Utc>,
+        to: SettlementStatus,
+    },
+    /// A metadata key was set or overwritten.
+    MetadataRecorded {
+        seq: u64,
+        at: DateTime<Utc>,
+        key: String,
+        value: String,
+    },
+    /// FX conversion details were recorded for a cross-currency leg.
+    FxDetailsRecorded {
+        seq: u64,
+        at: DateTime<Utc>,
+        details: FxDetails,
+    },
+}
+
+impl SettlementEvent {
+    /// The event's position in its settlement's log.
+    pub fn seq(&self) -> u64 {
+        match self {
+            SettlementEvent::Initiated { seq, .. }
+            | SettlementEvent::Validated { seq, .. }
+            | SettlementEvent::ReviewRequired { seq, .. }
+            | SettlementEvent::LocksAcquired { seq, .. }
+            | SettlementEvent::CommitStarted { seq, .. }
+            | SettlementEvent::Committed { seq, .. }
+            | SettlementEvent::Settled { seq, .. }
+            | SettlementEvent::Rejected { seq, .. }
+            | SettlementEvent::Failed { seq, .. }
+            | SettlementEvent::Transitioned { seq, .. }
+            | SettlementEvent::MetadataRecorded { seq, .. }
+            | SettlementEvent::FxDetailsRecorded { seq, .. } => *seq,
+        }
+    }
+
+    /// When the event was recorded.
+    pub fn at(&self) -> DateTime<Utc> {
+        match self {
+            SettlementEvent::Initiated { at, .. }
+            | SettlementEvent::Validated { at, .. }
+            | SettlementEvent::ReviewRequired { at, .. }
+            | SettlementEvent::LocksAcquired { at, .. }
+            | SettlementEvent::CommitStarted { at, .. }
+            | SettlementEvent::Committed { at, .. }
+            | SettlementEvent::Settled { at, .. }
+            | SettlementEvent::Rejected { at, .. }
+            | SettlementEvent::Failed { at, .. }
+            | SettlementEvent::Transitioned { at, .. }
+            | SettlementEvent::MetadataRecorded { at, .. }
+            | SettlementEvent::FxDetailsRecorded { at, .. } => *at,
+        }
+    }
+
+    /// The settlement status this event drives a transition to, or `None`
+    /// for events (metadata, FX details) that annotate the settlement
+    /// without changing its status.
+    fn target_status(&self) -> Option<SettlementStatus> {
+        match self {
+            SettlementEvent::Initiated { .. } => Some(SettlementStatus::Initiated),
+            SettlementEvent::Validated { .. } => Some(SettlementStatus::Validated),
+            SettlementEvent::ReviewRequired { .. } => Some(SettlementStatus::PendingReview),
+            SettlementEvent::LocksAcquired { .. } => Some(SettlementStatus::Locked),
+            SettlementEvent::CommitStarted { .. } => Some(SettlementStatus::Committing),
+            SettlementEvent::Committed { .. } => Some(SettlementStatus::Committed),
+            SettlementEvent::Settled { .. } => Some(SettlementStatus::Settled),
+            SettlementEvent::Rejected { .. } => Some(SettlementStatus::Rejected),
+            SettlementEvent::Failed { .. } => Some(SettlementStatus::Failed),
+            SettlementEvent::Transitioned { to, .. } => Some(*to),
+            SettlementEvent::MetadataRecorded { .. } | SettlementEvent::FxDetailsRecorded { .. } => {
+                None
+            }
+        }
+    }
+}
+
 /// A complete settlement.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Settlement {
@@ -210,11 +541,30 @@ pub struct Settlement {
     pub metadata: std::collections::HashMap<String, String>,
     /// Failure information (if failed).
     pub failure: Option<SettlementFailure>,
+    /// Block at which an external anchoring backend confirmed this
+    /// settlement's transfer actually resolved on-chain, if one is
+    /// attached and has confirmed it.
+    pub on_chain_confirmation: Option<BlockRef>,
+    /// Append-only log of every event folded into this settlement so
+    /// far, in order. The durable, replayable history `replay` rebuilds
+    /// from; `status` and the other fields above are just its current
+    /// materialized view.
+    pub events: Vec<SettlementEvent>,
+    /// The in-flight rollback plan while `status` is `Compensating`;
+    /// `None` once every previously-locked leg has been released and the
+    /// settlement has moved on to `Failed`.
+    pub compensation: Option<CompensationPlan>,
 }
 
 impl Settlement {
-    /// Create a new settlement.
+    /// Create a new settlement, recording its `Initiated` event.
     pub fn new(idempotency_key: String, legs: Vec<SettlementLeg>) -> Self {
+        let timing = SettlementTiming::new();
+        let initiated = SettlementEvent::Initiated {
+            seq: 0,
+            at: timing.initiated_at,
+        };
+
         Self {
             id: SettlementId::new(),
             idempotency_key,
@@ -222,13 +572,147 @@ impl Settlement {
             legs,
             compliance: None,
             fx_details: None,
+            timing,
+            metadata: std::collections::HashMap::new(),
+            failure: None,
+            on_chain_confirmation: None,
+            events: vec![initiated],
+            compensation: None,
+        }
+    }
+
+    /// Fold a single event into the current state, updating `status` and
+    /// whichever other field that event carries data for. Does not
+    /// validate the transition or append to `events` -- callers that
+    /// want both go through [`Self::transition_to`]/[`Self::fail`]/etc.,
+    /// or [`Self::replay`] for rebuilding from a persisted log.
+    pub fn apply(&mut self, event: &SettlementEvent) {
+        match event {
+            SettlementEvent::Initiated { at, .. } => {
+                self.status = SettlementStatus::Initiated;
+                self.timing.initiated_at = *at;
+            }
+            SettlementEvent::Validated { at, .. } => {
+                self.status = SettlementStatus::Validated;
+                self.timing.validated_at = Some(*at);
+            }
+            SettlementEvent::ReviewRequired { .. } => {
+                self.status = SettlementStatus::PendingReview;
+            }
+            SettlementEvent::LocksAcquired { at, lock_ids, .. } => {
+                self.status = SettlementStatus::Locked;
+                self.timing.locked_at = Some(*at);
+                for (leg, lock_id) in self.legs.iter_mut().zip(lock_ids.iter()) {
+                    leg.lock_id = Some(lock_id.clone());
+                }
+            }
+            SettlementEvent::CommitStarted { .. } => {
+                self.status = SettlementStatus::Committing;
+            }
+            SettlementEvent::Committed { at, .. } => {
+                self.status = SettlementStatus::Committed;
+                self.timing.committed_at = Some(*at);
+            }
+            SettlementEvent::Settled { at, .. } => {
+                self.status = SettlementStatus::Settled;
+                self.timing.settled_at = Some(*at);
+            }
+            SettlementEvent::Rejected { .. } => {
+                self.status = SettlementStatus::Rejected;
+            }
+            SettlementEvent::Failed { at, failure, .. } => {
+                self.status = SettlementStatus::Failed;
+                self.failure = Some(failure.clone());
+                self.timing.failed_at = Some(*at);
+            }
+            SettlementEvent::Transitioned { to, .. } => {
+                self.status = *to;
+            }
+            SettlementEvent::MetadataRecorded { key, value, .. } => {
+                self.metadata.insert(key.clone(), value.clone());
+            }
+            SettlementEvent::FxDetailsRecorded { details, .. } => {
+                self.fx_details = Some(details.clone());
+            }
+        }
+    }
+
+    /// Rebuild a settlement from an ordered event log, enforcing
+    /// `can_transition_to` at each status-changing event the same way
+    /// live transitions do. Static fields the log doesn't carry (`id`,
+    /// `idempotency_key`, `legs`) come back at their defaults; `legs` is
+    /// empty, so a `LocksAcquired` event's lock ids are not attached to
+    /// any leg. Callers that need them combine the replayed state with
+    /// the settlement's own stored envelope.
+    pub fn replay(events: &[SettlementEvent]) -> Result<Self, InvalidTransition> {
+        let mut settlement = Self {
+            id: SettlementId::new(),
+            idempotency_key: String::new(),
+            status: SettlementStatus::Initiated,
+            legs: Vec::new(),
+            compliance: None,
+            fx_details: None,
             timing: SettlementTiming::new(),
             metadata: std::collections::HashMap::new(),
             failure: None,
+            on_chain_confirmation: None,
+            events: Vec::new(),
+            compensation: None,
+        };
+
+        for event in events {
+            let is_genesis = matches!(event, SettlementEvent::Initiated { .. });
+            if let Some(target) = event.target_status() {
+                if !is_genesis && !settlement.status.can_transition_to(target) {
+                    return Err(InvalidTransition {
+                        from: settlement.status,
+                        to: target,
+                    });
+                }
+            }
+
+            settlement.apply(event);
+            settlement.events.push(event.clone());
+        }
+
+        Ok(settlement)
+    }
+
+    /// Rebuild this settlement's materialized view (`status`, `timing`,
+    /// `failure`, `fx_details`, `metadata`) by re-folding its own
+    /// `events` log from scratch via [`Self::replay`], keeping the static
+    /// envelope (`id`, `idempotency_key`, `legs` with their current
+    /// `lock_id`s, `compliance`, `on_chain_confirmation`, `compensation`)
+    /// from `self` rather than `replay`'s defaults. Used by a durable
+    /// store's `load` to treat the persisted event log, not whatever
+    /// snapshot happened to be written, as the source of truth on
+    /// recovery. Falls back to `self` unchanged if the log itself
+    /// contains an invalid transition (should not happen for a log this
+    /// same type produced, but recovery must never panic on it).
+    pub fn rebuild_from_events(self) -> Self {
+        match Self::replay(&self.events) {
+            Ok(mut replayed) => {
+                replayed.id = self.id;
+                replayed.idempotency_key = self.idempotency_key;
+                replayed.legs = self.legs;
+                replayed.compliance = self.compliance;
+                replayed.on_chain_confirmation = self.on_chain_confirmation;
+                replayed.compensation = self.compensation;
+                replayed
+            }
+            Err(_) => self,
         }
     }
 
-    /// Transition to a new status.
+    /// Record `event` for a transition already found valid, folding it
+    /// into state and appending it to the log.
+    fn record(&mut self, event: SettlementEvent) {
+        self.apply(&event);
+        self.events.push(event);
+    }
+
+    /// Transition to a new status, validating, then recording and
+    /// applying the matching event.
     pub fn transition_to(&mut self, new_status: SettlementStatus) -> Result<(), InvalidTransition> {
         if !self.status.can_transition_to(new_status) {
             return Err(InvalidTransition {
@@ -237,18 +721,41 @@ impl Settlement {
             });
         }
 
-        self.status = new_status;
+        let seq = self.events.len() as u64;
+        let at = Utc::now();
+        let event = match new_status {
+            SettlementStatus::Validated => SettlementEvent::Validated { seq, at },
+            SettlementStatus::PendingReview => SettlementEvent::ReviewRequired { seq, at },
+            SettlementStatus::Locked => SettlementEvent::LocksAcquired {
+                seq,
+                at,
+                lock_ids: self.legs.iter().filter_map(|leg| leg.lock_id.clone()).collect(),
+            },
+            SettlementStatus::Committing => SettlementEvent::CommitStarted { seq, at },
+            SettlementStatus::Committed => SettlementEvent::Committed { seq, at },
+            SettlementStatus::Settled => SettlementEvent::Settled { seq, at },
+            other => SettlementEvent::Transitioned { seq, at, to: other },
+        };
 
-        // Update timing based on status
-        let now = Utc::now();
-        match new_status {
-            SettlementStatus::Validated => self.timing.validated_at = Some(now),
-            SettlementStatus::Locked => self.timing.locked_at = Some(now),
-            SettlementStatus::Committed => self.timing.committed_at = Some(now),
-            SettlementStatus::Settled => self.timing.settled_at = Some(now),
-            _ => {}
+        self.record(event);
+        Ok(())
+    }
+
+    /// Reject the settlement before locking, recording the reason.
+    pub fn reject(&mut self, code: FailureCode) -> Result<(), InvalidTransition> {
+        if !self.status.can_transition_to(SettlementStatus::Rejected) {
+            return Err(InvalidTransition {
+                from: self.status,
+                to: SettlementStatus::Rejected,
+            });
         }
 
+        let seq = self.events.len() as u64;
+        self.record(SettlementEvent::Rejected {
+            seq,
+            at: Utc::now(),
+            code,
+        });
         Ok(())
     }
 
@@ -261,12 +768,142 @@ impl Settlement {
             });
         }
 
-        self.failure = Some(failure);
-        self.status = SettlementStatus::Failed;
-        self.timing.failed_at = Some(Utc::now());
+        let seq = self.events.len() as u64;
+        let at = failure.failed_at;
+        self.record(SettlementEvent::Failed { seq, at, failure });
         Ok(())
     }
 
+    /// Begin unwinding locks already acquired on earlier legs after a
+    /// mid-flight abort. Records `failure`, transitions to
+    /// `Compensating`, and returns the ordered rollback plan -- legs that
+    /// currently hold a `lock_id`, in reverse leg order. If no leg is
+    /// currently locked, compensation has nothing to do and the
+    /// settlement transitions straight on to `Failed`.
+    pub fn begin_compensation(
+        &mut self,
+        failure: SettlementFailure,
+    ) -> Result<CompensationPlan, InvalidTransition> {
+        if !self.status.can_transition_to(SettlementStatus::Compensating) {
+            return Err(InvalidTransition {
+                from: self.status,
+                to: SettlementStatus::Compensating,
+            });
+        }
+
+        let steps: Vec<CompensationStep> = self
+            .legs
+            .iter()
+            .rev()
+            .filter_map(|leg| {
+                leg.lock_id.map(|lock_id| CompensationStep {
+                    leg_number: leg.leg_number,
+                    lock_id,
+                    released: false,
+                })
+            })
+            .collect();
+
+        let plan = CompensationPlan { failure, steps };
+        self.compensation = Some(plan.clone());
+
+        let seq = self.events.len() as u64;
+        self.record(SettlementEvent::Transitioned {
+            seq,
+            at: Utc::now(),
+            to: SettlementStatus::Compensating,
+        });
+
+        if plan.steps.is_empty() {
+            if let Some(plan) = self.compensation.take() {
+                let _ = self.fail(plan.failure);
+            }
+        }
+
+        Ok(plan)
+    }
+
+    /// Record that `leg_number`'s lock has been released as part of an
+    /// in-progress compensation. Safe to call more than once for the
+    /// same leg (e.g. a coordinator retrying a lock release whose
+    /// outcome it couldn't confirm) -- repeat calls are no-ops. Once
+    /// every previously-locked leg is compensated, transitions
+    /// automatically to `Failed`.
+    pub fn record_compensated(&mut self, leg_number: u32) {
+        let all_released = if let Some(plan) = self.compensation.as_mut() {
+            for step in plan.steps.iter_mut() {
+                if step.leg_number == leg_number {
+                    step.released = true;
+                }
+            }
+            plan.steps.iter().all(|step| step.released)
+        } else {
+            false
+        };
+
+        for leg in self.legs.iter_mut() {
+            if leg.leg_number == leg_number {
+                leg.lock_id = None;
+            }
+        }
+
+        if all_released {
+            if let Some(plan) = self.compensation.take() {
+                let _ = self.fail(plan.failure);
+            }
+        }
+    }
+
+    /// Record a metadata key without transitioning status.
+    pub fn record_metadata(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        let seq = self.events.len() as u64;
+        self.record(SettlementEvent::MetadataRecorded {
+            seq,
+            at: Utc::now(),
+            key: key.into(),
+            value: value.into(),
+        });
+    }
+
+    /// Record FX conversion details without transitioning status.
+    pub fn record_fx_details(&mut self, details: FxDetails) {
+        let seq = self.events.len() as u64;
+        self.record(SettlementEvent::FxDetailsRecorded {
+            seq,
+            at: Utc::now(),
+            details,
+        });
+    }
+
+    /// Stamp an external rail's confirming `reference` for `leg_number`
+    /// into settlement metadata, and transition to `Settled` once every
+    /// leg carries a confirmation. A `ConfirmationMonitor` timeout for a
+    /// leg instead routes into [`Self::fail`] with
+    /// `FailureCode::ParticipantUnavailable` -- this method only handles
+    /// the success path.
+    pub fn record_confirmation(&mut self, leg_number: u32, reference: String) {
+        let seq = self.events.len() as u64;
+        self.record(SettlementEvent::MetadataRecorded {
+            seq,
+            at: Utc::now(),
+            key: Self::confirmation_metadata_key(leg_number),
+            value: reference,
+        });
+
+        let all_confirmed = self
+            .legs
+            .iter()
+            .all(|leg| self.metadata.contains_key(&Self::confirmation_metadata_key(leg.leg_number)));
+
+        if all_confirmed {
+            let _ = self.transition_to(SettlementStatus::Settled);
+        }
+    }
+
+    fn confirmation_metadata_key(leg_number: u32) -> String {
+        format!("confirmation_leg_{leg_number}")
+    }
+
     /// Get the total amount of this settlement (in source currency).
     pub fn total_amount(&self) -> Option<Money> {
         if self.legs.is_empty() {
@@ -292,6 +929,24 @@ impl Settlement {
     pub fn is_cross_currency(&self) -> bool {
         self.legs.iter().any(|leg| leg.is_cross_currency())
     }
+
+    /// Whether every leg is free to commit, i.e. none have a release
+    /// plan still waiting on witnesses. Drives whether the settlement
+    /// should pass through `PendingConditions` on its way to
+    /// `Committing`.
+    pub fn all_legs_committable(&self) -> bool {
+        self.legs.iter().all(|leg| leg.is_committable())
+    }
+
+    /// Apply a witness to every leg's release plan, reducing any that
+    /// are waiting on it.
+    pub fn apply_witness(&mut self, witness: &Witness) {
+        for leg in &mut self.legs {
+            if let Some(plan) = &mut leg.release_plan {
+                plan.apply_witness(witness);
+            }
+        }
+    }
 }
 
 /// Timing metrics for a settlement.
@@ -407,6 +1062,30 @@ pub enum FailureCode {
     InvalidRequest,
 }
 
+/// A deterministic rollback plan unwinding locks acquired on earlier
+/// legs after a mid-flight abort. Built once by
+/// [`Settlement::begin_compensation`] and driven to completion by
+/// repeated, idempotent calls to [`Settlement::record_compensated`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompensationPlan {
+    /// The failure that triggered compensation; replayed onto the
+    /// settlement as its `Failed` reason once every step releases.
+    pub failure: SettlementFailure,
+    /// Legs to release, in reverse leg order.
+    pub steps: Vec<CompensationStep>,
+}
+
+/// One leg's lock release within a [`CompensationPlan`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompensationStep {
+    /// The leg whose lock needs releasing.
+    pub leg_number: u32,
+    /// The lock to release.
+    pub lock_id: LockId,
+    /// Whether this leg's lock has been released.
+    pub released: bool,
+}
+
 /// Error when attempting invalid state transition.
 #[derive(Debug, Clone)]
 pub struct InvalidTransition {
@@ -467,6 +1146,9 @@ mod tests {
         assert!(settlement
             .transition_to(SettlementStatus::Committed)
             .is_ok());
+        assert!(settlement
+            .transition_to(SettlementStatus::PendingFinality)
+            .is_ok());
         assert!(settlement.transition_to(SettlementStatus::Settled).is_ok());
     }
 
@@ -484,6 +1166,395 @@ mod tests {
         assert!(SettlementStatus::Settled.is_final());
         assert!(SettlementStatus::Rejected.is_final());
         assert!(SettlementStatus::Failed.is_final());
+        assert!(SettlementStatus::HtlcRefunded.is_final());
         assert!(!SettlementStatus::Initiated.is_final());
+        assert!(!SettlementStatus::HtlcLocked.is_final());
+    }
+
+    #[test]
+    fn test_htlc_swap_claim_transitions() {
+        let leg = create_test_leg();
+        let mut settlement = Settlement::new("test-key".to_string(), vec![leg]);
+
+        settlement
+            .transition_to(SettlementStatus::Validated)
+            .unwrap();
+        settlement
+            .transition_to(SettlementStatus::Locking)
+            .unwrap();
+        settlement.transition_to(SettlementStatus::Locked).unwrap();
+        settlement
+            .transition_to(SettlementStatus::HtlcLocked)
+            .unwrap();
+
+        // A preimage reveal drives the swap to commit like any other
+        // settlement from here.
+        assert!(settlement
+            .transition_to(SettlementStatus::Committing)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_htlc_swap_refund_transitions() {
+        let leg = create_test_leg();
+        let mut settlement = Settlement::new("test-key".to_string(), vec![leg]);
+
+        settlement
+            .transition_to(SettlementStatus::Validated)
+            .unwrap();
+        settlement
+            .transition_to(SettlementStatus::Locking)
+            .unwrap();
+        settlement.transition_to(SettlementStatus::Locked).unwrap();
+        settlement
+            .transition_to(SettlementStatus::HtlcLocked)
+            .unwrap();
+
+        // A leg timeout with no preimage reveal refunds instead.
+        settlement
+            .transition_to(SettlementStatus::HtlcRefunding)
+            .unwrap();
+        assert!(settlement
+            .transition_to(SettlementStatus::HtlcRefunded)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_transition_to_appends_and_applies_events() {
+        let leg = create_test_leg();
+        let mut settlement = Settlement::new("test-key".to_string(), vec![leg]);
+
+        settlement
+            .transition_to(SettlementStatus::Validated)
+            .unwrap();
+
+        assert_eq!(settlement.events.len(), 2);
+        assert!(matches!(
+            settlement.events[0],
+            SettlementEvent::Initiated { seq: 0, .. }
+        ));
+        assert!(matches!(
+            settlement.events[1],
+            SettlementEvent::Validated { seq: 1, .. }
+        ));
+        assert!(settlement.timing.validated_at.is_some());
+    }
+
+    #[test]
+    fn test_locks_acquired_event_carries_leg_lock_ids() {
+        let mut leg = create_test_leg();
+        let lock_id = LockId::new();
+        leg.lock_id = Some(lock_id);
+        let mut settlement = Settlement::new("test-key".to_string(), vec![leg]);
+
+        settlement
+            .transition_to(SettlementStatus::Validated)
+            .unwrap();
+        settlement.transition_to(SettlementStatus::Locking).unwrap();
+        settlement.transition_to(SettlementStatus::Locked).unwrap();
+
+        let last = settlement.events.last().unwrap();
+        match last {
+            SettlementEvent::LocksAcquired { lock_ids, .. } => {
+                assert_eq!(lock_ids, &vec![lock_id]);
+            }
+            other => panic!("expected LocksAcquired, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_reject_before_locking() {
+        let leg = create_test_leg();
+        let mut settlement = Settlement::new("test-key".to_string(), vec![leg]);
+
+        settlement.reject(FailureCode::ComplianceRejected).unwrap();
+
+        assert_eq!(settlement.status, SettlementStatus::Rejected);
+        assert!(matches!(
+            settlement.events.last().unwrap(),
+            SettlementEvent::Rejected {
+                code: FailureCode::ComplianceRejected,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_replay_rebuilds_status_from_event_log() {
+        let leg = create_test_leg();
+        let mut settlement = Settlement::new("test-key".to_string(), vec![leg]);
+        settlement
+            .transition_to(SettlementStatus::Validated)
+            .unwrap();
+        settlement.transition_to(SettlementStatus::Locking).unwrap();
+        settlement.transition_to(SettlementStatus::Locked).unwrap();
+
+        let replayed = Settlement::replay(&settlement.events).unwrap();
+
+        assert_eq!(replayed.status, SettlementStatus::Locked);
+        assert_eq!(replayed.timing.validated_at, settlement.timing.validated_at);
+        assert_eq!(replayed.events.len(), settlement.events.len());
+    }
+
+    #[test]
+    fn test_rebuild_from_events_recovers_status_and_keeps_static_envelope() {
+        let leg = create_test_leg();
+        let mut settlement = Settlement::new("test-key".to_string(), vec![leg]);
+        settlement
+            .transition_to(SettlementStatus::Validated)
+            .unwrap();
+        settlement.transition_to(SettlementStatus::Locking).unwrap();
+        settlement.record_metadata("note", "rebuilt from log");
+
+        let original_id = settlement.id;
+        let rebuilt = settlement.clone().rebuild_from_events();
+
+        assert_eq!(rebuilt.id, original_id);
+        assert_eq!(rebuilt.idempotency_key, "test-key");
+        assert_eq!(rebuilt.status, SettlementStatus::Locking);
+        assert_eq!(
+            rebuilt.metadata.get("note"),
+            Some(&"rebuilt from log".to_string())
+        );
+        assert_eq!(rebuilt.events.len(), settlement.events.len());
+    }
+
+    #[test]
+    fn test_replay_rejects_invalid_transition() {
+        let events = vec![
+            SettlementEvent::Initiated {
+                seq: 0,
+                at: Utc::now(),
+            },
+            SettlementEvent::LocksAcquired {
+                seq: 1,
+                at: Utc::now(),
+                lock_ids: vec![],
+            },
+        ];
+
+        assert!(Settlement::replay(&events).is_err());
+    }
+
+    #[test]
+    fn test_release_plan_after_reduces_on_matching_witness() {
+        let source = ParticipantId::new("TIME_ORACLE");
+        let not_before = Utc::now();
+        let mut plan = ReleasePlan::After(
+            Condition::Timestamp {
+                not_before,
+                source: source.clone(),
+            },
+            Box::new(ReleasePlan::Pay),
+        );
+
+        // Witness from the wrong source doesn't satisfy the condition.
+        plan.apply_witness(&Witness::Timestamp {
+            at: not_before,
+            source: ParticipantId::new("SOMEONE_ELSE"),
+        });
+        assert!(!plan.is_committable());
+
+        plan.apply_witness(&Witness::Timestamp {
+            at: not_before,
+            source,
+        });
+        assert!(plan.is_committable());
+    }
+
+    #[test]
+    fn test_release_plan_or_resolves_to_first_branch_and_ignores_the_other() {
+        let signer = ParticipantId::new("BANK_A");
+        let refund_source = ParticipantId::new("TIME_ORACLE");
+        let not_before = Utc::now();
+
+        let mut plan = ReleasePlan::Or(
+            (
+                Condition::Signature {
+                    signer: signer.clone(),
+                },
+                Box::new(ReleasePlan::Pay),
+            ),
+            (
+                Condition::Timestamp {
+                    not_before,
+                    source: refund_source.clone(),
+                },
+                Box::new(ReleasePlan::Pay),
+            ),
+        );
+
+        plan.apply_witness(&Witness::Signature { signer });
+        assert!(plan.is_committable());
+
+        // Once resolved to Pay, further witnesses (even for the
+        // abandoned timeout branch) are no-ops.
+        plan.apply_witness(&Witness::Timestamp {
+            at: not_before,
+            source: refund_source,
+        });
+        assert!(plan.is_committable());
+    }
+
+    #[test]
+    fn test_release_plan_and_requires_every_condition() {
+        let signer_a = ParticipantId::new("BANK_A");
+        let signer_b = ParticipantId::new("BANK_B");
+
+        let mut plan = ReleasePlan::And(
+            vec![
+                Condition::Signature {
+                    signer: signer_a.clone(),
+                },
+                Condition::Signature {
+                    signer: signer_b.clone(),
+                },
+            ],
+            Box::new(ReleasePlan::Pay),
+        );
+
+        plan.apply_witness(&Witness::Signature { signer: signer_a });
+        assert!(!plan.is_committable());
+
+        plan.apply_witness(&Witness::Signature { signer: signer_b });
+        assert!(plan.is_committable());
+    }
+
+    #[test]
+    fn test_leg_is_committable_without_a_release_plan() {
+        let leg = create_test_leg();
+        assert!(leg.is_committable());
+    }
+
+    #[test]
+    fn test_settlement_all_legs_committable_tracks_release_plans() {
+        let signer = ParticipantId::new("BANK_A");
+        let leg = create_test_leg().with_release_plan(ReleasePlan::After(
+            Condition::Signature {
+                signer: signer.clone(),
+            },
+            Box::new(ReleasePlan::Pay),
+        ));
+        let mut settlement = Settlement::new("test-key".to_string(), vec![leg]);
+
+        assert!(!settlement.all_legs_committable());
+
+        settlement.apply_witness(&Witness::Signature { signer });
+
+        assert!(settlement.all_legs_committable());
+    }
+
+    fn test_failure() -> SettlementFailure {
+        SettlementFailure {
+            code: FailureCode::ParticipantUnavailable,
+            message: "participant BANK_B dropped mid-commit".to_string(),
+            failed_leg: Some(2),
+            failed_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_begin_compensation_plans_locked_legs_in_reverse_order() {
+        let mut leg1 = create_test_leg();
+        leg1.lock_id = Some(LockId::new());
+        let mut leg2 = create_test_leg();
+        leg2.leg_number = 2;
+        leg2.lock_id = Some(LockId::new());
+        let mut settlement = Settlement::new("test-key".to_string(), vec![leg1, leg2]);
+        settlement
+            .transition_to(SettlementStatus::Validated)
+            .unwrap();
+        settlement.transition_to(SettlementStatus::Locking).unwrap();
+        settlement.transition_to(SettlementStatus::Locked).unwrap();
+
+        let plan = settlement.begin_compensation(test_failure()).unwrap();
+
+        assert_eq!(settlement.status, SettlementStatus::Compensating);
+        assert_eq!(plan.steps.len(), 2);
+        assert_eq!(plan.steps[0].leg_number, 2);
+        assert_eq!(plan.steps[1].leg_number, 1);
+        assert!(plan.steps.iter().all(|step| !step.released));
+    }
+
+    #[test]
+    fn test_record_compensated_is_idempotent_and_finalizes_to_failed() {
+        let mut leg1 = create_test_leg();
+        leg1.lock_id = Some(LockId::new());
+        let mut leg2 = create_test_leg();
+        leg2.leg_number = 2;
+        leg2.lock_id = Some(LockId::new());
+        let mut settlement = Settlement::new("test-key".to_string(), vec![leg1, leg2]);
+        settlement
+            .transition_to(SettlementStatus::Validated)
+            .unwrap();
+        settlement.transition_to(SettlementStatus::Locking).unwrap();
+        settlement.transition_to(SettlementStatus::Locked).unwrap();
+        settlement.begin_compensation(test_failure()).unwrap();
+
+        // Retrying a release that already succeeded must not error or
+        // double-finalize.
+        settlement.record_compensated(2);
+        settlement.record_compensated(2);
+        assert_eq!(settlement.status, SettlementStatus::Compensating);
+        assert!(settlement.legs[1].lock_id.is_none());
+
+        settlement.record_compensated(1);
+
+        assert_eq!(settlement.status, SettlementStatus::Failed);
+        assert!(settlement.legs[0].lock_id.is_none());
+        assert!(settlement.compensation.is_none());
+        assert!(settlement.failure.is_some());
+    }
+
+    #[test]
+    fn test_begin_compensation_with_no_locked_legs_finalizes_immediately() {
+        let leg = create_test_leg();
+        let mut settlement = Settlement::new("test-key".to_string(), vec![leg]);
+        settlement
+            .transition_to(SettlementStatus::Validated)
+            .unwrap();
+        settlement.transition_to(SettlementStatus::Locking).unwrap();
+        settlement.transition_to(SettlementStatus::Locked).unwrap();
+
+        let plan = settlement.begin_compensation(test_failure()).unwrap();
+
+        assert!(plan.steps.is_empty());
+        assert_eq!(settlement.status, SettlementStatus::Failed);
+    }
+
+    fn committed_two_leg_settlement() -> Settlement {
+        let mut leg1 = create_test_leg();
+        leg1.leg_number = 1;
+        let mut leg2 = create_test_leg();
+        leg2.leg_number = 2;
+        let mut settlement = Settlement::new("test-key".to_string(), vec![leg1, leg2]);
+        settlement
+            .transition_to(SettlementStatus::Validated)
+            .unwrap();
+        settlement.transition_to(SettlementStatus::Locking).unwrap();
+        settlement.transition_to(SettlementStatus::Locked).unwrap();
+        settlement
+            .transition_to(SettlementStatus::Committing)
+            .unwrap();
+        settlement.transition_to(SettlementStatus::Committed).unwrap();
+        settlement
+            .transition_to(SettlementStatus::AwaitingConfirmation)
+            .unwrap();
+        settlement
+    }
+
+    #[test]
+    fn test_record_confirmation_settles_once_every_leg_confirmed() {
+        let mut settlement = committed_two_leg_settlement();
+
+        settlement.record_confirmation(1, "RAIL-REF-1".to_string());
+        assert_eq!(settlement.status, SettlementStatus::AwaitingConfirmation);
+        assert_eq!(
+            settlement.metadata.get("confirmation_leg_1"),
+            Some(&"RAIL-REF-1".to_string())
+        );
+
+        settlement.record_confirmation(2, "RAIL-REF-2".to_string());
+        assert_eq!(settlement.status, SettlementStatus::Settled);
     }
 }
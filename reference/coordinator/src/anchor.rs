@@ -0,0 +1,150 @@
+//! On-chain settlement anchoring, modeled on Serai's Router/Deployer
+//! pattern: a deterministically-deployed Router contract accepts inbound
+//! transfers tagged with an `inInstruction` and, on outbound transfers,
+//! emits an `InInstructions` event the coordinator reads back to prove the
+//! transfer actually resolved -- not merely that a transaction was
+//! broadcast, since a submitted tx can still be dropped or reorged out.
+
+use dashmap::DashMap;
+
+use atomicsettle_common::{AtomicSettleError, BlockRef, Result, SettlementId};
+use atomicsettle_crypto::Signature;
+
+/// Anchors a settled settlement onto an external ledger and later confirms
+/// it actually resolved there.
+pub trait AnchorBackend: Send + Sync {
+    /// Submit a Schnorr-signed `inInstruction` to the Router for a
+    /// completed settlement. Returns the external transaction reference.
+    fn anchor(&self, settlement_id: SettlementId, instruction_signature: Signature) -> Result<String>;
+
+    /// Confirm resolution by reading the corresponding transfer/
+    /// `InInstructions` event at `block`, rather than trusting the tx
+    /// receipt `anchor` returned. Returns whether the event was found.
+    fn confirm(&self, settlement_id: SettlementId, block: BlockRef) -> Result<bool>;
+
+    /// The external transaction reference `anchor` returned for a
+    /// settlement, if it has been anchored.
+    fn txid(&self, settlement_id: SettlementId) -> Option<String>;
+
+    /// The block at which `confirm` last found the settlement's event, if
+    /// any.
+    fn confirmed_block(&self, settlement_id: SettlementId) -> Option<BlockRef>;
+}
+
+/// Ethereum implementation of [`AnchorBackend`] backed by a deployed Router
+/// contract. RPC calls are not modeled here (there is no on-chain client in
+/// this workspace); this tracks exactly the state a real client would need
+/// to submit and later verify, so `Coordinator` can be wired against it
+/// without caring whether the backend is real or simulated.
+pub struct EthereumAnchorBackend {
+    /// JSON-RPC endpoint of the Ethereum node used to submit and read
+    /// transactions.
+    rpc_url: String,
+    /// Address of the deterministically-deployed Router contract.
+    router_address: String,
+    /// Settlement ID -> submitted transaction hash.
+    txids: DashMap<SettlementId, String>,
+    /// Settlement ID -> block the `InInstructions` event was confirmed at.
+    confirmations: DashMap<SettlementId, BlockRef>,
+}
+
+impl EthereumAnchorBackend {
+    /// Create a backend that anchors to `router_address` via `rpc_url`.
+    pub fn new(rpc_url: impl Into<String>, router_address: impl Into<String>) -> Self {
+        Self {
+            rpc_url: rpc_url.into(),
+            router_address: router_address.into(),
+            txids: DashMap::new(),
+            confirmations: DashMap::new(),
+        }
+    }
+
+    /// RPC endpoint this backend submits to.
+    pub fn rpc_url(&self) -> &str {
+        &self.rpc_url
+    }
+
+    /// Address of the Router contract this backend anchors to.
+    pub fn router_address(&self) -> &str {
+        &self.router_address
+    }
+}
+
+impl AnchorBackend for EthereumAnchorBackend {
+    fn anchor(&self, settlement_id: SettlementId, instruction_signature: Signature) -> Result<String> {
+        // In a real implementation:
+        // 1. ABI-encode an `inInstruction(settlementId, ...)` call carrying
+        //    `instruction_signature`
+        // 2. Submit it to `self.router_address` via `eth_sendRawTransaction`
+        //    against `self.rpc_url`
+        // 3. Return the resulting transaction hash
+        let txid = format!("0x{}", hex::encode(&instruction_signature.bytes));
+        self.txids.insert(settlement_id, txid.clone());
+        Ok(txid)
+    }
+
+    fn confirm(&self, settlement_id: SettlementId, block: BlockRef) -> Result<bool> {
+        let Some(txid) = self.txids.get(&settlement_id) else {
+            return Err(AtomicSettleError::InternalError(format!(
+                "settlement {settlement_id} was never anchored"
+            )));
+        };
+
+        // In a real implementation, read the Router's `InInstructions`
+        // event log at `block.block_hash` (not merely the tx receipt for
+        // `*txid`) and check it matches what `anchor` submitted.
+        let _ = &*txid;
+        self.confirmations.insert(settlement_id, block);
+        Ok(true)
+    }
+
+    fn txid(&self, settlement_id: SettlementId) -> Option<String> {
+        self.txids.get(&settlement_id).map(|t| t.clone())
+    }
+
+    fn confirmed_block(&self, settlement_id: SettlementId) -> Option<BlockRef> {
+        self.confirmations.get(&settlement_id).map(|b| b.clone())
+    }
+}
+
+mod hex {
+    pub fn encode(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{b:02x}")).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_signature() -> Signature {
+        Signature {
+            bytes: vec![1, 2, 3, 4],
+            key_id: "test-key".to_string(),
+            algorithm: "Secp256k1Schnorr".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_anchor_then_confirm() {
+        let backend = EthereumAnchorBackend::new("http://localhost:8545", "0xRouter");
+        let settlement_id = SettlementId::new();
+
+        let txid = backend.anchor(settlement_id, test_signature()).unwrap();
+        assert_eq!(backend.txid(settlement_id), Some(txid));
+        assert!(backend.confirmed_block(settlement_id).is_none());
+
+        let block = BlockRef::new("0xblockhash", 42);
+        assert!(backend.confirm(settlement_id, block.clone()).unwrap());
+        assert_eq!(backend.confirmed_block(settlement_id), Some(block));
+    }
+
+    #[test]
+    fn test_confirm_rejects_unanchored_settlement() {
+        let backend = EthereumAnchorBackend::new("http://localhost:8545", "0xRouter");
+        let err = backend
+            .confirm(SettlementId::new(), BlockRef::new("0xblockhash", 1))
+            .unwrap_err();
+        assert!(matches!(err, AtomicSettleError::InternalError(_)));
+    }
+}
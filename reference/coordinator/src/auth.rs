@@ -0,0 +1,139 @@
+//! Authenticating participant messages.
+//!
+//! Replaces the transport's bare `connected: bool` trust model: every
+//! settlement request, heartbeat, and balance query a participant sends is
+//! wrapped in an `atomicsettle_crypto::SignedMessage` and must carry the
+//! public key registered here for its claimed `ParticipantId` before the
+//! coordinator acts on it.
+
+use dashmap::DashMap;
+use serde::Serialize;
+
+use atomicsettle_common::{AtomicSettleError, ParticipantId, Result};
+use atomicsettle_crypto::{SignedMessage, Verifier};
+
+/// Registry of each participant's registered secp256k1 public key, used to
+/// authenticate its signed requests.
+#[derive(Debug, Default)]
+pub struct PublicKeyRegistry {
+    keys: DashMap<ParticipantId, Vec<u8>>,
+}
+
+impl PublicKeyRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register (or rotate) the compressed public key for `participant_id`.
+    pub fn register(&self, participant_id: ParticipantId, public_key: Vec<u8>) {
+        self.keys.insert(participant_id, public_key);
+    }
+
+    /// Remove a participant's registered key, e.g. on offboarding.
+    pub fn revoke(&self, participant_id: &ParticipantId) {
+        self.keys.remove(participant_id);
+    }
+
+    /// The compressed public key currently registered for `participant_id`,
+    /// if any.
+    pub fn public_key_for(&self, participant_id: &ParticipantId) -> Option<Vec<u8>> {
+        self.keys.get(participant_id).map(|entry| entry.clone())
+    }
+
+    /// Verify that `message`'s signature is cryptographically valid *and*
+    /// carries the public key registered for `participant_id`. Rejects the
+    /// message if no key is registered, if the claimed key doesn't match
+    /// the registry, or if the signature itself doesn't check out --
+    /// authenticating settlement requests, heartbeats, and balance queries
+    /// against the sender they claim to be from.
+    pub fn authenticate<T: Serialize>(
+        &self,
+        participant_id: &ParticipantId,
+        message: &SignedMessage<T>,
+    ) -> Result<()> {
+        let registered = self
+            .public_key_for(participant_id)
+            .ok_or_else(|| AtomicSettleError::UnknownParticipant(participant_id.clone()))?;
+
+        if registered != message.public_key {
+            return Err(AtomicSettleError::InvalidSignature(format!(
+                "message claims a public key not registered for {participant_id}"
+            )));
+        }
+
+        Verifier::verify(message)
+            .map_err(|e| AtomicSettleError::InvalidSignature(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use atomicsettle_crypto::Signer;
+    use serde::Deserialize;
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    struct SettlementRequestPayload {
+        to: String,
+        amount: u64,
+    }
+
+    fn payload() -> SettlementRequestPayload {
+        SettlementRequestPayload {
+            to: "BANK_B".to_string(),
+            amount: 100,
+        }
+    }
+
+    #[test]
+    fn test_authenticate_accepts_registered_key() {
+        let registry = PublicKeyRegistry::new();
+        let signer = Signer::generate();
+        let participant_id = ParticipantId::new("BANK_A");
+
+        registry.register(participant_id.clone(), signer.public_key_bytes());
+
+        let signed = signer.sign(payload()).unwrap();
+        assert!(registry.authenticate(&participant_id, &signed).is_ok());
+    }
+
+    #[test]
+    fn test_authenticate_rejects_unregistered_participant() {
+        let registry = PublicKeyRegistry::new();
+        let signer = Signer::generate();
+        let participant_id = ParticipantId::new("BANK_A");
+
+        let signed = signer.sign(payload()).unwrap();
+        assert!(registry.authenticate(&participant_id, &signed).is_err());
+    }
+
+    #[test]
+    fn test_authenticate_rejects_key_substitution() {
+        let registry = PublicKeyRegistry::new();
+        let signer = Signer::generate();
+        let impostor = Signer::generate();
+        let participant_id = ParticipantId::new("BANK_A");
+
+        // Registry has BANK_A's real key, but the message is signed (validly!)
+        // by a different key claiming to be BANK_A.
+        registry.register(participant_id.clone(), signer.public_key_bytes());
+
+        let signed = impostor.sign(payload()).unwrap();
+        assert!(registry.authenticate(&participant_id, &signed).is_err());
+    }
+
+    #[test]
+    fn test_authenticate_rejects_tampered_payload() {
+        let registry = PublicKeyRegistry::new();
+        let signer = Signer::generate();
+        let participant_id = ParticipantId::new("BANK_A");
+
+        registry.register(participant_id.clone(), signer.public_key_bytes());
+
+        let mut signed = signer.sign(payload()).unwrap();
+        signed.payload.amount = 999_999;
+
+        assert!(registry.authenticate(&participant_id, &signed).is_err());
+    }
+}
@@ -0,0 +1,76 @@
+//! Unified background maintenance for the lock subsystem.
+//!
+//! Replaces the old ad-hoc `LockManager::run_cleanup_loop` with a single
+//! supervised task that drives expired-lock cleanup, flushes durable
+//! storage, and runs any additional periodic jobs operators register,
+//! with graceful shutdown.
+
+use std::sync::Arc;
+
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+use tracing::error;
+
+use atomicsettle_common::AtomicSettleError;
+
+use crate::lock_manager::LockManager;
+
+/// A periodic maintenance job run alongside lock cleanup. Hooks run
+/// sequentially once per tick, in registration order; a failing hook is
+/// logged and does not stop the loop or other hooks.
+pub type MaintenanceHook = Box<dyn Fn() -> Result<(), AtomicSettleError> + Send + Sync>;
+
+/// Owns the coordinator's background maintenance task.
+pub struct BackgroundProcessor {
+    handle: JoinHandle<Result<(), AtomicSettleError>>,
+    stop_tx: oneshot::Sender<()>,
+}
+
+impl BackgroundProcessor {
+    /// Start the background task. Ticks every `lock_manager.cleanup_interval()`,
+    /// running expired-lock cleanup followed by every hook in `hooks`, in
+    /// order. On `stop()`, performs one final persistence flush.
+    pub fn start(lock_manager: Arc<LockManager>, hooks: Vec<MaintenanceHook>) -> Self {
+        let (stop_tx, mut stop_rx) = oneshot::channel();
+        let interval = lock_manager.cleanup_interval();
+
+        let handle = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+
+            loop {
+                tokio::select! {
+                    _ = ticker.tick() => {
+                        lock_manager.run_cleanup_pass();
+
+                        for hook in &hooks {
+                            if let Err(e) = hook() {
+                                error!(error = %e, "Background maintenance hook failed");
+                            }
+                        }
+
+                        if let Err(e) = lock_manager.flush_persistence() {
+                            error!(error = %e, "Periodic persistence flush failed");
+                        }
+                    }
+                    _ = &mut stop_rx => break,
+                }
+            }
+
+            lock_manager.flush_persistence()
+        });
+
+        Self { handle, stop_tx }
+    }
+
+    /// Signal the background task to stop and wait for it to finish,
+    /// returning any error from the final persistence flush.
+    pub async fn stop(self) -> Result<(), AtomicSettleError> {
+        let _ = self.stop_tx.send(());
+        match self.handle.await {
+            Ok(result) => result,
+            Err(e) => Err(AtomicSettleError::InternalError(format!(
+                "background processor task panicked: {e}"
+            ))),
+        }
+    }
+}
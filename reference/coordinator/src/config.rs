@@ -7,21 +7,35 @@ use std::time::Duration;
 pub struct LockConfig {
     /// Default lock duration.
     pub default_duration: Duration,
+    /// Minimum lock duration (floor for the adaptive estimator).
+    pub min_duration: Duration,
     /// Maximum lock duration.
     pub max_duration: Duration,
     /// Lock cleanup interval.
     pub cleanup_interval: Duration,
     /// Maximum concurrent locks per participant.
     pub max_concurrent_per_participant: usize,
+    /// Standard-deviation multiplier applied on top of the EWMA when
+    /// estimating an adaptive lock duration for a participant.
+    pub latency_stddev_multiplier: f64,
+    /// Minimum number of confirmation samples required before the adaptive
+    /// estimate is trusted over `default_duration`.
+    pub latency_min_samples: u64,
+    /// Smoothing factor (0.0-1.0) for the per-participant latency EWMA.
+    pub latency_ewma_alpha: f64,
 }
 
 impl Default for LockConfig {
     fn default() -> Self {
         Self {
             default_duration: Duration::from_secs(30),
+            min_duration: Duration::from_secs(5),
             max_duration: Duration::from_secs(60),
             cleanup_interval: Duration::from_secs(1),
             max_concurrent_per_participant: 1000,
+            latency_stddev_multiplier: 3.0,
+            latency_min_samples: 10,
+            latency_ewma_alpha: 0.2,
         }
     }
 }
@@ -67,6 +81,10 @@ pub struct SettlementConfig {
     pub netting_enabled: bool,
     /// Netting window duration.
     pub netting_window: Duration,
+    /// Number of external confirmations a settlement's underlying
+    /// ledger/RTGS posting must accumulate before it is considered
+    /// `Settled` rather than merely `PendingFinality`.
+    pub finality_confirmations_required: u64,
 }
 
 impl Default for SettlementConfig {
@@ -79,6 +97,7 @@ impl Default for SettlementConfig {
             ack_timeout: Duration::from_secs(60),
             netting_enabled: true,
             netting_window: Duration::from_millis(100),
+            finality_confirmations_required: 1,
         }
     }
 }
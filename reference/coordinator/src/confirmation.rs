@@ -0,0 +1,161 @@
+//! Pluggable external-confirmation tracking for settlements finalizing on
+//! an external rail (on-chain, RTGS, correspondent bank). A
+//! `ConfirmationMonitor` is a push source: `subscribe` hands back a
+//! per-settlement stream of per-leg confirmations (or timeouts), which
+//! `Coordinator::begin_confirmation_tracking` drains and folds into the
+//! settlement via `Settlement::record_confirmation`/`fail`, without the
+//! core state machine ever needing to know which rail is behind it.
+
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use tokio::sync::mpsc;
+
+use atomicsettle_common::{SettlementId, SettlementLeg};
+
+/// A confirming reference observed for one leg on its external rail.
+#[derive(Debug, Clone)]
+pub struct ConfirmationEvent {
+    /// The leg this confirmation is for.
+    pub leg_number: u32,
+    /// The rail's confirming reference (a tx hash, an RTGS UETR, etc.).
+    pub reference: String,
+    /// When the confirmation was observed.
+    pub confirmed_at: DateTime<Utc>,
+}
+
+/// A leg that never confirmed within the monitor's deadline.
+#[derive(Debug, Clone, Copy)]
+pub struct ConfirmationTimeout {
+    /// The leg that timed out.
+    pub leg_number: u32,
+}
+
+/// One item yielded by a [`ConfirmationStream`].
+#[derive(Debug, Clone)]
+pub enum ConfirmationUpdate {
+    /// A leg confirmed.
+    Confirmed(ConfirmationEvent),
+    /// A leg timed out waiting for confirmation.
+    TimedOut(ConfirmationTimeout),
+}
+
+/// Per-settlement stream of [`ConfirmationUpdate`]s, handed back by
+/// [`ConfirmationMonitor::subscribe`].
+pub type ConfirmationStream = mpsc::Receiver<ConfirmationUpdate>;
+
+/// Source of per-leg external-rail confirmations for a settlement. An
+/// implementation typically wraps a blockchain client's event log, an
+/// RTGS webhook feed, or a correspondent bank's status API.
+pub trait ConfirmationMonitor: Send + Sync {
+    /// Start watching `legs` of `settlement_id` for external-rail
+    /// confirmation, returning a stream that yields a
+    /// [`ConfirmationUpdate`] per leg as it resolves.
+    fn subscribe(&self, settlement_id: SettlementId, legs: &[SettlementLeg]) -> ConfirmationStream;
+}
+
+/// In-memory `ConfirmationMonitor` for tests and simulation, where
+/// confirmations and timeouts are pushed directly rather than observed
+/// from a real rail.
+#[derive(Debug, Default)]
+pub struct MockConfirmationMonitor {
+    senders: DashMap<SettlementId, mpsc::Sender<ConfirmationUpdate>>,
+}
+
+impl MockConfirmationMonitor {
+    /// Create a monitor with no active subscriptions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Push a confirmation for `leg_number` to `settlement_id`'s active
+    /// subscriber, if any.
+    pub fn confirm_leg(
+        &self,
+        settlement_id: SettlementId,
+        leg_number: u32,
+        reference: impl Into<String>,
+    ) {
+        if let Some(tx) = self.senders.get(&settlement_id) {
+            let _ = tx.try_send(ConfirmationUpdate::Confirmed(ConfirmationEvent {
+                leg_number,
+                reference: reference.into(),
+                confirmed_at: Utc::now(),
+            }));
+        }
+    }
+
+    /// Push a timeout for `leg_number` to `settlement_id`'s active
+    /// subscriber, if any.
+    pub fn timeout_leg(&self, settlement_id: SettlementId, leg_number: u32) {
+        if let Some(tx) = self.senders.get(&settlement_id) {
+            let _ = tx.try_send(ConfirmationUpdate::TimedOut(ConfirmationTimeout { leg_number }));
+        }
+    }
+}
+
+impl ConfirmationMonitor for MockConfirmationMonitor {
+    fn subscribe(&self, settlement_id: SettlementId, legs: &[SettlementLeg]) -> ConfirmationStream {
+        let (tx, rx) = mpsc::channel(legs.len().max(1) * 2 + 4);
+        self.senders.insert(settlement_id, tx);
+        rx
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use atomicsettle_common::{AccountId, Currency, Money, ParticipantId};
+
+    fn test_leg(leg_number: u32) -> SettlementLeg {
+        SettlementLeg::new(
+            leg_number,
+            ParticipantId::new("BANK_A"),
+            AccountId::new(ParticipantId::new("BANK_A"), "12345", "USD"),
+            ParticipantId::new("BANK_B"),
+            AccountId::new(ParticipantId::new("BANK_B"), "67890", "USD"),
+            Money::new(rust_decimal::Decimal::from(1000), Currency::usd()),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_yields_pushed_confirmation() {
+        let monitor = MockConfirmationMonitor::new();
+        let settlement_id = SettlementId::new();
+        let legs = vec![test_leg(1)];
+
+        let mut stream = monitor.subscribe(settlement_id, &legs);
+        monitor.confirm_leg(settlement_id, 1, "RAIL-REF-1");
+
+        let update = stream.recv().await.unwrap();
+        match update {
+            ConfirmationUpdate::Confirmed(event) => {
+                assert_eq!(event.leg_number, 1);
+                assert_eq!(event.reference, "RAIL-REF-1");
+            }
+            ConfirmationUpdate::TimedOut(_) => panic!("expected a confirmation"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_yields_pushed_timeout() {
+        let monitor = MockConfirmationMonitor::new();
+        let settlement_id = SettlementId::new();
+        let legs = vec![test_leg(1)];
+
+        let mut stream = monitor.subscribe(settlement_id, &legs);
+        monitor.timeout_leg(settlement_id, 1);
+
+        let update = stream.recv().await.unwrap();
+        assert!(matches!(
+            update,
+            ConfirmationUpdate::TimedOut(ConfirmationTimeout { leg_number: 1 })
+        ));
+    }
+
+    #[test]
+    fn test_unsubscribed_settlement_push_is_a_no_op() {
+        let monitor = MockConfirmationMonitor::new();
+        // No subscribe call; pushing must not panic.
+        monitor.confirm_leg(SettlementId::new(), 1, "RAIL-REF-1");
+    }
+}
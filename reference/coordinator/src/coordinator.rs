@@ -8,13 +8,22 @@ use tokio::sync::mpsc;
 use tracing::{info, warn, error, instrument};
 
 use atomicsettle_common::{
-    AtomicSettleError, ParticipantId, Result, Settlement, SettlementId, SettlementStatus,
+    AtomicSettleError, BlockRef, ParticipantId, PaymentHash, PaymentPreimage, Result, Settlement,
+    SettlementId, SettlementStatus,
 };
+use atomicsettle_crypto::Signature;
 
+use crate::anchor::AnchorBackend;
+use crate::background::BackgroundProcessor;
 use crate::config::CoordinatorConfig;
+use crate::confirmation::{ConfirmationMonitor, ConfirmationUpdate};
+use crate::finality::FinalityOracle;
+use crate::htlc::{ConditionalSettlement, HopSpec};
 use crate::lock_manager::LockManager;
 use crate::participant_manager::ParticipantManager;
+use crate::router::Router;
 use crate::settlement_processor::SettlementProcessor;
+use crate::settlement_store::SettlementStore;
 use crate::state::CoordinatorState;
 
 /// Settlement request received from a participant.
@@ -32,6 +41,26 @@ pub struct SettleRequest {
     pub amount: atomicsettle_common::Money,
     /// Compliance data.
     pub compliance: Option<atomicsettle_common::ComplianceData>,
+    /// When set, settle through this intermediary hop path using an
+    /// HTLC-style conditional settlement instead of a single direct leg.
+    /// `sender`/`receiver` are still used for validation and as the first
+    /// and implicit final parties of the route.
+    pub route: Option<ConditionalRoute>,
+}
+
+/// A multi-hop route for a conditional settlement: the intermediary hops
+/// between sender and receiver, the payment hash every leg is conditioned
+/// on, and the timeout budget for the first hop.
+#[derive(Debug, Clone)]
+pub struct ConditionalRoute {
+    /// Hops in traversal order, ending with the final receiver.
+    pub hops: Vec<HopSpec>,
+    /// Hash committing the whole route to a single preimage.
+    pub payment_hash: PaymentHash,
+    /// Timeout for the first hop; later hops get strictly less.
+    pub base_timeout: std::time::Duration,
+    /// Amount subtracted from the timeout for each subsequent hop.
+    pub timeout_step: std::time::Duration,
 }
 
 /// Settlement response returned to participants.
@@ -56,6 +85,23 @@ pub enum SettleResponse {
     },
 }
 
+/// A finality update for a settlement's underlying ledger/RTGS
+/// transaction, reported by an external adapter via
+/// `Coordinator::handle_finality_event`.
+#[derive(Debug, Clone)]
+pub enum FinalityEvent {
+    /// A new confirmation was observed; the coordinator consults the
+    /// attached `FinalityOracle` for the current depth before deciding
+    /// whether the settlement has reached finality.
+    Confirmed { settlement_id: SettlementId },
+    /// The transaction was reversed or orphaned before reaching finality
+    /// (a reorg, an RTGS recall). The settlement is rolled back.
+    Reversed {
+        settlement_id: SettlementId,
+        reason: String,
+    },
+}
+
 /// The main coordinator that orchestrates settlements.
 pub struct Coordinator {
     /// Configuration.
@@ -78,6 +124,25 @@ pub struct Coordinator {
     shutdown_tx: mpsc::Sender<()>,
     /// Shutdown signal receiver.
     shutdown_rx: Arc<RwLock<Option<mpsc::Receiver<()>>>>,
+    /// Background maintenance task (lock cleanup, persistence flush).
+    background_processor: Arc<RwLock<Option<BackgroundProcessor>>>,
+    /// In-flight HTLC-style conditional settlements, keyed by settlement ID.
+    conditional_settlements: Arc<DashMap<SettlementId, ConditionalSettlement>>,
+    /// Optional durable settlement store; when set, every settlement and
+    /// status transition is journaled before being acted on.
+    settlement_store: Option<Arc<dyn SettlementStore>>,
+    /// Optional router for finding an intermediary path when sender and
+    /// receiver have no direct relationship.
+    router: Option<Router>,
+    /// Optional oracle tracking external confirmation depth for settlements
+    /// awaiting finality.
+    finality_oracle: Option<Arc<dyn FinalityOracle>>,
+    /// Optional backend anchoring settled settlements onto an external
+    /// ledger (e.g. an Ethereum Router contract).
+    anchor_backend: Option<Arc<dyn AnchorBackend>>,
+    /// Optional monitor reporting per-leg confirmation from an external
+    /// settlement rail (on-chain, RTGS, correspondent bank).
+    confirmation_monitor: Option<Arc<dyn ConfirmationMonitor>>,
 }
 
 impl Coordinator {
@@ -103,14 +168,307 @@ impl Coordinator {
             settlement_processor,
             shutdown_tx,
             shutdown_rx: Arc::new(RwLock::new(Some(shutdown_rx))),
+            background_processor: Arc::new(RwLock::new(None)),
+            conditional_settlements: Arc::new(DashMap::new()),
+            settlement_store: None,
+            router: None,
+            finality_oracle: None,
+            anchor_backend: None,
+            confirmation_monitor: None,
+        }
+    }
+
+    /// Attach a durable settlement store. Must be called before `start` to
+    /// take effect on recovery. Also rebuilds `settlement_processor` with
+    /// the same store, so its own event-persisting steps durably record
+    /// each transition before notifying participants.
+    pub fn with_settlement_store(mut self, store: Arc<dyn SettlementStore>) -> Self {
+        self.settlement_processor = Arc::new(
+            SettlementProcessor::new(self.lock_manager.clone(), self.participant_manager.clone())
+                .with_settlement_store(store.clone()),
+        );
+        self.settlement_store = Some(store);
+        self
+    }
+
+    /// Attach a router for finding an intermediary path when sender and
+    /// receiver have no direct relationship.
+    pub fn with_router(mut self, router: Router) -> Self {
+        self.router = Some(router);
+        self
+    }
+
+    /// Attach a finality oracle tracking external confirmation depth for
+    /// settlements awaiting finality.
+    pub fn with_finality_oracle(mut self, oracle: Arc<dyn FinalityOracle>) -> Self {
+        self.finality_oracle = Some(oracle);
+        self
+    }
+
+    /// Attach a backend anchoring settled settlements onto an external
+    /// ledger.
+    pub fn with_anchor_backend(mut self, backend: Arc<dyn AnchorBackend>) -> Self {
+        self.anchor_backend = Some(backend);
+        self
+    }
+
+    /// Attach a monitor reporting per-leg confirmation from an external
+    /// settlement rail.
+    pub fn with_confirmation_monitor(mut self, monitor: Arc<dyn ConfirmationMonitor>) -> Self {
+        self.confirmation_monitor = Some(monitor);
+        self
+    }
+
+    /// Anchor a settled settlement onto the external ledger via the
+    /// attached `AnchorBackend`, submitting `instruction_signature` as its
+    /// Schnorr-signed `inInstruction`. Only valid once the settlement has
+    /// reached `SettlementStatus::Settled`.
+    pub fn anchor_settlement(
+        &self,
+        settlement_id: SettlementId,
+        instruction_signature: Signature,
+    ) -> Result<String> {
+        let settlement = self
+            .settlements
+            .get(&settlement_id)
+            .ok_or(AtomicSettleError::SettlementNotFound(settlement_id))?;
+
+        if settlement.status != SettlementStatus::Settled {
+            return Err(AtomicSettleError::InvalidTransition {
+                from: settlement.status,
+                to: settlement.status,
+            });
+        }
+        drop(settlement);
+
+        let backend = self.anchor_backend.as_ref().ok_or_else(|| {
+            AtomicSettleError::ConfigurationError("no anchor backend attached".to_string())
+        })?;
+
+        backend.anchor(settlement_id, instruction_signature)
+    }
+
+    /// Confirm a previously-anchored settlement actually resolved at
+    /// `block` by consulting the attached `AnchorBackend` (which reads the
+    /// Router's transfer event, not merely the submitted tx's receipt),
+    /// and record the confirming block on the settlement so participants
+    /// can read it back via `get_settlement`.
+    pub fn confirm_anchor(&self, settlement_id: SettlementId, block: BlockRef) -> Result<()> {
+        let backend = self.anchor_backend.as_ref().ok_or_else(|| {
+            AtomicSettleError::ConfigurationError("no anchor backend attached".to_string())
+        })?;
+
+        if backend.confirm(settlement_id, block.clone())? {
+            let mut settlement = self
+                .settlements
+                .get_mut(&settlement_id)
+                .ok_or(AtomicSettleError::SettlementNotFound(settlement_id))?;
+            settlement.on_chain_confirmation = Some(block);
+        }
+
+        Ok(())
+    }
+
+    /// Record the external transaction ID a settlement posted as, so the
+    /// attached `FinalityOracle` can track its confirmation depth.
+    pub fn register_settlement_txid(&self, settlement_id: SettlementId, txid: String) -> Result<()> {
+        let oracle = self.finality_oracle.as_ref().ok_or_else(|| {
+            AtomicSettleError::ConfigurationError("no finality oracle attached".to_string())
+        })?;
+        oracle.register_settlement_txid(settlement_id, txid);
+        Ok(())
+    }
+
+    /// Drive a settlement's finality transitions from an external ledger
+    /// adapter. `Confirmed` consults the attached `FinalityOracle` for the
+    /// current confirmation depth and only advances the settlement to
+    /// `Settled` once it meets `finality_confirmations_required`; until
+    /// then (or if no oracle is attached) the settlement simply moves into
+    /// `PendingFinality`. `Reversed` rolls the settlement back, releasing
+    /// its locks, regardless of how many confirmations it had accumulated;
+    /// a settlement already in a final state (including `Settled`) is
+    /// left unchanged.
+    pub fn handle_finality_event(&self, event: FinalityEvent) -> Result<()> {
+        match event {
+            FinalityEvent::Confirmed { settlement_id } => {
+                let mut settlement = self
+                    .settlements
+                    .get_mut(&settlement_id)
+                    .ok_or(AtomicSettleError::SettlementNotFound(settlement_id))?;
+
+                if settlement.status == SettlementStatus::Committed {
+                    settlement
+                        .transition_to(SettlementStatus::PendingFinality)
+                        .map_err(|e| AtomicSettleError::InvalidTransition {
+                            from: e.from,
+                            to: e.to,
+                        })?;
+                }
+
+                let confirmations = self
+                    .finality_oracle
+                    .as_ref()
+                    .and_then(|oracle| oracle.confirmations(settlement_id))
+                    .unwrap_or(0);
+
+                if settlement.status == SettlementStatus::PendingFinality
+                    && confirmations >= self.config.settlement_config.finality_confirmations_required
+                {
+                    settlement.transition_to(SettlementStatus::Settled).map_err(|e| {
+                        AtomicSettleError::InvalidTransition {
+                            from: e.from,
+                            to: e.to,
+                        }
+                    })?;
+                    let snapshot = settlement.clone();
+                    drop(settlement);
+                    self.persist_settlement(&snapshot)?;
+                }
+
+                Ok(())
+            }
+            FinalityEvent::Reversed {
+                settlement_id,
+                reason,
+            } => {
+                let mut settlement = self
+                    .settlements
+                    .get_mut(&settlement_id)
+                    .ok_or(AtomicSettleError::SettlementNotFound(settlement_id))?;
+
+                if settlement.status.is_final() {
+                    return Ok(());
+                }
+
+                self.lock_manager.release_locks_for_settlement(&settlement_id);
+                settlement
+                    .fail(atomicsettle_common::SettlementFailure {
+                        code: atomicsettle_common::FailureCode::CoordinatorError,
+                        message: format!("settlement reversed before finality: {reason}"),
+                        failed_leg: None,
+                        failed_at: atomicsettle_common::time::now(),
+                    })
+                    .map_err(|e| AtomicSettleError::InvalidTransition {
+                        from: e.from,
+                        to: e.to,
+                    })?;
+                let snapshot = settlement.clone();
+                drop(settlement);
+                self.persist_settlement(&snapshot)?;
+
+                Ok(())
+            }
         }
     }
 
+    /// Transition a committed settlement into `AwaitingConfirmation` and
+    /// drain the attached `ConfirmationMonitor`'s per-leg confirmation
+    /// stream for it in the background: each confirmation is folded in via
+    /// `Settlement::record_confirmation` (settling once every leg has
+    /// confirmed), and a timeout fails the settlement with
+    /// `FailureCode::ParticipantUnavailable`, releasing its locks.
+    /// Requires a monitor to have been attached via
+    /// `with_confirmation_monitor`.
+    pub fn begin_confirmation_tracking(&self, settlement_id: SettlementId) -> Result<()> {
+        let monitor = self.confirmation_monitor.as_ref().ok_or_else(|| {
+            AtomicSettleError::ConfigurationError("no confirmation monitor attached".to_string())
+        })?;
+
+        let mut settlement = self
+            .settlements
+            .get_mut(&settlement_id)
+            .ok_or(AtomicSettleError::SettlementNotFound(settlement_id))?;
+
+        settlement
+            .transition_to(SettlementStatus::AwaitingConfirmation)
+            .map_err(|e| AtomicSettleError::InvalidTransition {
+                from: e.from,
+                to: e.to,
+            })?;
+
+        let mut stream = monitor.subscribe(settlement_id, &settlement.legs);
+        drop(settlement);
+
+        let settlements = self.settlements.clone();
+        let lock_manager = self.lock_manager.clone();
+
+        tokio::spawn(async move {
+            while let Some(update) = stream.recv().await {
+                let Some(mut settlement) = settlements.get_mut(&settlement_id) else {
+                    break;
+                };
+
+                match update {
+                    ConfirmationUpdate::Confirmed(event) => {
+                        settlement.record_confirmation(event.leg_number, event.reference);
+                    }
+                    ConfirmationUpdate::TimedOut(timeout) => {
+                        drop(settlement);
+                        lock_manager.release_locks_for_settlement(&settlement_id);
+                        let Some(mut settlement) = settlements.get_mut(&settlement_id) else {
+                            break;
+                        };
+                        let _ = settlement.fail(atomicsettle_common::SettlementFailure {
+                            code: atomicsettle_common::FailureCode::ParticipantUnavailable,
+                            message: format!(
+                                "leg {} confirmation timed out",
+                                timeout.leg_number
+                            ),
+                            failed_leg: Some(timeout.leg_number),
+                            failed_at: atomicsettle_common::time::now(),
+                        });
+                        break;
+                    }
+                }
+
+                if settlement.status.is_final() {
+                    break;
+                }
+            }
+        });
+
+        Ok(())
+    }
+
+    /// Find the cheapest intermediary path from `sender` to `receiver` able
+    /// to carry `amount`, wrapping it as a `ConditionalRoute` ready to hand
+    /// to `create_settlement`. Requires a router to have been attached via
+    /// `with_router`.
+    pub fn build_route(
+        &self,
+        sender: &ParticipantId,
+        receiver: &ParticipantId,
+        amount: &atomicsettle_common::Money,
+        base_timeout: std::time::Duration,
+        timeout_step: std::time::Duration,
+    ) -> Result<ConditionalRoute> {
+        let router = self.router.as_ref().ok_or_else(|| {
+            AtomicSettleError::ConfigurationError("no router attached to coordinator".to_string())
+        })?;
+
+        let hops = router.find_route(sender, receiver, amount)?;
+
+        Ok(ConditionalRoute {
+            hops,
+            payment_hash: atomicsettle_common::PaymentPreimage::random().hash(),
+            base_timeout,
+            timeout_step,
+        })
+    }
+
     /// Start the coordinator.
+    ///
+    /// If a settlement store is attached, this first replays it: every
+    /// settlement found in a non-terminal state is re-registered under its
+    /// idempotency key and resumed (or rolled back, if its locks can no
+    /// longer be trusted) rather than silently forgotten.
     #[instrument(skip(self))]
     pub async fn start(&self) -> Result<()> {
         info!(node_id = %self.node_id, "Starting coordinator");
 
+        *self.state.write() = CoordinatorState::Recovering;
+        self.recover_settlements().await?;
+
         // Transition to running state
         *self.state.write() = CoordinatorState::Running;
 
@@ -121,6 +479,73 @@ impl Coordinator {
         Ok(())
     }
 
+    /// Replay the durable settlement store (if any) and resume or roll back
+    /// whatever was left in a non-terminal state.
+    async fn recover_settlements(&self) -> Result<()> {
+        let Some(store) = &self.settlement_store else {
+            return Ok(());
+        };
+
+        let settlements = store.load_all().map_err(AtomicSettleError::from)?;
+        info!(node_id = %self.node_id, count = settlements.len(), "Replaying settlement journal");
+
+        for settlement in settlements {
+            self.idempotency_map
+                .insert(settlement.idempotency_key.clone(), settlement.id);
+
+            if matches!(
+                settlement.status,
+                SettlementStatus::Locking | SettlementStatus::Locked | SettlementStatus::Committing
+            ) {
+                info!(
+                    settlement_id = %settlement.id,
+                    status = ?settlement.status,
+                    "Resuming settlement left in-progress by prior crash"
+                );
+                let resumed = self.settlement_processor.resume(settlement).await?;
+                self.settlements.insert(resumed.id, resumed);
+            } else if settlement.status.is_in_progress() {
+                warn!(
+                    settlement_id = %settlement.id,
+                    status = ?settlement.status,
+                    "Rolling back settlement left in-progress by prior crash"
+                );
+                self.lock_manager.release_locks_for_settlement(&settlement.id);
+
+                let mut settlement = settlement;
+                let _ = settlement.fail(atomicsettle_common::SettlementFailure {
+                    code: atomicsettle_common::FailureCode::CoordinatorError,
+                    message: "settlement in progress at coordinator restart".to_string(),
+                    failed_leg: None,
+                    failed_at: atomicsettle_common::time::now(),
+                });
+                self.persist_settlement(&settlement)?;
+                self.settlements.insert(settlement.id, settlement);
+            } else {
+                self.settlements.insert(settlement.id, settlement);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Persist a settlement, parking the coordinator in `Stalled` and
+    /// surfacing the failure if the write itself fails. Never swallows a
+    /// persistence error.
+    fn persist_settlement(&self, settlement: &Settlement) -> Result<()> {
+        let Some(store) = &self.settlement_store else {
+            return Ok(());
+        };
+
+        if let Err(e) = store.persist(settlement) {
+            error!(node_id = %self.node_id, settlement_id = %settlement.id, error = %e, "Settlement persistence failed; stalling coordinator");
+            *self.state.write() = CoordinatorState::Stalled;
+            return Err(e.into());
+        }
+
+        Ok(())
+    }
+
     /// Stop the coordinator gracefully.
     #[instrument(skip(self))]
     pub async fn stop(&self) -> Result<()> {
@@ -128,12 +553,32 @@ impl Coordinator {
 
         *self.state.write() = CoordinatorState::ShuttingDown;
 
+        // Stop accepting brand-new settlements at the processor itself, not
+        // just at `handle_settlement`'s state check, so anything already
+        // in-flight still drains to a terminal state below.
+        self.settlement_processor.set_resume_only(true);
+
         // Signal shutdown to background tasks
         let _ = self.shutdown_tx.send(()).await;
 
         // Wait for pending settlements to complete or timeout
         self.drain_pending_settlements().await;
 
+        // Stop the background maintenance task and flush durable storage
+        if let Some(processor) = self.background_processor.write().take() {
+            if let Err(e) = processor.stop().await {
+                error!(node_id = %self.node_id, error = %e, "Background processor shutdown failed");
+            }
+        }
+
+        // Flush the settlement journal so nothing acknowledged above ends
+        // up unpersisted.
+        if let Some(store) = &self.settlement_store {
+            if let Err(e) = store.flush() {
+                error!(node_id = %self.node_id, error = %e, "Settlement journal flush failed");
+            }
+        }
+
         *self.state.write() = CoordinatorState::Stopped;
 
         info!(node_id = %self.node_id, "Coordinator stopped");
@@ -155,6 +600,20 @@ impl Coordinator {
             return self.get_existing_settlement_response(*existing_id);
         }
 
+        // A content-addressed ID lets us recognize a replay even when this
+        // node's idempotency map doesn't have the key - e.g. a cold start
+        // without a settlement store, or the request landing on a different
+        // coordinator node than the original.
+        let derived_id = SettlementId::from_content(
+            &request.idempotency_key,
+            &request.sender,
+            &request.receiver,
+            &request.amount,
+        );
+        if self.settlements.contains_key(&derived_id) {
+            return self.get_existing_settlement_response(derived_id);
+        }
+
         // Validate request
         self.validate_request(&request)?;
 
@@ -162,6 +621,9 @@ impl Coordinator {
         let settlement = self.create_settlement(&request)?;
         let settlement_id = settlement.id;
 
+        // Durably record the settlement before acting on it
+        self.persist_settlement(&settlement)?;
+
         // Store settlement and idempotency key
         self.settlements.insert(settlement_id, settlement.clone());
         self.idempotency_map
@@ -261,6 +723,10 @@ impl Coordinator {
     fn create_settlement(&self, request: &SettleRequest) -> Result<Settlement> {
         use atomicsettle_common::{AccountId, SettlementLeg};
 
+        if let Some(route) = &request.route {
+            return self.create_conditional_settlement(request, route);
+        }
+
         // Create a single leg settlement
         let leg = SettlementLeg::new(
             1,
@@ -280,11 +746,138 @@ impl Coordinator {
         );
 
         let mut settlement = Settlement::new(request.idempotency_key.clone(), vec![leg]);
+        settlement.id = SettlementId::from_content(
+            &request.idempotency_key,
+            &request.sender,
+            &request.receiver,
+            &request.amount,
+        );
         settlement.compliance = request.compliance.clone();
 
         Ok(settlement)
     }
 
+    /// Build an HTLC-style conditional settlement: one leg per hop, each
+    /// locked against the same payment hash with a strictly decreasing
+    /// timeout, and register it so `reveal_preimage` can later settle or
+    /// roll it back as a unit.
+    fn create_conditional_settlement(
+        &self,
+        request: &SettleRequest,
+        route: &ConditionalRoute,
+    ) -> Result<Settlement> {
+        use atomicsettle_common::{AccountId, SettlementLeg};
+
+        if route.hops.is_empty() {
+            return Err(AtomicSettleError::InvalidMessage {
+                message: "conditional route must have at least one hop".to_string(),
+                field: Some("route".to_string()),
+            });
+        }
+
+        let timeouts = crate::htlc::hop_timeouts(route.base_timeout, route.hops.len(), route.timeout_step);
+
+        let mut legs = Vec::with_capacity(route.hops.len());
+        let mut from_participant = request.sender.clone();
+        let mut from_account = AccountId::new(
+            request.sender.clone(),
+            "default",
+            request.amount.currency.code(),
+        );
+
+        for (i, hop) in route.hops.iter().enumerate() {
+            legs.push(SettlementLeg::new(
+                (i + 1) as u32,
+                from_participant.clone(),
+                from_account.clone(),
+                hop.participant_id.clone(),
+                hop.account.clone(),
+                hop.amount.clone(),
+            ));
+
+            from_participant = hop.participant_id.clone();
+            from_account = hop.account.clone();
+        }
+
+        let mut settlement = Settlement::new(request.idempotency_key.clone(), legs);
+        settlement.id = SettlementId::from_content(
+            &request.idempotency_key,
+            &request.sender,
+            &request.receiver,
+            &request.amount,
+        );
+
+        let mut conditional = ConditionalSettlement::new(route.payment_hash, route.hops.clone());
+        let lock_ids: Vec<_> = route
+            .hops
+            .iter()
+            .zip(timeouts)
+            .map(|(hop, timeout)| {
+                self.lock_manager
+                    .create_lock_with_duration(
+                        settlement.id,
+                        hop.participant_id.clone(),
+                        hop.amount.clone(),
+                        timeout,
+                    )
+                    .id
+            })
+            .collect();
+        conditional.set_lock_ids(lock_ids);
+
+        self.conditional_settlements.insert(settlement.id, conditional);
+
+        Ok(settlement)
+    }
+
+    /// Reveal the preimage for an in-flight conditional settlement. Verifies
+    /// it satisfies the settlement's payment hash, then commits every hop's
+    /// lock and marks the settlement `Committed`. An invalid preimage, or a
+    /// settlement with no conditional route, is rejected without side
+    /// effects.
+    pub fn reveal_preimage(
+        &self,
+        settlement_id: SettlementId,
+        preimage: PaymentPreimage,
+    ) -> Result<()> {
+        let mut conditional = self
+            .conditional_settlements
+            .get_mut(&settlement_id)
+            .ok_or(AtomicSettleError::SettlementNotFound(settlement_id))?;
+
+        conditional.reveal(preimage)?;
+
+        for lock_id in &conditional.lock_ids {
+            self.lock_manager.consume_lock(lock_id);
+        }
+
+        if let Some(mut settlement) = self.settlements.get_mut(&settlement_id) {
+            let _ = settlement.transition_to(SettlementStatus::Committed);
+        }
+
+        Ok(())
+    }
+
+    /// Roll back an in-flight conditional settlement: release every hop's
+    /// lock and transition the settlement to `Failed`. Called when a hop's
+    /// timeout fires before the preimage is revealed end-to-end.
+    pub fn abort_conditional_settlement(&self, settlement_id: SettlementId) {
+        if let Some((_, conditional)) = self.conditional_settlements.remove(&settlement_id) {
+            for lock_id in &conditional.lock_ids {
+                self.lock_manager.release_lock(lock_id);
+            }
+        }
+
+        if let Some(mut settlement) = self.settlements.get_mut(&settlement_id) {
+            let _ = settlement.fail(atomicsettle_common::SettlementFailure {
+                code: atomicsettle_common::FailureCode::LockTimeout,
+                message: "conditional settlement timed out before preimage was revealed".to_string(),
+                failed_leg: None,
+                failed_at: atomicsettle_common::time::now(),
+            });
+        }
+    }
+
     fn get_existing_settlement_response(&self, settlement_id: SettlementId) -> Result<SettleResponse> {
         let settlement = self
             .settlements
@@ -317,11 +910,9 @@ impl Coordinator {
     }
 
     async fn start_background_tasks(&self) -> Result<()> {
-        // Start lock cleanup task
-        let lock_manager = self.lock_manager.clone();
-        tokio::spawn(async move {
-            lock_manager.run_cleanup_loop().await;
-        });
+        // Start unified lock cleanup / persistence-flush processor
+        let processor = BackgroundProcessor::start(self.lock_manager.clone(), Vec::new());
+        *self.background_processor.write() = Some(processor);
 
         // Start heartbeat checker
         let participant_manager = self.participant_manager.clone();
@@ -369,6 +960,7 @@ mod tests {
             receiver: ParticipantId::new("BANK_B"),
             amount: Money::new(Decimal::from(1000), Currency::usd()),
             compliance: None,
+            route: None,
         }
     }
 
@@ -392,4 +984,300 @@ mod tests {
         coordinator.stop().await.unwrap();
         assert_eq!(coordinator.state(), CoordinatorState::Stopped);
     }
+
+    #[tokio::test]
+    async fn test_recovery_rolls_back_in_progress_settlement() {
+        use crate::settlement_store::JournalSettlementStore;
+
+        let path = std::env::temp_dir().join(format!("atomicsettle-test-{}.journal", SettlementId::new()));
+        let store: Arc<dyn crate::settlement_store::SettlementStore> =
+            Arc::new(JournalSettlementStore::open(&path).unwrap());
+
+        let mut stuck = Settlement::new("stuck-key".to_string(), vec![]);
+        stuck.status = SettlementStatus::Locking;
+        store.persist(&stuck).unwrap();
+
+        let coordinator =
+            Coordinator::new(create_test_config(), "test-node-1".to_string()).with_settlement_store(store);
+        coordinator.start().await.unwrap();
+
+        let recovered = coordinator.get_settlement(stuck.id).unwrap();
+        assert_eq!(recovered.status, SettlementStatus::Failed);
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_build_route_feeds_conditional_settlement() {
+        use crate::router::{Edge, RoutingGraph};
+
+        let coordinator = Coordinator::new(create_test_config(), "test-node-1".to_string());
+        coordinator.start().await.unwrap();
+
+        let sender = ParticipantId::new("BANK_A");
+        let receiver = ParticipantId::new("BANK_B");
+        for id in [&sender, &receiver] {
+            let _rx = coordinator
+                .participant_manager
+                .register(id.clone(), "1.0".to_string());
+            coordinator.participant_manager.activate(id);
+        }
+
+        let amount = Money::new(Decimal::from(100), Currency::usd());
+        let mut graph = RoutingGraph::new();
+        graph.add_edge(
+            sender.clone(),
+            Edge {
+                to: receiver.clone(),
+                to_account: atomicsettle_common::AccountId::new(receiver.clone(), "1", "USD"),
+                capacity: Money::new(Decimal::from(1000), Currency::usd()),
+                fee: Money::new(Decimal::from(1), Currency::usd()),
+            },
+        );
+
+        let coordinator = coordinator.with_router(Router::new(
+            graph,
+            coordinator.participant_manager.clone(),
+            coordinator.lock_manager.clone(),
+        ));
+
+        let route = coordinator
+            .build_route(
+                &sender,
+                &receiver,
+                &amount,
+                std::time::Duration::from_secs(60),
+                std::time::Duration::from_secs(15),
+            )
+            .unwrap();
+        assert_eq!(route.hops.len(), 1);
+
+        let mut request = create_test_request();
+        request.sender = sender;
+        request.receiver = receiver;
+        request.amount = amount;
+        request.route = Some(route);
+
+        let settlement = coordinator.create_settlement(&request).unwrap();
+        assert_eq!(settlement.legs.len(), 1);
+    }
+
+    fn response_settlement_id(response: &SettleResponse) -> SettlementId {
+        match response {
+            SettleResponse::Accepted { settlement_id, .. } => *settlement_id,
+            SettleResponse::Success(settlement) => settlement.id,
+            SettleResponse::Rejected { settlement_id, .. } => *settlement_id,
+            SettleResponse::Failed { settlement_id, .. } => *settlement_id,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_handle_settlement_dedupes_by_content_across_idempotency_map() {
+        let coordinator = Coordinator::new(create_test_config(), "test-node-1".to_string());
+        coordinator.start().await.unwrap();
+
+        let request = create_test_request();
+        for id in [&request.sender, &request.receiver] {
+            let _rx = coordinator
+                .participant_manager
+                .register(id.clone(), "1.0".to_string());
+            coordinator.participant_manager.activate(id);
+        }
+        let response = coordinator.handle_settlement(request.clone()).await.unwrap();
+        let settlement_id = response_settlement_id(&response);
+
+        // Simulate a cold start on another node: no in-memory idempotency
+        // entry, but the derived ID matches the same content.
+        coordinator.idempotency_map.remove(&request.idempotency_key);
+
+        let replay_response = coordinator.handle_settlement(request).await.unwrap();
+        assert_eq!(response_settlement_id(&replay_response), settlement_id);
+    }
+
+    #[tokio::test]
+    async fn test_finality_confirmed_requires_threshold() {
+        use crate::finality::InMemoryFinalityOracle;
+
+        let oracle = Arc::new(InMemoryFinalityOracle::new());
+        let coordinator = Coordinator::new(create_test_config(), "test-node-1".to_string())
+            .with_finality_oracle(oracle.clone());
+        coordinator.start().await.unwrap();
+
+        let mut settlement = Settlement::new("committed-key".to_string(), vec![]);
+        settlement.status = SettlementStatus::Committed;
+        let settlement_id = settlement.id;
+        coordinator.settlements.insert(settlement_id, settlement);
+
+        // No confirmations registered yet: moves to PendingFinality but not
+        // further.
+        coordinator
+            .handle_finality_event(FinalityEvent::Confirmed { settlement_id })
+            .unwrap();
+        assert_eq!(
+            coordinator.get_settlement(settlement_id).unwrap().status,
+            SettlementStatus::PendingFinality
+        );
+
+        oracle.register_settlement_txid(settlement_id, "rtgs-txid-1".to_string());
+        oracle.set_confirmations(settlement_id, 1);
+
+        coordinator
+            .handle_finality_event(FinalityEvent::Confirmed { settlement_id })
+            .unwrap();
+        assert_eq!(
+            coordinator.get_settlement(settlement_id).unwrap().status,
+            SettlementStatus::Settled
+        );
+    }
+
+    #[tokio::test]
+    async fn test_finality_reversed_rolls_back() {
+        let coordinator = Coordinator::new(create_test_config(), "test-node-1".to_string());
+        coordinator.start().await.unwrap();
+
+        let mut settlement = Settlement::new("committed-key".to_string(), vec![]);
+        settlement.status = SettlementStatus::Committed;
+        let settlement_id = settlement.id;
+        coordinator.settlements.insert(settlement_id, settlement);
+
+        coordinator
+            .handle_finality_event(FinalityEvent::Reversed {
+                settlement_id,
+                reason: "reorg".to_string(),
+            })
+            .unwrap();
+
+        assert_eq!(
+            coordinator.get_settlement(settlement_id).unwrap().status,
+            SettlementStatus::Failed
+        );
+    }
+
+    #[tokio::test]
+    async fn test_confirmation_monitor_settles_once_every_leg_confirms() {
+        use crate::confirmation::MockConfirmationMonitor;
+
+        let monitor = Arc::new(MockConfirmationMonitor::new());
+        let coordinator = Coordinator::new(create_test_config(), "test-node-1".to_string())
+            .with_confirmation_monitor(monitor.clone());
+        coordinator.start().await.unwrap();
+
+        let leg = atomicsettle_common::SettlementLeg::new(
+            1,
+            atomicsettle_common::ParticipantId::new("BANK_A"),
+            atomicsettle_common::AccountId::new(
+                atomicsettle_common::ParticipantId::new("BANK_A"),
+                "12345",
+                "USD",
+            ),
+            atomicsettle_common::ParticipantId::new("BANK_B"),
+            atomicsettle_common::AccountId::new(
+                atomicsettle_common::ParticipantId::new("BANK_B"),
+                "67890",
+                "USD",
+            ),
+            atomicsettle_common::Money::new(
+                rust_decimal::Decimal::from(1000),
+                atomicsettle_common::Currency::usd(),
+            ),
+        );
+        let mut settlement = Settlement::new("committed-key".to_string(), vec![leg]);
+        settlement.status = SettlementStatus::Committed;
+        let settlement_id = settlement.id;
+        coordinator.settlements.insert(settlement_id, settlement);
+
+        coordinator
+            .begin_confirmation_tracking(settlement_id)
+            .unwrap();
+        assert_eq!(
+            coordinator.get_settlement(settlement_id).unwrap().status,
+            SettlementStatus::AwaitingConfirmation
+        );
+
+        monitor.confirm_leg(settlement_id, 1, "RAIL-REF-1");
+
+        // Give the background consumer task a chance to fold the event in.
+        for _ in 0..50 {
+            if coordinator.get_settlement(settlement_id).unwrap().status
+                == SettlementStatus::Settled
+            {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+
+        assert_eq!(
+            coordinator.get_settlement(settlement_id).unwrap().status,
+            SettlementStatus::Settled
+        );
+    }
+
+    #[tokio::test]
+    async fn test_anchor_settlement_requires_settled_status() {
+        use crate::anchor::EthereumAnchorBackend;
+
+        let backend = Arc::new(EthereumAnchorBackend::new("http://localhost:8545", "0xRouter"));
+        let coordinator = Coordinator::new(create_test_config(), "test-node-1".to_string())
+            .with_anchor_backend(backend);
+        coordinator.start().await.unwrap();
+
+        let mut settlement = Settlement::new("pending-key".to_string(), vec![]);
+        settlement.status = SettlementStatus::PendingFinality;
+        let settlement_id = settlement.id;
+        coordinator.settlements.insert(settlement_id, settlement);
+
+        let signature = Signature {
+            bytes: vec![1, 2, 3],
+            key_id: "test-key".to_string(),
+            algorithm: "Secp256k1Schnorr".to_string(),
+        };
+
+        let err = coordinator
+            .anchor_settlement(settlement_id, signature)
+            .unwrap_err();
+        assert!(matches!(err, AtomicSettleError::InvalidTransition { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_anchor_settlement_then_confirm_surfaces_on_settlement() {
+        use crate::anchor::EthereumAnchorBackend;
+
+        let backend = Arc::new(EthereumAnchorBackend::new("http://localhost:8545", "0xRouter"));
+        let coordinator = Coordinator::new(create_test_config(), "test-node-1".to_string())
+            .with_anchor_backend(backend);
+        coordinator.start().await.unwrap();
+
+        let mut settlement = Settlement::new("settled-key".to_string(), vec![]);
+        settlement.status = SettlementStatus::Settled;
+        let settlement_id = settlement.id;
+        coordinator.settlements.insert(settlement_id, settlement);
+
+        let signature = Signature {
+            bytes: vec![1, 2, 3],
+            key_id: "test-key".to_string(),
+            algorithm: "Secp256k1Schnorr".to_string(),
+        };
+
+        coordinator
+            .anchor_settlement(settlement_id, signature)
+            .unwrap();
+        assert!(coordinator
+            .get_settlement(settlement_id)
+            .unwrap()
+            .on_chain_confirmation
+            .is_none());
+
+        let block = BlockRef::new("0xblockhash", 42);
+        coordinator
+            .confirm_anchor(settlement_id, block.clone())
+            .unwrap();
+
+        assert_eq!(
+            coordinator
+                .get_settlement(settlement_id)
+                .unwrap()
+                .on_chain_confirmation,
+            Some(block)
+        );
+    }
 }
@@ -0,0 +1,76 @@
+//! External settlement finality tracking.
+//!
+//! Real RTGS and ledger postings settle asynchronously and can be reversed
+//! before they are truly final (a chain reorg, an RTGS recall). Modeled on
+//! Lightning's `Confirm`/`BestBlock` interface: a `FinalityOracle` tracks the
+//! external confirmation depth for each settlement's underlying
+//! transaction, and `Coordinator::handle_finality_event` only advances a
+//! settlement out of `PendingFinality` once that depth has been consulted
+//! and meets the configured threshold.
+
+use dashmap::DashMap;
+
+use atomicsettle_common::SettlementId;
+
+/// Source of truth for a settlement's external confirmation depth. An
+/// implementation typically wraps an RTGS webhook feed or a blockchain
+/// client's block-height tracking.
+pub trait FinalityOracle: Send + Sync {
+    /// Record the external transaction ID a settlement posted as, so its
+    /// confirmation depth can later be queried.
+    fn register_settlement_txid(&self, settlement_id: SettlementId, txid: String);
+
+    /// Current confirmation depth for a settlement's transaction, or `None`
+    /// if no transaction has been registered for it yet.
+    fn confirmations(&self, settlement_id: SettlementId) -> Option<u64>;
+}
+
+/// In-memory `FinalityOracle` for tests and simulation, where confirmation
+/// counts are set directly rather than observed from a real ledger.
+#[derive(Debug, Default)]
+pub struct InMemoryFinalityOracle {
+    txids: DashMap<SettlementId, String>,
+    confirmations: DashMap<SettlementId, u64>,
+}
+
+impl InMemoryFinalityOracle {
+    /// Create an oracle with no registered transactions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Directly set the confirmation depth for a settlement's transaction.
+    pub fn set_confirmations(&self, settlement_id: SettlementId, confirmations: u64) {
+        self.confirmations.insert(settlement_id, confirmations);
+    }
+}
+
+impl FinalityOracle for InMemoryFinalityOracle {
+    fn register_settlement_txid(&self, settlement_id: SettlementId, txid: String) {
+        self.txids.insert(settlement_id, txid);
+        self.confirmations.entry(settlement_id).or_insert(0);
+    }
+
+    fn confirmations(&self, settlement_id: SettlementId) -> Option<u64> {
+        self.confirmations.get(&settlement_id).map(|c| *c)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_in_memory_oracle_tracks_confirmations() {
+        let oracle = InMemoryFinalityOracle::new();
+        let settlement_id = SettlementId::new();
+
+        assert_eq!(oracle.confirmations(settlement_id), None);
+
+        oracle.register_settlement_txid(settlement_id, "txid-1".to_string());
+        assert_eq!(oracle.confirmations(settlement_id), Some(0));
+
+        oracle.set_confirmations(settlement_id, 3);
+        assert_eq!(oracle.confirmations(settlement_id), Some(3));
+    }
+}
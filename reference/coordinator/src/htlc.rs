@@ -0,0 +1,116 @@
+//! Hash-time-locked conditional settlements for atomic multi-hop routing.
+//!
+//! Lets a settlement traverse one or more intermediary participants while
+//! keeping the all-or-nothing guarantee that a direct settlement gets for
+//! free: every leg is locked against the same `PaymentHash` with a strictly
+//! decreasing per-hop timeout (the first hop waits longest). The final
+//! recipient reveals the `PaymentPreimage` to claim its leg; the preimage
+//! then propagates back upstream where each hop verifies it before
+//! committing its own lock. If any hop's timeout fires before the preimage
+//! arrives, every lock for the settlement is released and the settlement
+//! fails as a whole.
+
+use std::time::Duration;
+
+use atomicsettle_common::{
+    AccountId, AtomicSettleError, LockId, Money, ParticipantId, PaymentHash, PaymentPreimage,
+};
+
+/// One hop of a multi-hop conditional settlement: the participant whose
+/// account receives this leg, and the amount it is owed.
+#[derive(Debug, Clone)]
+pub struct HopSpec {
+    pub participant_id: ParticipantId,
+    pub account: AccountId,
+    pub amount: Money,
+}
+
+/// Compute the per-hop lock timeout budget: the first hop gets
+/// `base_timeout`, and each subsequent hop gets `step` less, leaving the
+/// upstream hops time to react after a downstream hop reveals (or fails to
+/// reveal) the preimage.
+pub fn hop_timeouts(base_timeout: Duration, hop_count: usize, step: Duration) -> Vec<Duration> {
+    (0..hop_count)
+        .map(|i| base_timeout.saturating_sub(step * i as u32))
+        .collect()
+}
+
+/// Tracks the locks and preimage state of an in-flight conditional
+/// settlement.
+#[derive(Debug, Clone)]
+pub struct ConditionalSettlement {
+    /// Hash every leg's lock is conditioned on.
+    pub payment_hash: PaymentHash,
+    /// Hops in traversal order (sender-adjacent hop first).
+    pub hops: Vec<HopSpec>,
+    /// Lock IDs, one per hop, in the same order as `hops`.
+    pub lock_ids: Vec<LockId>,
+    /// The preimage, once revealed by the final recipient.
+    pub preimage: Option<PaymentPreimage>,
+}
+
+impl ConditionalSettlement {
+    /// Start tracking a new conditional settlement before any locks exist.
+    pub fn new(payment_hash: PaymentHash, hops: Vec<HopSpec>) -> Self {
+        Self {
+            payment_hash,
+            hops,
+            lock_ids: Vec::new(),
+            preimage: None,
+        }
+    }
+
+    /// Record the lock acquired for each hop, in hop order.
+    pub fn set_lock_ids(&mut self, lock_ids: Vec<LockId>) {
+        self.lock_ids = lock_ids;
+    }
+
+    /// Reveal the preimage, verifying it actually satisfies the payment
+    /// hash before accepting it.
+    pub fn reveal(&mut self, preimage: PaymentPreimage) -> Result<(), AtomicSettleError> {
+        if !self.payment_hash.is_satisfied_by(&preimage) {
+            return Err(AtomicSettleError::InvalidMessage {
+                message: "preimage does not satisfy payment hash".to_string(),
+                field: Some("preimage".to_string()),
+            });
+        }
+        self.preimage = Some(preimage);
+        Ok(())
+    }
+
+    /// Whether the preimage has been revealed (and every leg can commit).
+    pub fn is_revealed(&self) -> bool {
+        self.preimage.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hop_timeouts_strictly_decrease() {
+        let timeouts = hop_timeouts(Duration::from_secs(60), 3, Duration::from_secs(15));
+        assert_eq!(
+            timeouts,
+            vec![
+                Duration::from_secs(60),
+                Duration::from_secs(45),
+                Duration::from_secs(30),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reveal_rejects_wrong_preimage() {
+        let preimage = PaymentPreimage::random();
+        let mut settlement = ConditionalSettlement::new(preimage.hash(), vec![]);
+
+        let wrong = PaymentPreimage::random();
+        assert!(settlement.reveal(wrong).is_err());
+        assert!(!settlement.is_revealed());
+
+        assert!(settlement.reveal(preimage).is_ok());
+        assert!(settlement.is_revealed());
+    }
+}
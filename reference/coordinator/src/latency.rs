@@ -0,0 +1,203 @@
+//! Adaptive per-participant lock-duration estimation.
+//!
+//! Participants confirm locks at very different speeds depending on network
+//! distance, load, and their own internal processing. Rather than handing
+//! every participant the same `default_duration`, we track how long each one
+//! has actually taken to confirm in the past and size new locks accordingly.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use dashmap::DashMap;
+
+use atomicsettle_common::ParticipantId;
+
+use crate::config::LockConfig;
+
+/// Online (exponentially-weighted) statistics of a participant's lock
+/// confirmation latency, in milliseconds.
+#[derive(Debug, Clone, Copy)]
+pub struct LatencyStats {
+    /// Number of samples observed so far.
+    pub count: u64,
+    /// Exponentially-weighted moving average latency, in milliseconds.
+    pub ewma_ms: f64,
+    /// Exponentially-weighted variance of the latency, in milliseconds^2.
+    pub variance_ms: f64,
+}
+
+impl LatencyStats {
+    /// An estimator with no observations yet.
+    pub fn empty() -> Self {
+        Self {
+            count: 0,
+            ewma_ms: 0.0,
+            variance_ms: 0.0,
+        }
+    }
+
+    /// Fold in a new confirmation-latency sample using a Welford-style
+    /// incremental update of the EWMA and its variance.
+    pub fn update(&mut self, sample_ms: f64, alpha: f64) {
+        if self.count == 0 {
+            self.ewma_ms = sample_ms;
+            self.variance_ms = 0.0;
+        } else {
+            let delta = sample_ms - self.ewma_ms;
+            self.ewma_ms += alpha * delta;
+            self.variance_ms = (1.0 - alpha) * (self.variance_ms + alpha * delta * delta);
+        }
+        self.count += 1;
+    }
+
+    /// Standard deviation of the observed latency.
+    pub fn stddev_ms(&self) -> f64 {
+        self.variance_ms.max(0.0).sqrt()
+    }
+}
+
+impl Default for LatencyStats {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+/// Storage backend for per-participant `LatencyStats`.
+///
+/// The default in-memory `LatencyEstimator` implements this itself, but the
+/// trait lets stats be seeded from a prior run, persisted alongside lock
+/// state, or surveyed by an operator without depending on the concrete
+/// in-memory representation.
+pub trait SampleStore: Send + Sync {
+    /// Load previously recorded stats for a participant, if any.
+    fn load(&self, participant_id: &ParticipantId) -> Option<LatencyStats>;
+
+    /// Seed or overwrite the stats for a participant.
+    fn seed(&self, participant_id: &ParticipantId, stats: LatencyStats);
+
+    /// Return a snapshot of every participant's stats, for inspection or
+    /// persistence.
+    fn survey(&self) -> HashMap<ParticipantId, LatencyStats>;
+}
+
+/// Tracks per-participant confirmation latency and derives an adaptive lock
+/// duration from it.
+pub struct LatencyEstimator {
+    stats: Arc<DashMap<ParticipantId, LatencyStats>>,
+}
+
+impl LatencyEstimator {
+    /// Create a new, empty estimator.
+    pub fn new() -> Self {
+        Self {
+            stats: Arc::new(DashMap::new()),
+        }
+    }
+
+    /// Record an observed confirmation latency for a participant.
+    pub fn record_sample(&self, participant_id: &ParticipantId, latency: Duration, alpha: f64) {
+        let sample_ms = latency.as_secs_f64() * 1000.0;
+        self.stats
+            .entry(participant_id.clone())
+            .or_insert_with(LatencyStats::empty)
+            .update(sample_ms, alpha);
+    }
+
+    /// Estimate a lock duration for a participant given the current config.
+    ///
+    /// Falls back to `config.default_duration` until at least
+    /// `config.latency_min_samples` confirmations have been observed, then
+    /// uses `ewma + k * stddev` clamped to `[min_duration, max_duration]`.
+    pub fn estimate_duration(&self, participant_id: &ParticipantId, config: &LockConfig) -> Duration {
+        let Some(stats) = self.stats.get(participant_id) else {
+            return config.default_duration;
+        };
+
+        if stats.count < config.latency_min_samples {
+            return config.default_duration;
+        }
+
+        let estimate_ms =
+            stats.ewma_ms + config.latency_stddev_multiplier * stats.stddev_ms();
+        let estimate = Duration::from_secs_f64((estimate_ms / 1000.0).max(0.0));
+
+        estimate.clamp(config.min_duration, config.max_duration)
+    }
+
+    /// Get a copy of the current stats for a participant, if any.
+    pub fn stats_for(&self, participant_id: &ParticipantId) -> Option<LatencyStats> {
+        self.stats.get(participant_id).map(|s| *s)
+    }
+}
+
+impl Default for LatencyEstimator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SampleStore for LatencyEstimator {
+    fn load(&self, participant_id: &ParticipantId) -> Option<LatencyStats> {
+        self.stats_for(participant_id)
+    }
+
+    fn seed(&self, participant_id: &ParticipantId, stats: LatencyStats) {
+        self.stats.insert(participant_id.clone(), stats);
+    }
+
+    fn survey(&self) -> HashMap<ParticipantId, LatencyStats> {
+        self.stats
+            .iter()
+            .map(|entry| (entry.key().clone(), *entry.value()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_falls_back_to_default_with_no_samples() {
+        let estimator = LatencyEstimator::new();
+        let config = LockConfig::default();
+        let participant_id = ParticipantId::new("BANK_A");
+
+        assert_eq!(
+            estimator.estimate_duration(&participant_id, &config),
+            config.default_duration
+        );
+    }
+
+    #[test]
+    fn test_adapts_after_enough_samples() {
+        let estimator = LatencyEstimator::new();
+        let mut config = LockConfig::default();
+        config.latency_min_samples = 3;
+        let participant_id = ParticipantId::new("BANK_A");
+
+        for _ in 0..5 {
+            estimator.record_sample(&participant_id, Duration::from_millis(500), config.latency_ewma_alpha);
+        }
+
+        let duration = estimator.estimate_duration(&participant_id, &config);
+        assert!(duration >= config.min_duration);
+        assert!(duration <= config.max_duration);
+    }
+
+    #[test]
+    fn test_seed_and_survey() {
+        let estimator = LatencyEstimator::new();
+        let participant_id = ParticipantId::new("BANK_A");
+        let stats = LatencyStats {
+            count: 50,
+            ewma_ms: 250.0,
+            variance_ms: 10.0,
+        };
+
+        estimator.seed(&participant_id, stats);
+        assert_eq!(estimator.load(&participant_id).unwrap().count, 50);
+        assert_eq!(estimator.survey().len(), 1);
+    }
+}
@@ -3,10 +3,20 @@
 //! The coordinator is the trusted entity that orchestrates settlement between participants.
 //! It provides atomicity guarantees by managing locks and executing atomic commits.
 
+pub mod anchor;
+pub mod auth;
+pub mod background;
+pub mod confirmation;
 pub mod coordinator;
 pub mod config;
+pub mod finality;
+pub mod htlc;
+pub mod latency;
 pub mod participant_manager;
+pub mod persistence;
+pub mod router;
 pub mod settlement_processor;
+pub mod settlement_store;
 pub mod lock_manager;
 pub mod state;
 pub mod metrics;
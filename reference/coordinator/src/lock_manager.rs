@@ -4,14 +4,65 @@ use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use tokio::sync::{broadcast, Notify};
 use tracing::{info, warn};
 
-use atomicsettle_common::{LockId, Money, ParticipantId, SettlementId};
+use atomicsettle_common::{
+    time, AtomicSettleError, Currency, LockId, Money, ParticipantId, SettlementId, Timestamp,
+};
 
 use crate::config::LockConfig;
+use crate::latency::LatencyEstimator;
+use crate::persistence::{LockPersister, PersistedLock};
+
+/// Default capacity of the lock lifecycle event broadcast channel. Lagging
+/// subscribers drop the oldest events once this many are buffered.
+const EVENT_CHANNEL_CAPACITY: usize = 1024;
+
+/// A lock lifecycle transition, published for auditing and monitors so they
+/// don't have to poll `get_lock`.
+#[derive(Debug, Clone)]
+pub enum LockEvent {
+    /// A lock was created.
+    Created {
+        lock_id: LockId,
+        settlement_id: SettlementId,
+        participant_id: ParticipantId,
+        at: Timestamp,
+    },
+    /// A lock was confirmed by its participant.
+    Confirmed {
+        lock_id: LockId,
+        settlement_id: SettlementId,
+        participant_id: ParticipantId,
+        at: Timestamp,
+    },
+    /// A lock was consumed by a successful settlement.
+    Consumed {
+        lock_id: LockId,
+        settlement_id: SettlementId,
+        participant_id: ParticipantId,
+        at: Timestamp,
+    },
+    /// A lock was released due to failure or abort.
+    Released {
+        lock_id: LockId,
+        settlement_id: SettlementId,
+        participant_id: ParticipantId,
+        at: Timestamp,
+    },
+    /// A lock expired without being confirmed in time.
+    Expired {
+        lock_id: LockId,
+        settlement_id: SettlementId,
+        participant_id: ParticipantId,
+        at: Timestamp,
+    },
+}
 
 /// Lock status.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum LockStatus {
     /// Lock is active and holding funds.
     Active,
@@ -131,32 +182,163 @@ pub struct LockManager {
     locks_by_participant: Arc<DashMap<ParticipantId, Vec<LockId>>>,
     /// Configuration.
     config: LockConfig,
+    /// Per-participant confirmation-latency estimator, used to size new
+    /// lock durations adaptively.
+    latency: LatencyEstimator,
+    /// Optional durable backing store; when set, every state transition is
+    /// written to it right after the in-memory update. A failed write is
+    /// logged and does not undo the transition.
+    persister: Option<Arc<dyn LockPersister>>,
+    /// Broadcasts lock lifecycle transitions to subscribers.
+    events: broadcast::Sender<LockEvent>,
+    /// Per-settlement completion notifications, registered by
+    /// [`Self::wakeup_handle`] and signaled by `confirm_lock` the instant
+    /// every lock for that settlement is confirmed. Lets
+    /// `acquire_locks`-style callers await confirmation directly instead of
+    /// polling `are_all_locks_confirmed` on a fixed interval.
+    lock_wakeups: Arc<DashMap<SettlementId, Arc<Notify>>>,
 }
 
 impl LockManager {
-    /// Create a new lock manager.
+    /// Create a new lock manager with no durable backing store.
     pub fn new(config: LockConfig) -> Self {
+        let (events, _) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
         Self {
             locks: Arc::new(DashMap::new()),
             locks_by_settlement: Arc::new(DashMap::new()),
             locks_by_participant: Arc::new(DashMap::new()),
             config,
+            latency: LatencyEstimator::new(),
+            persister: None,
+            events,
+            lock_wakeups: Arc::new(DashMap::new()),
+        }
+    }
+
+    /// Get (creating if needed) the [`Notify`] signaled when every lock for
+    /// `settlement_id` is confirmed. Callers should register this handle
+    /// *before* checking [`Self::are_all_locks_confirmed`], so a
+    /// confirmation landing in between is never missed: `Notify::notify_one`
+    /// stores a permit for the next `notified().await` if nothing is
+    /// waiting yet. Pair with [`Self::clear_wakeup`] once done waiting, on
+    /// both the success and timeout paths, so a late confirmation can't
+    /// find (and signal) a handle nobody is listening to anymore.
+    pub fn wakeup_handle(&self, settlement_id: SettlementId) -> Arc<Notify> {
+        self.lock_wakeups
+            .entry(settlement_id)
+            .or_insert_with(|| Arc::new(Notify::new()))
+            .clone()
+    }
+
+    /// Stop tracking the completion notification for `settlement_id`,
+    /// registered via [`Self::wakeup_handle`]. Safe to call whether or not
+    /// a handle was ever registered.
+    pub fn clear_wakeup(&self, settlement_id: &SettlementId) {
+        self.lock_wakeups.remove(settlement_id);
+    }
+
+    /// Subscribe to lock lifecycle events (creation, confirmation,
+    /// consumption, release, expiry). Events published before a subscriber
+    /// connects are not delivered to it.
+    pub fn subscribe(&self) -> broadcast::Receiver<LockEvent> {
+        self.events.subscribe()
+    }
+
+    /// Publish a lifecycle event, ignoring the "no active subscribers"
+    /// error (there is nothing else useful to do with it).
+    fn publish(&self, event: LockEvent) {
+        let _ = self.events.send(event);
+    }
+
+    /// Create a new lock manager backed by `persister`, with an empty index.
+    /// Use `recover` instead to rebuild state from an existing store.
+    pub fn with_persister(config: LockConfig, persister: Arc<dyn LockPersister>) -> Self {
+        Self {
+            persister: Some(persister),
+            ..Self::new(config)
+        }
+    }
+
+    /// Rebuild a `LockManager` from everything in `persister`, re-indexing by
+    /// settlement and participant. Any lock whose stored expiry has already
+    /// passed is recovered in the `Expired` state.
+    pub fn recover(
+        config: LockConfig,
+        persister: Arc<dyn LockPersister>,
+    ) -> Result<Self, AtomicSettleError> {
+        let manager = Self::with_persister(config, persister);
+
+        for persisted in manager
+            .persister
+            .as_ref()
+            .expect("persister set above")
+            .load_all()?
+        {
+            let lock = persisted.into_lock();
+            let lock_id = lock.id;
+
+            if lock.status == LockStatus::Expired {
+                warn!(lock_id = %lock_id, settlement_id = %lock.settlement_id, "Recovered already-expired lock");
+            }
+
+            manager
+                .locks_by_settlement
+                .entry(lock.settlement_id)
+                .or_insert_with(Vec::new)
+                .push(lock_id);
+            manager
+                .locks_by_participant
+                .entry(lock.participant_id.clone())
+                .or_insert_with(Vec::new)
+                .push(lock_id);
+            manager.locks.insert(lock_id, lock);
+        }
+
+        info!(recovered = manager.locks.len(), "Lock manager recovered from durable store");
+        Ok(manager)
+    }
+
+    /// Get the latency estimator, e.g. to seed or survey stats.
+    pub fn latency_estimator(&self) -> &LatencyEstimator {
+        &self.latency
+    }
+
+    /// Persist the current state of a lock, if a durable store is configured.
+    fn persist(&self, lock: &Lock) {
+        if let Some(persister) = &self.persister {
+            if let Err(e) = persister.persist_lock(&PersistedLock::from_lock(lock)) {
+                warn!(lock_id = %lock.id, error = %e, "Failed to persist lock state");
+            }
         }
     }
 
     /// Create a new lock (does not send to participant).
+    ///
+    /// The lock duration is derived adaptively from the participant's
+    /// observed confirmation latency (see `LatencyEstimator`), falling back
+    /// to `config.default_duration` until enough samples exist.
     pub fn create_lock(
         &self,
         settlement_id: SettlementId,
         participant_id: ParticipantId,
         amount: Money,
     ) -> Lock {
-        let lock = Lock::new(
-            settlement_id,
-            participant_id.clone(),
-            amount,
-            self.config.default_duration,
-        );
+        let duration = self.latency.estimate_duration(&participant_id, &self.config);
+        self.create_lock_with_duration(settlement_id, participant_id, amount, duration)
+    }
+
+    /// Create a new lock with an explicit duration, bypassing the adaptive
+    /// latency estimate. Used by callers that must control the exact
+    /// expiry themselves, e.g. HTLC-style conditional settlements where each
+    /// hop needs a strictly decreasing timeout.
+    pub fn create_lock_with_duration(
+        &self,
+        settlement_id: SettlementId,
+        participant_id: ParticipantId,
+        amount: Money,
+        duration: Duration,
+    ) -> Lock {
+        let lock = Lock::new(settlement_id, participant_id.clone(), amount, duration);
 
         let lock_id = lock.id;
 
@@ -171,10 +353,18 @@ impl LockManager {
 
         // Index by participant
         self.locks_by_participant
-            .entry(participant_id)
+            .entry(participant_id.clone())
             .or_insert_with(Vec::new)
             .push(lock_id);
 
+        self.persist(&lock);
+        self.publish(LockEvent::Created {
+            lock_id,
+            settlement_id,
+            participant_id,
+            at: time::now(),
+        });
+
         info!(
             lock_id = %lock_id,
             settlement_id = %settlement_id,
@@ -184,6 +374,46 @@ impl LockManager {
         lock
     }
 
+    /// Acquire a lock for a settlement leg, the checked entry point.
+    ///
+    /// Rejects with `Error` when the participant is already holding
+    /// `>= max_concurrent_per_participant` active locks, and with `Conflict`
+    /// when an active lock already exists for this settlement+participant
+    /// pair. `create_lock` remains the unchecked primitive for callers (e.g.
+    /// recovery) that have already validated these invariants.
+    pub fn acquire_lock(
+        &self,
+        settlement_id: SettlementId,
+        participant_id: ParticipantId,
+        amount: Money,
+    ) -> LockResult {
+        if let Some(existing) = self
+            .get_locks_for_settlement(&settlement_id)
+            .into_iter()
+            .find(|l| l.participant_id == participant_id && l.is_active())
+        {
+            return LockResult::Conflict {
+                existing_lock_id: existing.id,
+            };
+        }
+
+        let active_for_participant = self.active_lock_count_for_participant(&participant_id);
+        if active_for_participant >= self.config.max_concurrent_per_participant {
+            warn!(
+                participant_id = %participant_id,
+                active = active_for_participant,
+                limit = self.config.max_concurrent_per_participant,
+                "Lock acquisition rejected: participant at concurrency limit"
+            );
+            return LockResult::Error(format!(
+                "participant {participant_id} already has {active_for_participant} active locks (limit {})",
+                self.config.max_concurrent_per_participant
+            ));
+        }
+
+        LockResult::Acquired(self.create_lock(settlement_id, participant_id, amount))
+    }
+
     /// Get a lock by ID.
     pub fn get_lock(&self, lock_id: &LockId) -> Option<Lock> {
         self.locks.get(lock_id).map(|l| l.clone())
@@ -202,10 +432,34 @@ impl LockManager {
             .unwrap_or_default()
     }
 
-    /// Confirm a lock (participant acknowledged).
+    /// Confirm a lock (participant acknowledged). Wakes up any
+    /// [`Self::wakeup_handle`] waiter for this lock's settlement the moment
+    /// this was the *last* outstanding lock for it to confirm.
     pub fn confirm_lock(&self, lock_id: &LockId) -> bool {
         if let Some(mut lock) = self.locks.get_mut(lock_id) {
             lock.confirm();
+            let confirmed_at = lock.confirmed_at.expect("just confirmed");
+            let latency = confirmed_at.saturating_duration_since(lock.created_at);
+            let participant_id = lock.participant_id.clone();
+            let snapshot = lock.clone();
+            drop(lock);
+
+            self.persist(&snapshot);
+            self.latency
+                .record_sample(&participant_id, latency, self.config.latency_ewma_alpha);
+            self.publish(LockEvent::Confirmed {
+                lock_id: *lock_id,
+                settlement_id: snapshot.settlement_id,
+                participant_id,
+                at: time::now(),
+            });
+
+            if self.are_all_locks_confirmed(&snapshot.settlement_id) {
+                if let Some(notify) = self.lock_wakeups.get(&snapshot.settlement_id) {
+                    notify.notify_one();
+                }
+            }
+
             info!(lock_id = %lock_id, "Lock confirmed");
             return true;
         }
@@ -216,6 +470,16 @@ impl LockManager {
     pub fn consume_lock(&self, lock_id: &LockId) -> bool {
         if let Some(mut lock) = self.locks.get_mut(lock_id) {
             lock.consume();
+            let snapshot = lock.clone();
+            drop(lock);
+
+            self.persist(&snapshot);
+            self.publish(LockEvent::Consumed {
+                lock_id: *lock_id,
+                settlement_id: snapshot.settlement_id,
+                participant_id: snapshot.participant_id,
+                at: time::now(),
+            });
             info!(lock_id = %lock_id, "Lock consumed");
             return true;
         }
@@ -226,6 +490,16 @@ impl LockManager {
     pub fn release_lock(&self, lock_id: &LockId) -> bool {
         if let Some(mut lock) = self.locks.get_mut(lock_id) {
             lock.release();
+            let snapshot = lock.clone();
+            drop(lock);
+
+            self.persist(&snapshot);
+            self.publish(LockEvent::Released {
+                lock_id: *lock_id,
+                settlement_id: snapshot.settlement_id,
+                participant_id: snapshot.participant_id,
+                at: time::now(),
+            });
             info!(lock_id = %lock_id, "Lock released");
             return true;
         }
@@ -277,11 +551,44 @@ impl LockManager {
             .unwrap_or(0)
     }
 
-    /// Run cleanup loop to expire stale locks.
-    pub async fn run_cleanup_loop(&self) {
-        loop {
-            tokio::time::sleep(self.config.cleanup_interval).await;
-            self.cleanup_expired_locks();
+    /// Total amount currently locked against a participant in `currency`,
+    /// i.e. liquidity already committed to in-flight settlements. Used by
+    /// the router to avoid pathing new settlements over exhausted edges.
+    pub fn locked_amount_for_participant(
+        &self,
+        participant_id: &ParticipantId,
+        currency: &Currency,
+    ) -> rust_decimal::Decimal {
+        self.locks_by_participant
+            .get(participant_id)
+            .map(|lock_ids| {
+                lock_ids
+                    .iter()
+                    .filter_map(|id| self.get_lock(id))
+                    .filter(|l| l.is_active() && l.amount.currency == *currency)
+                    .map(|l| l.amount.value)
+                    .sum()
+            })
+            .unwrap_or(rust_decimal::Decimal::ZERO)
+    }
+
+    /// Configured interval between cleanup passes, for callers that drive
+    /// their own scheduling (e.g. `BackgroundProcessor`).
+    pub fn cleanup_interval(&self) -> Duration {
+        self.config.cleanup_interval
+    }
+
+    /// Run a single expired-lock cleanup pass. Public so external schedulers
+    /// (e.g. `BackgroundProcessor`) can drive it directly.
+    pub fn run_cleanup_pass(&self) {
+        self.cleanup_expired_locks();
+    }
+
+    /// Flush any pending durable-storage writes.
+    pub fn flush_persistence(&self) -> Result<(), AtomicSettleError> {
+        match &self.persister {
+            Some(persister) => persister.flush(),
+            None => Ok(()),
         }
     }
 
@@ -297,9 +604,20 @@ impl LockManager {
         for lock_id in expired_locks {
             if let Some(mut lock) = self.locks.get_mut(&lock_id) {
                 lock.expire();
+                let snapshot = lock.clone();
+                let settlement_id = lock.settlement_id;
+                drop(lock);
+
+                self.persist(&snapshot);
+                self.publish(LockEvent::Expired {
+                    lock_id,
+                    settlement_id,
+                    participant_id: snapshot.participant_id,
+                    at: time::now(),
+                });
                 warn!(
                     lock_id = %lock_id,
-                    settlement_id = %lock.settlement_id,
+                    settlement_id = %settlement_id,
                     "Lock expired"
                 );
             }
@@ -374,4 +692,90 @@ mod tests {
         let locks = manager.get_locks_for_settlement(&settlement_id);
         assert_eq!(locks.len(), 2);
     }
+
+    #[test]
+    fn test_acquire_lock_detects_conflict() {
+        let manager = create_test_lock_manager();
+        let settlement_id = SettlementId::new();
+        let participant_id = ParticipantId::new("BANK_A");
+        let amount = Money::new(Decimal::from(1000), Currency::usd());
+
+        let first = match manager.acquire_lock(settlement_id, participant_id.clone(), amount.clone()) {
+            LockResult::Acquired(lock) => lock,
+            other => panic!("expected Acquired, got {other:?}"),
+        };
+
+        match manager.acquire_lock(settlement_id, participant_id, amount) {
+            LockResult::Conflict { existing_lock_id } => assert_eq!(existing_lock_id, first.id),
+            other => panic!("expected Conflict, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_acquire_lock_enforces_concurrency_limit() {
+        let mut config = LockConfig::default();
+        config.max_concurrent_per_participant = 1;
+        let manager = LockManager::new(config);
+        let participant_id = ParticipantId::new("BANK_A");
+        let amount = Money::new(Decimal::from(1000), Currency::usd());
+
+        assert!(matches!(
+            manager.acquire_lock(SettlementId::new(), participant_id.clone(), amount.clone()),
+            LockResult::Acquired(_)
+        ));
+
+        assert!(matches!(
+            manager.acquire_lock(SettlementId::new(), participant_id, amount),
+            LockResult::Error(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_wakeup_fires_only_once_the_last_lock_confirms() {
+        let manager = create_test_lock_manager();
+        let settlement_id = SettlementId::new();
+        let amount = Money::new(Decimal::from(1000), Currency::usd());
+
+        let lock_a = manager.create_lock(settlement_id, ParticipantId::new("BANK_A"), amount.clone());
+        let lock_b = manager.create_lock(settlement_id, ParticipantId::new("BANK_B"), amount);
+
+        let notify = manager.wakeup_handle(settlement_id);
+        let notified = notify.notified();
+
+        manager.confirm_lock(&lock_a.id);
+        assert!(!manager.are_all_locks_confirmed(&settlement_id));
+
+        manager.confirm_lock(&lock_b.id);
+        assert!(manager.are_all_locks_confirmed(&settlement_id));
+
+        // The notification fired once the last lock confirmed, so the
+        // previously-created future resolves immediately rather than
+        // hanging.
+        tokio::time::timeout(std::time::Duration::from_millis(100), notified)
+            .await
+            .expect("wakeup should have already fired");
+
+        manager.clear_wakeup(&settlement_id);
+    }
+
+    #[test]
+    fn test_lock_events_published() {
+        let manager = create_test_lock_manager();
+        let mut events = manager.subscribe();
+        let settlement_id = SettlementId::new();
+        let participant_id = ParticipantId::new("BANK_A");
+        let amount = Money::new(Decimal::from(1000), Currency::usd());
+
+        let lock = manager.create_lock(settlement_id, participant_id, amount);
+        manager.consume_lock(&lock.id);
+
+        assert!(matches!(
+            events.try_recv().unwrap(),
+            LockEvent::Created { lock_id, .. } if lock_id == lock.id
+        ));
+        assert!(matches!(
+            events.try_recv().unwrap(),
+            LockEvent::Consumed { lock_id, .. } if lock_id == lock.id
+        ));
+    }
 }
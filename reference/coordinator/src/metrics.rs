@@ -2,6 +2,284 @@
 
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use dashmap::DashMap;
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+
+use atomicsettle_common::ParticipantId;
+
+/// Default interval between periodic metrics reports.
+pub const DEFAULT_REPORT_INTERVAL: Duration = Duration::from_millis(2000);
+
+/// RAII guard that adds the time between its creation and drop to a target
+/// `AtomicU64` accumulator, in microseconds. Lets a caller time a scope --
+/// a lock held, a settlement processed -- without manually calling
+/// `Instant::now()` at every exit path, including early returns.
+pub struct TimerGuard<'a> {
+    target: &'a AtomicU64,
+    start: Instant,
+}
+
+impl<'a> TimerGuard<'a> {
+    /// Start timing, accumulating the elapsed time into `target` once this
+    /// guard is dropped.
+    pub fn new(target: &'a AtomicU64) -> Self {
+        Self {
+            target,
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Drop for TimerGuard<'_> {
+    fn drop(&mut self) {
+        let elapsed_micros = self.start.elapsed().as_micros() as u64;
+        self.target.fetch_add(elapsed_micros, Ordering::Relaxed);
+    }
+}
+
+/// Upper bound, in milliseconds, of each of a [`LatencyHistogram`]'s
+/// buckets, excluding the implicit trailing `+Inf` bucket.
+const HISTOGRAM_BUCKETS_MS: [u64; 8] = [1, 5, 10, 50, 100, 500, 1000, 5000];
+
+/// Lock-free latency histogram with exponential millisecond buckets,
+/// modeled on Prometheus's own histogram type. [`Self::record`] touches a
+/// single bucket counter plus the running sum and count, so concurrent
+/// recorders never contend on a lock the way a `Mutex<Vec<u64>>` of raw
+/// samples would. [`Self::snapshot`] turns the per-bucket populations
+/// cumulative (as Prometheus's `_bucket{le="..."}` series requires) at
+/// read time instead of on every write.
+pub struct LatencyHistogram {
+    /// Per-bucket sample counts, not yet made cumulative. Index `i` for
+    /// `i < HISTOGRAM_BUCKETS_MS.len()` holds samples `<= HISTOGRAM_BUCKETS_MS[i]`
+    /// but `>` the previous bound; the last index is the `+Inf` bucket.
+    buckets: [AtomicU64; HISTOGRAM_BUCKETS_MS.len() + 1],
+    sum_ms: AtomicU64,
+    count: AtomicU64,
+}
+
+impl LatencyHistogram {
+    /// Create an empty histogram.
+    pub fn new() -> Self {
+        Self {
+            buckets: std::array::from_fn(|_| AtomicU64::new(0)),
+            sum_ms: AtomicU64::new(0),
+            count: AtomicU64::new(0),
+        }
+    }
+
+    /// Record an observed latency, in milliseconds.
+    pub fn record(&self, latency_ms: u64) {
+        let idx = HISTOGRAM_BUCKETS_MS
+            .iter()
+            .position(|&bound| latency_ms <= bound)
+            .unwrap_or(HISTOGRAM_BUCKETS_MS.len());
+        self.buckets[idx].fetch_add(1, Ordering::Relaxed);
+        self.sum_ms.fetch_add(latency_ms, Ordering::Relaxed);
+        self.count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Copy out this histogram's cumulative bucket counts, running sum,
+    /// and total count.
+    pub fn snapshot(&self) -> HistogramSnapshot {
+        let mut cumulative = 0u64;
+        let mut bucket_counts = Vec::with_capacity(self.buckets.len());
+        for bucket in &self.buckets {
+            cumulative += bucket.load(Ordering::Relaxed);
+            bucket_counts.push(cumulative);
+        }
+
+        HistogramSnapshot {
+            bucket_counts,
+            sum_ms: self.sum_ms.load(Ordering::Relaxed),
+            count: self.count.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Render this histogram as Prometheus exposition-format
+    /// `_bucket`/`_sum`/`_count` lines under `name`.
+    pub fn to_prometheus(&self, name: &str) -> String {
+        let snapshot = self.snapshot();
+        let mut out = String::new();
+
+        for (bound, count) in HISTOGRAM_BUCKETS_MS.iter().zip(snapshot.bucket_counts.iter()) {
+            out.push_str(&format!("{name}_bucket{{le=\"{bound}\"}} {count}\n"));
+        }
+        let inf_count = snapshot.bucket_counts.last().copied().unwrap_or(0);
+        out.push_str(&format!("{name}_bucket{{le=\"+Inf\"}} {inf_count}\n"));
+        out.push_str(&format!("{name}_sum {}\n", snapshot.sum_ms));
+        out.push_str(&format!("{name}_count {}\n", snapshot.count));
+
+        out
+    }
+}
+
+impl Default for LatencyHistogram {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Snapshot of a [`LatencyHistogram`]'s cumulative bucket counts, sum, and
+/// count at a point in time.
+#[derive(Debug, Clone)]
+pub struct HistogramSnapshot {
+    /// Cumulative sample count per bucket, in ascending bound order, the
+    /// last entry being the `+Inf` bucket.
+    pub bucket_counts: Vec<u64>,
+    /// Sum of every recorded latency, in milliseconds.
+    pub sum_ms: u64,
+    /// Total number of samples recorded.
+    pub count: u64,
+}
+
+/// Per-participant settlement counters, labeled in Prometheus export by
+/// participant ID so an operator can see which counterparty is driving
+/// failures or load, rather than only the coordinator-wide aggregate.
+#[derive(Default)]
+pub struct ParticipantMetrics {
+    pub settlements_total: AtomicU64,
+    pub settlements_success: AtomicU64,
+    pub settlements_failed: AtomicU64,
+}
+
+impl ParticipantMetrics {
+    fn snapshot(&self) -> ParticipantMetricsSnapshot {
+        ParticipantMetricsSnapshot {
+            settlements_total: self.settlements_total.load(Ordering::Relaxed),
+            settlements_success: self.settlements_success.load(Ordering::Relaxed),
+            settlements_failed: self.settlements_failed.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Snapshot of a single participant's counters at a point in time.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ParticipantMetricsSnapshot {
+    pub settlements_total: u64,
+    pub settlements_success: u64,
+    pub settlements_failed: u64,
+}
+
+/// Per-worker-thread settlement counters. Where [`ParticipantMetrics`]
+/// answers "which counterparty", `WorkerMetrics` answers "which worker
+/// thread" -- useful when settlements are sharded across a worker pool and
+/// an operator needs to tell a stuck or overloaded worker apart from a
+/// healthy one.
+#[derive(Default)]
+pub struct WorkerMetrics {
+    pub settlements_total: AtomicU64,
+    pub settlements_success: AtomicU64,
+    pub settlements_failed: AtomicU64,
+}
+
+impl WorkerMetrics {
+    /// Record a settlement this worker picked up.
+    pub fn settlement_initiated(&self) {
+        self.settlements_total.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record a settlement this worker completed successfully.
+    pub fn settlement_success(&self) {
+        self.settlements_success.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record a settlement this worker failed to complete.
+    pub fn settlement_failed(&self) {
+        self.settlements_failed.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn snapshot(&self) -> ParticipantMetricsSnapshot {
+        ParticipantMetricsSnapshot {
+            settlements_total: self.settlements_total.load(Ordering::Relaxed),
+            settlements_success: self.settlements_success.load(Ordering::Relaxed),
+            settlements_failed: self.settlements_failed.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// A fixed-size pool of [`WorkerMetrics`], one per worker thread, each
+/// independently updatable through its own `Arc` without contending with
+/// the others.
+pub struct MetricsRegistry {
+    workers: Vec<Arc<WorkerMetrics>>,
+}
+
+impl MetricsRegistry {
+    /// Create a registry with `worker_count` workers, each starting at
+    /// zero.
+    pub fn new(worker_count: usize) -> Self {
+        Self {
+            workers: (0..worker_count).map(|_| Arc::new(WorkerMetrics::default())).collect(),
+        }
+    }
+
+    /// Get the shared counters for worker `index`.
+    ///
+    /// # Panics
+    /// Panics if `index >= worker_count`.
+    pub fn worker(&self, index: usize) -> Arc<WorkerMetrics> {
+        self.workers[index].clone()
+    }
+
+    /// Number of workers in the registry.
+    pub fn worker_count(&self) -> usize {
+        self.workers.len()
+    }
+
+    /// Sum every worker's counters into a single snapshot.
+    pub fn aggregate(&self) -> ParticipantMetricsSnapshot {
+        let mut total = ParticipantMetricsSnapshot::default();
+        for worker in &self.workers {
+            let snapshot = worker.snapshot();
+            total.settlements_total += snapshot.settlements_total;
+            total.settlements_success += snapshot.settlements_success;
+            total.settlements_failed += snapshot.settlements_failed;
+        }
+        total
+    }
+
+    /// Render per-worker counters as labeled Prometheus exposition-format
+    /// lines, plus the aggregate across all workers.
+    pub fn to_prometheus(&self) -> String {
+        let mut out = String::new();
+        out.push_str("# HELP atomicsettle_worker_settlements_total Total settlements per worker\n");
+        out.push_str("# TYPE atomicsettle_worker_settlements_total counter\n");
+        for (index, worker) in self.workers.iter().enumerate() {
+            out.push_str(&format!(
+                "atomicsettle_worker_settlements_total{{worker=\"{index}\"}} {}\n",
+                worker.snapshot().settlements_total
+            ));
+        }
+
+        out.push_str("# HELP atomicsettle_worker_settlements_success Successful settlements per worker\n");
+        out.push_str("# TYPE atomicsettle_worker_settlements_success counter\n");
+        for (index, worker) in self.workers.iter().enumerate() {
+            out.push_str(&format!(
+                "atomicsettle_worker_settlements_success{{worker=\"{index}\"}} {}\n",
+                worker.snapshot().settlements_success
+            ));
+        }
+
+        out.push_str("# HELP atomicsettle_worker_settlements_failed Failed settlements per worker\n");
+        out.push_str("# TYPE atomicsettle_worker_settlements_failed counter\n");
+        for (index, worker) in self.workers.iter().enumerate() {
+            out.push_str(&format!(
+                "atomicsettle_worker_settlements_failed{{worker=\"{index}\"}} {}\n",
+                worker.snapshot().settlements_failed
+            ));
+        }
+
+        let aggregate = self.aggregate();
+        out.push_str("# HELP atomicsettle_worker_settlements_total_aggregate Total settlements across all workers\n");
+        out.push_str("# TYPE atomicsettle_worker_settlements_total_aggregate counter\n");
+        out.push_str(&format!("atomicsettle_worker_settlements_total_aggregate {}\n", aggregate.settlements_total));
+
+        out
+    }
+}
 
 /// Coordinator metrics.
 pub struct Metrics {
@@ -27,6 +305,21 @@ pub struct Metrics {
     pub messages_received: AtomicU64,
     /// Total messages sent.
     pub messages_sent: AtomicU64,
+    /// Distribution of end-to-end settlement processing latency.
+    pub settlement_latency: LatencyHistogram,
+    /// Distribution of per-participant lock-confirmation wait time.
+    pub lock_wait_latency: LatencyHistogram,
+    /// Per-participant settlement counters, keyed by participant ID.
+    participants: DashMap<ParticipantId, ParticipantMetrics>,
+    /// Cumulative time participants spent waiting for a lock to be
+    /// confirmed, in microseconds.
+    pub lock_wait_micros_total: AtomicU64,
+    /// Cumulative time locks spent held (acquired but not yet consumed or
+    /// released), in microseconds.
+    pub lock_hold_micros_total: AtomicU64,
+    /// Cumulative time spent processing settlements end to end, in
+    /// microseconds.
+    pub settlement_process_micros_total: AtomicU64,
 }
 
 impl Metrics {
@@ -44,9 +337,117 @@ impl Metrics {
             participants_active: AtomicU64::new(0),
             messages_received: AtomicU64::new(0),
             messages_sent: AtomicU64::new(0),
+            settlement_latency: LatencyHistogram::new(),
+            lock_wait_latency: LatencyHistogram::new(),
+            participants: DashMap::new(),
+            lock_wait_micros_total: AtomicU64::new(0),
+            lock_hold_micros_total: AtomicU64::new(0),
+            settlement_process_micros_total: AtomicU64::new(0),
         }
     }
 
+    /// Add an already-measured lock-confirmation wait time to the running
+    /// total.
+    pub fn add_lock_wait(&self, duration: Duration) {
+        self.lock_wait_micros_total.fetch_add(duration.as_micros() as u64, Ordering::Relaxed);
+    }
+
+    /// Start timing a lock's hold duration; accumulates into
+    /// `lock_hold_micros_total` when the returned guard drops.
+    pub fn lock_hold_timer(&self) -> TimerGuard<'_> {
+        TimerGuard::new(&self.lock_hold_micros_total)
+    }
+
+    /// Start timing a settlement's end-to-end processing; accumulates into
+    /// `settlement_process_micros_total` when the returned guard drops.
+    pub fn settlement_process_timer(&self) -> TimerGuard<'_> {
+        TimerGuard::new(&self.settlement_process_micros_total)
+    }
+
+    /// Record an end-to-end settlement's processing latency.
+    pub fn record_settlement_latency(&self, latency_ms: u64) {
+        self.settlement_latency.record(latency_ms);
+    }
+
+    /// Record a participant's lock-confirmation wait time.
+    pub fn record_lock_wait(&self, latency_ms: u64) {
+        self.lock_wait_latency.record(latency_ms);
+    }
+
+    /// Record a settlement initiated involving `participant_id`, alongside
+    /// the coordinator-wide [`Self::settlement_initiated`].
+    pub fn settlement_initiated_for(&self, participant_id: &ParticipantId) {
+        self.participants
+            .entry(participant_id.clone())
+            .or_default()
+            .settlements_total
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record a settlement succeeding for `participant_id`.
+    pub fn settlement_success_for(&self, participant_id: &ParticipantId) {
+        self.participants
+            .entry(participant_id.clone())
+            .or_default()
+            .settlements_success
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record a settlement failing for `participant_id`.
+    pub fn settlement_failed_for(&self, participant_id: &ParticipantId) {
+        self.participants
+            .entry(participant_id.clone())
+            .or_default()
+            .settlements_failed
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Get a snapshot of a single participant's counters, if any have been
+    /// recorded for it.
+    pub fn participant_snapshot(&self, participant_id: &ParticipantId) -> Option<ParticipantMetricsSnapshot> {
+        self.participants.get(participant_id).map(|entry| entry.snapshot())
+    }
+
+    /// Render every tracked participant's counters as labeled Prometheus
+    /// exposition-format lines.
+    pub fn participants_to_prometheus(&self) -> String {
+        let mut out = String::new();
+        out.push_str("# HELP atomicsettle_participant_settlements_total Total settlements per participant\n");
+        out.push_str("# TYPE atomicsettle_participant_settlements_total counter\n");
+        for entry in self.participants.iter() {
+            let snapshot = entry.value().snapshot();
+            out.push_str(&format!(
+                "atomicsettle_participant_settlements_total{{participant=\"{}\"}} {}\n",
+                entry.key(),
+                snapshot.settlements_total
+            ));
+        }
+
+        out.push_str("# HELP atomicsettle_participant_settlements_success Successful settlements per participant\n");
+        out.push_str("# TYPE atomicsettle_participant_settlements_success counter\n");
+        for entry in self.participants.iter() {
+            let snapshot = entry.value().snapshot();
+            out.push_str(&format!(
+                "atomicsettle_participant_settlements_success{{participant=\"{}\"}} {}\n",
+                entry.key(),
+                snapshot.settlements_success
+            ));
+        }
+
+        out.push_str("# HELP atomicsettle_participant_settlements_failed Failed settlements per participant\n");
+        out.push_str("# TYPE atomicsettle_participant_settlements_failed counter\n");
+        for entry in self.participants.iter() {
+            let snapshot = entry.value().snapshot();
+            out.push_str(&format!(
+                "atomicsettle_participant_settlements_failed{{participant=\"{}\"}} {}\n",
+                entry.key(),
+                snapshot.settlements_failed
+            ));
+        }
+
+        out
+    }
+
     /// Increment settlement initiated.
     pub fn settlement_initiated(&self) {
         self.settlements_total.fetch_add(1, Ordering::Relaxed);
@@ -179,7 +580,18 @@ atomicsettle_messages_sent {}
             snapshot.participants_active,
             snapshot.messages_received,
             snapshot.messages_sent,
-        )
+        ) + "\n# HELP atomicsettle_settlement_latency_ms Settlement processing latency\n# TYPE atomicsettle_settlement_latency_ms histogram\n"
+            + &self.settlement_latency.to_prometheus("atomicsettle_settlement_latency_ms")
+            + "\n# HELP atomicsettle_lock_wait_latency_ms Participant lock confirmation wait time\n# TYPE atomicsettle_lock_wait_latency_ms histogram\n"
+            + &self.lock_wait_latency.to_prometheus("atomicsettle_lock_wait_latency_ms")
+            + "\n"
+            + &self.participants_to_prometheus()
+            + &format!(
+                "\n# HELP atomicsettle_lock_wait_micros_total Cumulative lock confirmation wait time\n# TYPE atomicsettle_lock_wait_micros_total counter\natomicsettle_lock_wait_micros_total {}\n\n# HELP atomicsettle_lock_hold_micros_total Cumulative lock hold time\n# TYPE atomicsettle_lock_hold_micros_total counter\natomicsettle_lock_hold_micros_total {}\n\n# HELP atomicsettle_settlement_process_micros_total Cumulative settlement processing time\n# TYPE atomicsettle_settlement_process_micros_total counter\natomicsettle_settlement_process_micros_total {}\n",
+                self.lock_wait_micros_total.load(Ordering::Relaxed),
+                self.lock_hold_micros_total.load(Ordering::Relaxed),
+                self.settlement_process_micros_total.load(Ordering::Relaxed),
+            )
     }
 }
 
@@ -189,6 +601,80 @@ impl Default for Metrics {
     }
 }
 
+/// Destination for periodic metrics reports, e.g. a logger or a push
+/// gateway client. `Metrics` itself doesn't know or care what a sink does
+/// with a report.
+pub trait MetricsSink: Send + Sync {
+    /// Receive one periodic report.
+    fn report(&self, report: &MetricsReport);
+}
+
+/// One periodic snapshot handed to a [`MetricsSink`]: the cumulative
+/// totals at report time, plus the rate of change since the previous
+/// report.
+#[derive(Debug, Clone)]
+pub struct MetricsReport {
+    /// Cumulative totals at the time of this report.
+    pub snapshot: MetricsSnapshot,
+    /// Settlements initiated since the previous report.
+    pub settlements_total_delta: u64,
+    /// Settlements that succeeded since the previous report.
+    pub settlements_success_delta: u64,
+    /// Settlements that failed since the previous report.
+    pub settlements_failed_delta: u64,
+}
+
+/// Owns a background task that periodically reports `Metrics` snapshots to
+/// a [`MetricsSink`], modeled on [`crate::background::BackgroundProcessor`]'s
+/// spawn-with-oneshot-stop-handle pattern.
+pub struct MetricsReporter {
+    handle: JoinHandle<()>,
+    stop_tx: oneshot::Sender<()>,
+}
+
+impl Metrics {
+    /// Spawn a background task that reports a [`MetricsReport`] to `sink`
+    /// every `interval`, until the returned [`MetricsReporter`] is
+    /// stopped. The first report's deltas are measured against the
+    /// snapshot taken when this call starts, not against zero.
+    pub fn spawn_reporter(self: &Arc<Self>, interval: Duration, sink: impl MetricsSink + 'static) -> MetricsReporter {
+        let metrics = self.clone();
+        let (stop_tx, mut stop_rx) = oneshot::channel();
+        let mut previous = metrics.snapshot();
+
+        let handle = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+
+            loop {
+                tokio::select! {
+                    _ = ticker.tick() => {
+                        let snapshot = metrics.snapshot();
+                        let report = MetricsReport {
+                            settlements_total_delta: snapshot.settlements_total.saturating_sub(previous.settlements_total),
+                            settlements_success_delta: snapshot.settlements_success.saturating_sub(previous.settlements_success),
+                            settlements_failed_delta: snapshot.settlements_failed.saturating_sub(previous.settlements_failed),
+                            snapshot: snapshot.clone(),
+                        };
+                        sink.report(&report);
+                        previous = snapshot;
+                    }
+                    _ = &mut stop_rx => break,
+                }
+            }
+        });
+
+        MetricsReporter { handle, stop_tx }
+    }
+}
+
+impl MetricsReporter {
+    /// Signal the reporter task to stop and wait for it to finish.
+    pub async fn stop(self) {
+        let _ = self.stop_tx.send(());
+        let _ = self.handle.await;
+    }
+}
+
 /// Snapshot of metrics at a point in time.
 #[derive(Debug, Clone)]
 pub struct MetricsSnapshot {
@@ -234,4 +720,195 @@ mod tests {
         let output = metrics.to_prometheus();
         assert!(output.contains("atomicsettle_settlements_total 1"));
     }
+
+    #[test]
+    fn test_latency_histogram_buckets_are_cumulative() {
+        let histogram = LatencyHistogram::new();
+        histogram.record(3);
+        histogram.record(30);
+        histogram.record(3000);
+
+        let snapshot = histogram.snapshot();
+
+        // bucket bounds: [1, 5, 10, 50, 100, 500, 1000, 5000, +Inf]
+        assert_eq!(snapshot.bucket_counts[0], 0); // <=1ms
+        assert_eq!(snapshot.bucket_counts[1], 1); // <=5ms: the 3ms sample
+        assert_eq!(snapshot.bucket_counts[3], 2); // <=50ms: 3ms and 30ms
+        assert_eq!(snapshot.bucket_counts[7], 3); // <=5000ms: the 3000ms sample joins them
+        assert_eq!(*snapshot.bucket_counts.last().unwrap(), 3); // +Inf: all three
+        assert_eq!(snapshot.count, 3);
+        assert_eq!(snapshot.sum_ms, 3033);
+    }
+
+    #[test]
+    fn test_latency_histogram_prometheus_export_includes_le_labels() {
+        let histogram = LatencyHistogram::new();
+        histogram.record(7);
+
+        let output = histogram.to_prometheus("atomicsettle_test_latency_ms");
+
+        assert!(output.contains(r#"atomicsettle_test_latency_ms_bucket{le="10"} 1"#));
+        assert!(output.contains(r#"atomicsettle_test_latency_ms_bucket{le="+Inf"} 1"#));
+        assert!(output.contains("atomicsettle_test_latency_ms_sum 7"));
+        assert!(output.contains("atomicsettle_test_latency_ms_count 1"));
+    }
+
+    #[test]
+    fn test_record_settlement_latency_and_lock_wait_surface_in_prometheus() {
+        let metrics = Metrics::new();
+        metrics.record_settlement_latency(42);
+        metrics.record_lock_wait(12);
+
+        let output = metrics.to_prometheus();
+        assert!(output.contains("atomicsettle_settlement_latency_ms_count 1"));
+        assert!(output.contains("atomicsettle_lock_wait_latency_ms_count 1"));
+    }
+
+    #[test]
+    fn test_participant_counters_tracked_independently() {
+        use atomicsettle_common::ParticipantId;
+
+        let metrics = Metrics::new();
+        let bank_a = ParticipantId::new("BANK_A");
+        let bank_b = ParticipantId::new("BANK_B");
+
+        metrics.settlement_initiated_for(&bank_a);
+        metrics.settlement_success_for(&bank_a);
+        metrics.settlement_initiated_for(&bank_b);
+        metrics.settlement_failed_for(&bank_b);
+
+        let snapshot_a = metrics.participant_snapshot(&bank_a).unwrap();
+        assert_eq!(snapshot_a.settlements_total, 1);
+        assert_eq!(snapshot_a.settlements_success, 1);
+        assert_eq!(snapshot_a.settlements_failed, 0);
+
+        let snapshot_b = metrics.participant_snapshot(&bank_b).unwrap();
+        assert_eq!(snapshot_b.settlements_total, 1);
+        assert_eq!(snapshot_b.settlements_failed, 1);
+
+        assert!(metrics.participant_snapshot(&ParticipantId::new("BANK_C")).is_none());
+    }
+
+    #[test]
+    fn test_participant_metrics_labeled_in_prometheus_export() {
+        use atomicsettle_common::ParticipantId;
+
+        let metrics = Metrics::new();
+        let bank_a = ParticipantId::new("BANK_A");
+        metrics.settlement_success_for(&bank_a);
+
+        let output = metrics.to_prometheus();
+        assert!(output.contains(r#"atomicsettle_participant_settlements_success{participant="BANK_A"} 1"#));
+    }
+
+    #[test]
+    fn test_metrics_registry_tracks_workers_independently() {
+        let registry = MetricsRegistry::new(2);
+
+        registry.worker(0).settlement_initiated();
+        registry.worker(0).settlement_success();
+        registry.worker(1).settlement_initiated();
+        registry.worker(1).settlement_failed();
+
+        assert_eq!(registry.worker(0).snapshot().settlements_success, 1);
+        assert_eq!(registry.worker(1).snapshot().settlements_failed, 1);
+    }
+
+    #[test]
+    fn test_metrics_registry_aggregate_sums_all_workers() {
+        let registry = MetricsRegistry::new(3);
+
+        for worker_index in 0..3 {
+            registry.worker(worker_index).settlement_initiated();
+        }
+        registry.worker(0).settlement_success();
+
+        let aggregate = registry.aggregate();
+        assert_eq!(aggregate.settlements_total, 3);
+        assert_eq!(aggregate.settlements_success, 1);
+    }
+
+    #[test]
+    fn test_metrics_registry_prometheus_export_labels_each_worker() {
+        let registry = MetricsRegistry::new(2);
+        registry.worker(1).settlement_success();
+
+        let output = registry.to_prometheus();
+        assert!(output.contains(r#"atomicsettle_worker_settlements_success{worker="1"} 1"#));
+        assert!(output.contains("atomicsettle_worker_settlements_total_aggregate 1"));
+    }
+
+    #[test]
+    fn test_add_lock_wait_accumulates_micros() {
+        let metrics = Metrics::new();
+
+        metrics.add_lock_wait(Duration::from_millis(5));
+        metrics.add_lock_wait(Duration::from_millis(5));
+
+        assert_eq!(metrics.lock_wait_micros_total.load(Ordering::Relaxed), 10_000);
+    }
+
+    #[test]
+    fn test_timer_guard_accumulates_elapsed_time_on_drop() {
+        let metrics = Metrics::new();
+
+        {
+            let _guard = metrics.lock_hold_timer();
+            std::thread::sleep(Duration::from_millis(5));
+        }
+
+        assert!(metrics.lock_hold_micros_total.load(Ordering::Relaxed) >= 5_000);
+    }
+
+    #[test]
+    fn test_timing_counters_surface_in_prometheus() {
+        let metrics = Metrics::new();
+        metrics.add_lock_wait(Duration::from_millis(1));
+
+        let output = metrics.to_prometheus();
+        assert!(output.contains("atomicsettle_lock_wait_micros_total 1000"));
+        assert!(output.contains("atomicsettle_lock_hold_micros_total 0"));
+        assert!(output.contains("atomicsettle_settlement_process_micros_total 0"));
+    }
+
+    struct RecordingSink {
+        reports: Arc<std::sync::Mutex<Vec<MetricsReport>>>,
+    }
+
+    impl MetricsSink for RecordingSink {
+        fn report(&self, report: &MetricsReport) {
+            self.reports.lock().unwrap().push(report.clone());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_spawn_reporter_fires_periodically_with_deltas() {
+        let metrics = Arc::new(Metrics::new());
+        let reports = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = RecordingSink { reports: reports.clone() };
+
+        let reporter = metrics.spawn_reporter(Duration::from_millis(10), sink);
+
+        metrics.settlement_initiated();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        metrics.settlement_initiated();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        reporter.stop().await;
+
+        let collected = reports.lock().unwrap();
+        assert!(collected.len() >= 2);
+        assert_eq!(collected.iter().map(|r| r.settlements_total_delta).sum::<u64>(), 2);
+        assert_eq!(collected.last().unwrap().snapshot.settlements_total, 2);
+    }
+
+    #[tokio::test]
+    async fn test_metrics_reporter_stop_terminates_task() {
+        let metrics = Arc::new(Metrics::new());
+        let reports = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = RecordingSink { reports: reports.clone() };
+
+        let reporter = metrics.spawn_reporter(Duration::from_millis(5), sink);
+        reporter.stop().await;
+    }
 }
@@ -0,0 +1,197 @@
+//! Durable storage for lock state, so a coordinator restart doesn't silently
+//! drop funds that participants still believe are held.
+//!
+//! Monotonic `Instant`s can't be serialized, so the persisted representation
+//! of a lock uses the wall-clock `Timestamp` type instead. On recovery we
+//! recompute the remaining lifetime from the stored expiry rather than
+//! trusting any saved duration.
+
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+
+use atomicsettle_common::{time, AtomicSettleError, LockId, Money, ParticipantId, SettlementId, Timestamp};
+
+use crate::lock_manager::{Lock, LockStatus};
+
+/// Wall-clock, serializable snapshot of a `Lock`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PersistedLock {
+    pub id: LockId,
+    pub settlement_id: SettlementId,
+    pub participant_id: ParticipantId,
+    pub amount: Money,
+    pub status: LockStatus,
+    pub created_at: Timestamp,
+    pub expires_at: Timestamp,
+    pub confirmed_at: Option<Timestamp>,
+}
+
+impl PersistedLock {
+    /// Snapshot a live `Lock`, translating its monotonic clock readings to
+    /// wall-clock timestamps relative to now.
+    pub fn from_lock(lock: &Lock) -> Self {
+        let now_instant = Instant::now();
+        let now_wall = time::now();
+
+        let to_wall = |instant: Instant| -> Timestamp {
+            if instant >= now_instant {
+                now_wall + chrono::Duration::from_std(instant - now_instant).unwrap_or_default()
+            } else {
+                now_wall - chrono::Duration::from_std(now_instant - instant).unwrap_or_default()
+            }
+        };
+
+        Self {
+            id: lock.id,
+            settlement_id: lock.settlement_id,
+            participant_id: lock.participant_id.clone(),
+            amount: lock.amount.clone(),
+            status: lock.status,
+            created_at: to_wall(lock.created_at),
+            expires_at: to_wall(lock.expires_at),
+            confirmed_at: lock.confirmed_at.map(to_wall),
+        }
+    }
+
+    /// Rebuild a live `Lock`, re-deriving monotonic clock readings from the
+    /// stored wall-clock timestamps relative to now. If the stored expiry has
+    /// already passed, the reconstructed lock is immediately marked expired.
+    pub fn into_lock(self) -> Lock {
+        let now_instant = Instant::now();
+        let now_wall = time::now();
+
+        let to_instant = |wall: Timestamp| -> Instant {
+            if wall >= now_wall {
+                now_instant + (wall - now_wall).to_std().unwrap_or_default()
+            } else {
+                now_instant
+                    .checked_sub((now_wall - wall).to_std().unwrap_or_default())
+                    .unwrap_or(now_instant)
+            }
+        };
+
+        let mut status = self.status;
+        if status == LockStatus::Active && time::is_expired(self.expires_at) {
+            status = LockStatus::Expired;
+        }
+
+        Lock {
+            id: self.id,
+            settlement_id: self.settlement_id,
+            participant_id: self.participant_id,
+            amount: self.amount,
+            status,
+            created_at: to_instant(self.created_at),
+            expires_at: to_instant(self.expires_at),
+            confirmed_at: self.confirmed_at.map(to_instant),
+        }
+    }
+}
+
+/// Durable storage backend for lock state.
+pub trait LockPersister: Send + Sync {
+    /// Persist the current state of a lock (insert or overwrite).
+    fn persist_lock(&self, lock: &PersistedLock) -> Result<(), AtomicSettleError>;
+
+    /// Remove a lock from durable storage (e.g. once fully settled).
+    fn remove_lock(&self, lock_id: &LockId) -> Result<(), AtomicSettleError>;
+
+    /// Load every lock currently in durable storage.
+    fn load_all(&self) -> Result<Vec<PersistedLock>, AtomicSettleError>;
+
+    /// Flush any buffered writes to stable storage. The default
+    /// implementation is a no-op, suitable for backends (like the WAL) that
+    /// already sync on every write.
+    fn flush(&self) -> Result<(), AtomicSettleError> {
+        Ok(())
+    }
+}
+
+/// Append-only, write-ahead-log backed `LockPersister`.
+///
+/// Every call appends a newline-delimited JSON record to the log file; a
+/// `None` record marks a removal. `load_all` replays the log and keeps only
+/// the latest record per lock ID, dropping removed locks.
+pub struct WalLockPersister {
+    path: PathBuf,
+    file: Mutex<File>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct WalRecord {
+    lock_id: LockId,
+    lock: Option<PersistedLock>,
+}
+
+impl WalLockPersister {
+    /// Open (creating if needed) a WAL file at `path`.
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, AtomicSettleError> {
+        let path = path.into();
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .read(true)
+            .open(&path)
+            .map_err(|e| AtomicSettleError::InternalError(format!("opening lock WAL: {e}")))?;
+
+        Ok(Self {
+            path,
+            file: Mutex::new(file),
+        })
+    }
+
+    fn append(&self, record: &WalRecord) -> Result<(), AtomicSettleError> {
+        let line = serde_json::to_string(record)
+            .map_err(|e| AtomicSettleError::InternalError(format!("encoding lock WAL record: {e}")))?;
+
+        let mut file = self.file.lock().expect("lock WAL mutex poisoned");
+        writeln!(file, "{line}")
+            .map_err(|e| AtomicSettleError::InternalError(format!("appending to lock WAL: {e}")))?;
+        file.flush()
+            .map_err(|e| AtomicSettleError::InternalError(format!("flushing lock WAL: {e}")))
+    }
+}
+
+impl LockPersister for WalLockPersister {
+    fn persist_lock(&self, lock: &PersistedLock) -> Result<(), AtomicSettleError> {
+        self.append(&WalRecord {
+            lock_id: lock.id,
+            lock: Some(lock.clone()),
+        })
+    }
+
+    fn remove_lock(&self, lock_id: &LockId) -> Result<(), AtomicSettleError> {
+        self.append(&WalRecord {
+            lock_id: *lock_id,
+            lock: None,
+        })
+    }
+
+    fn load_all(&self) -> Result<Vec<PersistedLock>, AtomicSettleError> {
+        let file = File::open(&self.path)
+            .map_err(|e| AtomicSettleError::InternalError(format!("reopening lock WAL: {e}")))?;
+
+        let mut latest: std::collections::HashMap<LockId, Option<PersistedLock>> =
+            std::collections::HashMap::new();
+
+        for line in BufReader::new(file).lines() {
+            let line = line.map_err(|e| {
+                AtomicSettleError::InternalError(format!("reading lock WAL: {e}"))
+            })?;
+            if line.is_empty() {
+                continue;
+            }
+            let record: WalRecord = serde_json::from_str(&line).map_err(|e| {
+                AtomicSettleError::InternalError(format!("decoding lock WAL record: {e}"))
+            })?;
+            latest.insert(record.lock_id, record.lock);
+        }
+
+        Ok(latest.into_values().flatten().collect())
+    }
+}
@@ -0,0 +1,280 @@
+//! Liquidity-aware routing across the participant graph.
+//!
+//! When a settlement's sender and receiver have no direct bilateral
+//! relationship, the coordinator needs to find an intermediary path. `Router`
+//! models every configured bilateral relationship as a directed, weighted
+//! edge (cost = per-hop fee) and runs Dijkstra to find the cheapest path
+//! with enough spare capacity for the requested amount. Capacity is checked
+//! against `LockManager`'s live reservations so in-flight settlements can't
+//! be routed over an edge that's already exhausted, and `ParticipantManager`
+//! is consulted so a path never traverses a participant that isn't active.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::sync::Arc;
+
+use rust_decimal::Decimal;
+
+use atomicsettle_common::{AccountId, AtomicSettleError, Money, ParticipantId, Result};
+
+use crate::htlc::HopSpec;
+use crate::lock_manager::LockManager;
+use crate::participant_manager::ParticipantManager;
+
+/// A bilateral relationship: the participant at the tail of this edge will
+/// forward up to `capacity` of `to`'s currency for a fee of `fee`.
+#[derive(Debug, Clone)]
+pub struct Edge {
+    /// Participant at the head of the edge.
+    pub to: ParticipantId,
+    /// Account at `to` that receives the forwarded leg.
+    pub to_account: AccountId,
+    /// Maximum amount this relationship can carry.
+    pub capacity: Money,
+    /// Fee charged for routing across this edge.
+    pub fee: Money,
+}
+
+/// The configured bilateral relationships between participants, as an
+/// adjacency list keyed by the tail of each edge.
+#[derive(Debug, Default)]
+pub struct RoutingGraph {
+    edges: HashMap<ParticipantId, Vec<Edge>>,
+}
+
+impl RoutingGraph {
+    /// Create an empty routing graph.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a directed edge `from -> edge.to`.
+    pub fn add_edge(&mut self, from: ParticipantId, edge: Edge) {
+        self.edges.entry(from).or_insert_with(Vec::new).push(edge);
+    }
+
+    fn edges_from(&self, participant_id: &ParticipantId) -> &[Edge] {
+        self.edges
+            .get(participant_id)
+            .map(|edges| edges.as_slice())
+            .unwrap_or(&[])
+    }
+}
+
+/// Finds settlement paths across the participant graph.
+pub struct Router {
+    graph: RoutingGraph,
+    participants: Arc<ParticipantManager>,
+    lock_manager: Arc<LockManager>,
+}
+
+impl Router {
+    /// Create a router over `graph`, consulting `participants` for liveness
+    /// and `lock_manager` for currently reserved liquidity.
+    pub fn new(
+        graph: RoutingGraph,
+        participants: Arc<ParticipantManager>,
+        lock_manager: Arc<LockManager>,
+    ) -> Self {
+        Self {
+            graph,
+            participants,
+            lock_manager,
+        }
+    }
+
+    /// Find the cheapest path from `from` to `to` with enough spare
+    /// capacity to carry `amount`, as an ordered hop list (excluding `from`,
+    /// ending with `to`) suitable for `Coordinator::create_settlement`'s
+    /// conditional route. Returns `NoRouteFound` when no such path exists.
+    pub fn find_route(
+        &self,
+        from: &ParticipantId,
+        to: &ParticipantId,
+        amount: &Money,
+    ) -> Result<Vec<HopSpec>> {
+        let mut dist: HashMap<ParticipantId, Decimal> = HashMap::new();
+        let mut prev: HashMap<ParticipantId, (ParticipantId, Edge)> = HashMap::new();
+        let mut heap: BinaryHeap<Reverse<(Decimal, ParticipantId)>> = BinaryHeap::new();
+
+        dist.insert(from.clone(), Decimal::ZERO);
+        heap.push(Reverse((Decimal::ZERO, from.clone())));
+
+        while let Some(Reverse((cost, node))) = heap.pop() {
+            if node == *to {
+                break;
+            }
+            if cost > *dist.get(&node).unwrap_or(&Decimal::MAX) {
+                continue;
+            }
+
+            for edge in self.graph.edges_from(&node) {
+                if !self.participants.is_participant_active(&edge.to) {
+                    continue;
+                }
+                if edge.capacity.currency != amount.currency || edge.fee.currency != amount.currency
+                {
+                    continue;
+                }
+
+                let reserved = self
+                    .lock_manager
+                    .locked_amount_for_participant(&edge.to, &amount.currency);
+                let available = edge.capacity.value - reserved;
+                if available < amount.value {
+                    continue;
+                }
+
+                let next_cost = cost + edge.fee.value;
+                if next_cost < *dist.get(&edge.to).unwrap_or(&Decimal::MAX) {
+                    dist.insert(edge.to.clone(), next_cost);
+                    prev.insert(edge.to.clone(), (node.clone(), edge.clone()));
+                    heap.push(Reverse((next_cost, edge.to.clone())));
+                }
+            }
+        }
+
+        if !dist.contains_key(to) {
+            return Err(AtomicSettleError::NoRouteFound {
+                from: from.clone(),
+                to: to.clone(),
+            });
+        }
+
+        let mut hops = Vec::new();
+        let mut current = to.clone();
+        while let Some((prev_node, edge)) = prev.get(&current) {
+            hops.push(HopSpec {
+                participant_id: edge.to.clone(),
+                account: edge.to_account.clone(),
+                amount: amount.clone(),
+            });
+            current = prev_node.clone();
+        }
+        hops.reverse();
+
+        Ok(hops)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use atomicsettle_common::Currency;
+    use rust_decimal::Decimal;
+
+    use crate::config::LockConfig;
+
+    fn activate(participants: &ParticipantManager, id: &ParticipantId) {
+        let _rx = participants.register(id.clone(), "1.0".to_string());
+        participants.activate(id);
+    }
+
+    fn money(value: i64) -> Money {
+        Money::new(Decimal::from(value), Currency::usd())
+    }
+
+    #[test]
+    fn test_find_route_picks_cheapest_path() {
+        let participants = Arc::new(ParticipantManager::new());
+        let lock_manager = Arc::new(LockManager::new(LockConfig::default()));
+
+        let a = ParticipantId::new("BANK_A");
+        let b = ParticipantId::new("BANK_B");
+        let c = ParticipantId::new("BANK_C");
+        for id in [&a, &b, &c] {
+            activate(&participants, id);
+        }
+
+        let mut graph = RoutingGraph::new();
+        graph.add_edge(
+            a.clone(),
+            Edge {
+                to: b.clone(),
+                to_account: AccountId::new(b.clone(), "1", "USD"),
+                capacity: money(1000),
+                fee: money(10),
+            },
+        );
+        graph.add_edge(
+            b.clone(),
+            Edge {
+                to: c.clone(),
+                to_account: AccountId::new(c.clone(), "1", "USD"),
+                capacity: money(1000),
+                fee: money(5),
+            },
+        );
+        graph.add_edge(
+            a.clone(),
+            Edge {
+                to: c.clone(),
+                to_account: AccountId::new(c.clone(), "1", "USD"),
+                capacity: money(1000),
+                fee: money(50),
+            },
+        );
+
+        let router = Router::new(graph, participants, lock_manager);
+        let hops = router.find_route(&a, &c, &money(100)).unwrap();
+
+        assert_eq!(hops.len(), 2);
+        assert_eq!(hops[0].participant_id, b);
+        assert_eq!(hops[1].participant_id, c);
+    }
+
+    #[test]
+    fn test_find_route_prunes_insufficient_capacity() {
+        let participants = Arc::new(ParticipantManager::new());
+        let lock_manager = Arc::new(LockManager::new(LockConfig::default()));
+
+        let a = ParticipantId::new("BANK_A");
+        let b = ParticipantId::new("BANK_B");
+        activate(&participants, &a);
+        activate(&participants, &b);
+
+        let mut graph = RoutingGraph::new();
+        graph.add_edge(
+            a.clone(),
+            Edge {
+                to: b.clone(),
+                to_account: AccountId::new(b.clone(), "1", "USD"),
+                capacity: money(50),
+                fee: money(1),
+            },
+        );
+
+        let router = Router::new(graph, participants, lock_manager);
+        let err = router.find_route(&a, &b, &money(100)).unwrap_err();
+
+        assert!(matches!(err, AtomicSettleError::NoRouteFound { .. }));
+    }
+
+    #[test]
+    fn test_find_route_skips_inactive_participant() {
+        let participants = Arc::new(ParticipantManager::new());
+        let lock_manager = Arc::new(LockManager::new(LockConfig::default()));
+
+        let a = ParticipantId::new("BANK_A");
+        let b = ParticipantId::new("BANK_B");
+        activate(&participants, &a);
+        // b is registered but never activated.
+        let _rx = participants.register(b.clone(), "1.0".to_string());
+
+        let mut graph = RoutingGraph::new();
+        graph.add_edge(
+            a.clone(),
+            Edge {
+                to: b.clone(),
+                to_account: AccountId::new(b.clone(), "1", "USD"),
+                capacity: money(1000),
+                fee: money(1),
+            },
+        );
+
+        let router = Router::new(graph, participants, lock_manager);
+        let err = router.find_route(&a, &b, &money(100)).unwrap_err();
+
+        assert!(matches!(err, AtomicSettleError::NoRouteFound { .. }));
+    }
+}
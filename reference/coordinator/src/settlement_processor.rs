@@ -1,5 +1,6 @@
 //! Settlement processing logic.
 
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
 use tracing::{info, warn, error, instrument};
@@ -9,21 +10,44 @@ use atomicsettle_common::{
     SettlementStatus,
 };
 
-use crate::lock_manager::LockManager;
+use crate::lock_manager::{Lock, LockManager};
 use crate::participant_manager::{ParticipantManager, ParticipantNotification};
+use crate::settlement_store::SettlementStore;
 
 /// Settlement processor handles the settlement lifecycle.
+///
+/// Every step below that changes settlement status does so through
+/// `Settlement::transition_to`/`fail`, which append a typed
+/// `SettlementEvent` to the settlement's own event log before returning.
+/// When a durable store is attached, the processor persists that log
+/// immediately after each such step and *before* notifying participants,
+/// so a crash between persist and notify leaves nothing to replay twice --
+/// `SettlementStore::load` rebuilds the settlement from exactly the events
+/// that made it to disk.
+///
+/// A settlement left in a non-terminal status by a prior crash is never
+/// re-submitted to `process` (which would re-validate and re-lock it from
+/// scratch); instead the caller hands it to [`Self::resume`], which
+/// re-enters the lifecycle at the stage its persisted status says it
+/// reached.
 pub struct SettlementProcessor {
     /// Lock manager.
-    #[allow(dead_code)]
     lock_manager: Arc<LockManager>,
     /// Participant manager.
-    #[allow(dead_code)]
     participant_manager: Arc<ParticipantManager>,
+    /// Optional durable settlement store; when set, every event-producing
+    /// step below is persisted before its participant notifications go
+    /// out.
+    settlement_store: Option<Arc<dyn SettlementStore>>,
+    /// When set, `process` rejects any new settlement while `resume` keeps
+    /// driving already in-flight ones to a terminal state. Toggled via
+    /// [`Self::set_resume_only`] to drain a coordinator before a planned
+    /// shutdown.
+    resume_only: AtomicBool,
 }
 
 impl SettlementProcessor {
-    /// Create a new settlement processor.
+    /// Create a new settlement processor with no durable store attached.
     pub fn new(
         lock_manager: Arc<LockManager>,
         participant_manager: Arc<ParticipantManager>,
@@ -31,31 +55,144 @@ impl SettlementProcessor {
         Self {
             lock_manager,
             participant_manager,
+            settlement_store: None,
+            resume_only: AtomicBool::new(false),
         }
     }
 
-    /// Process a settlement through its lifecycle.
+    /// Attach a durable settlement store.
+    pub fn with_settlement_store(mut self, store: Arc<dyn SettlementStore>) -> Self {
+        self.settlement_store = Some(store);
+        self
+    }
+
+    /// Enter or leave resume-only (draining) mode. While resume-only,
+    /// `process` rejects any new settlement with `CoordinatorBusy`, but
+    /// `resume` is unaffected -- settlements already in flight still drive
+    /// to completion.
+    pub fn set_resume_only(&self, resume_only: bool) {
+        self.resume_only.store(resume_only, Ordering::SeqCst);
+    }
+
+    /// Whether the processor is currently in resume-only (draining) mode.
+    pub fn is_resume_only(&self) -> bool {
+        self.resume_only.load(Ordering::SeqCst)
+    }
+
+    /// Persist the current state of `settlement`, if a durable store is
+    /// attached. Mirrors `LockManager::persist`: warns rather than
+    /// aborting the step, since the in-memory settlement (and its event
+    /// log) is already correct -- only crash recovery depends on this
+    /// write having landed.
+    fn persist(&self, settlement: &Settlement) {
+        if let Some(store) = &self.settlement_store {
+            if let Err(e) = store.persist(settlement) {
+                warn!(settlement_id = %settlement.id, error = %e, "Failed to persist settlement state");
+            }
+        }
+    }
+
+    /// Process a settlement through its full lifecycle, stepping it
+    /// forward via [`Self::drive`] until it reaches a terminal (or
+    /// externally-awaited) status. Requires a settlement store to load the
+    /// settlement's current state from -- the processor itself holds no
+    /// settlement index of its own.
     #[instrument(skip(self), fields(settlement_id = %settlement_id))]
     pub async fn process(&self, settlement_id: SettlementId) -> Result<Settlement> {
-        // This is a placeholder implementation showing the settlement flow
-        // In a real implementation, this would interact with the database and participants
+        if self.is_resume_only() {
+            return Err(AtomicSettleError::CoordinatorBusy {
+                retry_after_ms: 1000,
+            });
+        }
+
+        let store = self.settlement_store.as_ref().ok_or_else(|| {
+            AtomicSettleError::InternalError(
+                "settlement processor has no durable store to load from".to_string(),
+            )
+        })?;
+
+        let mut settlement = store
+            .load(settlement_id)?
+            .ok_or(AtomicSettleError::SettlementNotFound(settlement_id))?;
 
         info!(settlement_id = %settlement_id, "Processing settlement");
+        self.drive(&mut settlement).await;
 
-        // 1. Validate
-        // 2. Acquire locks
-        // 3. Commit
-        // 4. Notify participants
-        // 5. Wait for acknowledgments
+        Ok(settlement)
+    }
 
-        // For now, return a mock processed settlement
-        Err(AtomicSettleError::InternalError(
-            "Settlement processor not fully implemented".to_string(),
-        ))
+    /// Drive a settlement found in a non-terminal status at startup (or
+    /// left in flight when the processor was switched into resume-only
+    /// mode) to a terminal one, re-entering the lifecycle at the stage its
+    /// persisted status says it reached rather than restarting from
+    /// `validate` -- a settlement that already holds locks must never be
+    /// re-validated and re-locked from scratch. Shares [`Self::step`] and
+    /// [`Self::drive`] with `process`, so a crash-recovered settlement is
+    /// driven by exactly the same per-transition logic as a fresh one.
+    #[instrument(skip(self, settlement), fields(settlement_id = %settlement.id, status = ?settlement.status))]
+    pub async fn resume(&self, mut settlement: Settlement) -> Result<Settlement> {
+        info!(settlement_id = %settlement.id, status = ?settlement.status, "Resuming in-flight settlement");
+        self.drive(&mut settlement).await;
+
+        Ok(settlement)
+    }
+
+    /// Step `settlement` forward one transition at a time via
+    /// [`Self::step`] until a step reports no further progress (a
+    /// terminal status, or one awaiting external input), invoking
+    /// `handle_failure` uniformly the moment any step errors.
+    async fn drive(&self, settlement: &mut Settlement) {
+        loop {
+            match self.step(settlement).await {
+                Ok(true) => continue,
+                Ok(false) => break,
+                Err(e) => {
+                    self.handle_failure(settlement, &e).await;
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Perform exactly one lifecycle transition for `settlement`,
+    /// dispatching on its *current* status rather than assuming linear
+    /// fall-through -- this is what makes it safe to call again after a
+    /// crash lands mid-transition, and what lets `process` and `resume`
+    /// share one driver. Returns `Ok(true)` if a transition was made and
+    /// there may be more work to do, `Ok(false)` once `settlement` is in a
+    /// status this driver doesn't advance further (a terminal one, or one
+    /// awaiting external input such as `PendingReview`/`PendingFinality`).
+    async fn step(&self, settlement: &mut Settlement) -> Result<bool> {
+        match settlement.status {
+            SettlementStatus::Initiated => {
+                self.validate(settlement).await?;
+                Ok(true)
+            }
+            SettlementStatus::Validated => {
+                self.begin_acquire_locks(settlement).await?;
+                Ok(true)
+            }
+            SettlementStatus::Locking => {
+                self.continue_acquire_locks(settlement).await?;
+                Ok(true)
+            }
+            SettlementStatus::Locked => {
+                self.begin_commit(settlement).await?;
+                Ok(true)
+            }
+            SettlementStatus::Committing => {
+                self.finish_commit(settlement).await?;
+                Ok(true)
+            }
+            SettlementStatus::Committed => {
+                self.notify_completion(settlement).await?;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
     }
 
     /// Validate a settlement.
-    #[allow(dead_code)]
     async fn validate(&self, settlement: &mut Settlement) -> Result<()> {
         info!(
             settlement_id = %settlement.id,
@@ -88,13 +225,17 @@ impl SettlementProcessor {
                 to: SettlementStatus::Validated,
             }
         })?;
+        self.persist(settlement);
 
         Ok(())
     }
 
-    /// Acquire locks from all source participants.
-    #[allow(dead_code)]
-    async fn acquire_locks(&self, settlement: &mut Settlement) -> Result<()> {
+    /// Begin lock acquisition: transition to `Locking` and create+request a
+    /// new lock for every leg, without looking up existing ones. A single
+    /// transition, per [`Self::step`]'s contract -- waiting for those locks
+    /// to confirm is [`Self::continue_acquire_locks`]'s job, on the next
+    /// step.
+    async fn begin_acquire_locks(&self, settlement: &mut Settlement) -> Result<()> {
         info!(
             settlement_id = %settlement.id,
             "Acquiring locks"
@@ -106,9 +247,44 @@ impl SettlementProcessor {
                 to: SettlementStatus::Locking,
             }
         })?;
+        self.persist(settlement);
+
+        self.create_and_request_locks(settlement, &[]).await
+    }
 
-        // Create locks for each leg
+    /// Finish lock acquisition for a settlement in `Locking`. Reuses
+    /// whichever locks the lock manager already has a durable record of
+    /// for this settlement instead of blindly calling `create_lock` again
+    /// (which would double-lock funds for a leg a prior, crash-interrupted
+    /// call here already locked), creates the rest, then waits for every
+    /// leg's lock to confirm before transitioning to `Locked`. Reached both
+    /// immediately after `begin_acquire_locks` in a normal run and when
+    /// resuming a settlement a crash left in `Locking`.
+    async fn continue_acquire_locks(&self, settlement: &mut Settlement) -> Result<()> {
+        let existing = self.lock_manager.get_locks_for_settlement(&settlement.id);
+        self.create_and_request_locks(settlement, &existing).await?;
+        self.wait_for_locks_confirmed(settlement).await
+    }
+
+    /// Ensure every leg has a lock, reusing a matching entry from
+    /// `existing` (keyed by the leg's `from_participant`) before creating a
+    /// new one, then send each newly created lock's request to its
+    /// participant. Rolls back everything held for this settlement if any
+    /// request fails to send.
+    async fn create_and_request_locks(
+        &self,
+        settlement: &mut Settlement,
+        existing: &[Lock],
+    ) -> Result<()> {
         for leg in &mut settlement.legs {
+            if let Some(lock) = existing
+                .iter()
+                .find(|lock| lock.participant_id == leg.from_participant)
+            {
+                leg.lock_id = Some(lock.id);
+                continue;
+            }
+
             let lock = self.lock_manager.create_lock(
                 settlement.id,
                 leg.from_participant.clone(),
@@ -143,33 +319,53 @@ impl SettlementProcessor {
             }
         }
 
-        // Wait for lock confirmations (with timeout)
-        // In real implementation, this would use channels or async wait
+        Ok(())
+    }
+
+    /// Wait for every leg's lock to confirm, then transition to `Locked`.
+    /// Notification-driven rather than polled: registers a per-settlement
+    /// wakeup with the lock manager before checking
+    /// `are_all_locks_confirmed`, so a confirmation landing in the gap
+    /// between the check and the wait is never missed (see
+    /// `LockManager::wakeup_handle`), and lets settlements proceed the
+    /// instant confirmations arrive instead of waiting out a fixed poll
+    /// interval. Releases everything held for this settlement on timeout.
+    /// The wakeup is deregistered on both the success and timeout paths, so
+    /// a late confirmation after rollback can't find a handle to signal.
+    async fn wait_for_locks_confirmed(&self, settlement: &mut Settlement) -> Result<()> {
         let timeout = tokio::time::Duration::from_secs(10);
-        let deadline = tokio::time::Instant::now() + timeout;
-
-        while tokio::time::Instant::now() < deadline {
-            if self.lock_manager.are_all_locks_confirmed(&settlement.id) {
-                settlement.transition_to(SettlementStatus::Locked).map_err(|_| {
-                    AtomicSettleError::InvalidTransition {
-                        from: settlement.status,
-                        to: SettlementStatus::Locked,
-                    }
-                })?;
-                return Ok(());
+        let notify = self.lock_manager.wakeup_handle(settlement.id);
+
+        let confirmed = tokio::time::timeout(timeout, async {
+            while !self.lock_manager.are_all_locks_confirmed(&settlement.id) {
+                notify.notified().await;
             }
-            tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+        })
+        .await;
+
+        self.lock_manager.clear_wakeup(&settlement.id);
+
+        if confirmed.is_err() {
+            warn!(settlement_id = %settlement.id, "Lock acquisition timeout");
+            self.lock_manager.release_locks_for_settlement(&settlement.id);
+            return Err(AtomicSettleError::Timeout("Lock acquisition".to_string()));
         }
 
-        // Lock timeout
-        warn!(settlement_id = %settlement.id, "Lock acquisition timeout");
-        self.lock_manager.release_locks_for_settlement(&settlement.id);
-        Err(AtomicSettleError::Timeout("Lock acquisition".to_string()))
+        settlement.transition_to(SettlementStatus::Locked).map_err(|_| {
+            AtomicSettleError::InvalidTransition {
+                from: settlement.status,
+                to: SettlementStatus::Locked,
+            }
+        })?;
+        self.persist(settlement);
+        Ok(())
     }
 
-    /// Execute atomic commit.
-    #[allow(dead_code)]
-    async fn commit(&self, settlement: &mut Settlement) -> Result<()> {
+    /// Begin atomic commit: transition to `Committing`. A single
+    /// transition, per [`Self::step`]'s contract -- consuming locks and
+    /// finishing the commit is [`Self::finish_commit`]'s job, on the next
+    /// step.
+    async fn begin_commit(&self, settlement: &mut Settlement) -> Result<()> {
         info!(
             settlement_id = %settlement.id,
             "Committing settlement"
@@ -181,15 +377,27 @@ impl SettlementProcessor {
                 to: SettlementStatus::Committing,
             }
         })?;
+        self.persist(settlement);
 
-        // In real implementation:
-        // 1. Start database transaction
-        // 2. Verify all locks still valid
-        // 3. Execute ledger transfers
-        // 4. Mark locks as consumed
-        // 5. Commit transaction
+        Ok(())
+    }
 
-        // Mark locks as consumed
+    /// Finish a commit for a settlement in `Committing`.
+    ///
+    /// In real implementation:
+    /// 1. Verify all locks still valid
+    /// 2. Execute ledger transfers
+    /// 3. Mark locks as consumed
+    /// 4. Commit transaction
+    ///
+    /// `consume_locks_for_settlement` only touches locks still `Active`, so
+    /// calling it again here is a no-op for whichever legs a prior,
+    /// crash-interrupted call here already consumed -- the gate is each
+    /// lock's own persisted status, not any in-memory marker this
+    /// processor might have lost across a restart. Reached both
+    /// immediately after `begin_commit` in a normal run and when resuming
+    /// a settlement a crash left in `Committing`.
+    async fn finish_commit(&self, settlement: &mut Settlement) -> Result<()> {
         self.lock_manager.consume_locks_for_settlement(&settlement.id);
 
         settlement.transition_to(SettlementStatus::Committed).map_err(|_| {
@@ -198,12 +406,12 @@ impl SettlementProcessor {
                 to: SettlementStatus::Committed,
             }
         })?;
+        self.persist(settlement);
 
         Ok(())
     }
 
     /// Notify participants of settlement completion.
-    #[allow(dead_code)]
     async fn notify_completion(&self, settlement: &mut Settlement) -> Result<()> {
         info!(
             settlement_id = %settlement.id,
@@ -227,19 +435,22 @@ impl SettlementProcessor {
                 .await;
         }
 
-        // Mark as settled (acknowledgment is fire-and-forget)
-        settlement.transition_to(SettlementStatus::Settled).map_err(|_| {
+        // Acknowledgment is fire-and-forget; the settlement isn't `Settled`
+        // yet, since its underlying ledger/RTGS posting hasn't reached
+        // finality. It awaits a `Coordinator::handle_finality_event` call
+        // from an external adapter to advance further.
+        settlement.transition_to(SettlementStatus::PendingFinality).map_err(|_| {
             AtomicSettleError::InvalidTransition {
                 from: settlement.status,
-                to: SettlementStatus::Settled,
+                to: SettlementStatus::PendingFinality,
             }
         })?;
+        self.persist(settlement);
 
         Ok(())
     }
 
     /// Handle settlement failure.
-    #[allow(dead_code)]
     async fn handle_failure(&self, settlement: &mut Settlement, error: &AtomicSettleError) {
         error!(
             settlement_id = %settlement.id,
@@ -259,6 +470,7 @@ impl SettlementProcessor {
         };
 
         let _ = settlement.fail(failure);
+        self.persist(settlement);
 
         // Notify participants of failure
         let notification = ParticipantNotification::Settlement {
@@ -280,7 +492,6 @@ impl SettlementProcessor {
 }
 
 /// Convert error to failure code.
-#[allow(dead_code)]
 fn error_to_failure_code(error: &AtomicSettleError) -> FailureCode {
     match error {
         AtomicSettleError::Timeout(_) => FailureCode::LockTimeout,
@@ -296,6 +507,8 @@ fn error_to_failure_code(error: &AtomicSettleError) -> FailureCode {
 mod tests {
     use super::*;
     use crate::config::LockConfig;
+    use crate::settlement_store::JournalSettlementStore;
+    use atomicsettle_common::{AccountId, Currency, Money, ParticipantId, SettlementLeg};
 
     fn create_test_processor() -> SettlementProcessor {
         let lock_manager = Arc::new(LockManager::new(LockConfig::default()));
@@ -309,4 +522,143 @@ mod tests {
         // Just verify it creates successfully
         assert!(true);
     }
+
+    #[tokio::test]
+    async fn test_process_without_a_store_errors() {
+        let processor = create_test_processor();
+        let result = processor.process(SettlementId::new()).await;
+        assert!(result.is_err());
+    }
+
+    fn test_settlement() -> Settlement {
+        let leg = SettlementLeg::new(
+            1,
+            ParticipantId::new("BANK_A"),
+            AccountId::new(ParticipantId::new("BANK_A"), "111", "USD"),
+            ParticipantId::new("BANK_B"),
+            AccountId::new(ParticipantId::new("BANK_B"), "222", "USD"),
+            Money::new(rust_decimal::Decimal::from(1000), Currency::usd()),
+        );
+        Settlement::new("test-key".to_string(), vec![leg])
+    }
+
+    #[tokio::test]
+    async fn test_process_fails_and_persists_when_a_participant_is_offline() {
+        let lock_manager = Arc::new(LockManager::new(LockConfig::default()));
+        let participant_manager = Arc::new(ParticipantManager::new());
+
+        let path = std::env::temp_dir().join(format!("atomicsettle-test-{}.journal", SettlementId::new()));
+        let store: Arc<dyn SettlementStore> = Arc::new(JournalSettlementStore::open(&path).unwrap());
+
+        let settlement = test_settlement();
+        let settlement_id = settlement.id;
+        store.persist(&settlement).unwrap();
+
+        let processor = SettlementProcessor::new(lock_manager, participant_manager)
+            .with_settlement_store(store.clone());
+
+        // Neither BANK_A nor BANK_B is registered as active, so validate()
+        // rejects the settlement before any lock is ever created.
+        let result = processor.process(settlement_id).await.unwrap();
+        assert_eq!(result.status, SettlementStatus::Failed);
+
+        let persisted = store.load(settlement_id).unwrap().unwrap();
+        assert_eq!(persisted.status, SettlementStatus::Failed);
+        assert!(persisted.failure.is_some());
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_resume_only_mode_rejects_new_process_calls() {
+        let processor = create_test_processor();
+        processor.set_resume_only(true);
+
+        let result = processor.process(SettlementId::new()).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_resume_finishes_a_commit_interrupted_after_locks_were_consumed() {
+        let lock_manager = Arc::new(LockManager::new(LockConfig::default()));
+        let participant_manager = Arc::new(ParticipantManager::new());
+
+        let bank_a = ParticipantId::new("BANK_A");
+        let bank_b = ParticipantId::new("BANK_B");
+        let _rx_a = participant_manager.register(bank_a.clone(), "1.0".to_string());
+        let _rx_b = participant_manager.register(bank_b.clone(), "1.0".to_string());
+        participant_manager.activate(&bank_a);
+        participant_manager.activate(&bank_b);
+
+        let path = std::env::temp_dir().join(format!("atomicsettle-test-{}.journal", SettlementId::new()));
+        let store: Arc<dyn SettlementStore> = Arc::new(JournalSettlementStore::open(&path).unwrap());
+
+        let mut settlement = test_settlement();
+        settlement.transition_to(SettlementStatus::Validated).unwrap();
+        settlement.transition_to(SettlementStatus::Locking).unwrap();
+
+        let lock = lock_manager.create_lock(
+            settlement.id,
+            settlement.legs[0].from_participant.clone(),
+            settlement.legs[0].amount.clone(),
+        );
+        settlement.legs[0].lock_id = Some(lock.id);
+        lock_manager.confirm_lock(&lock.id);
+        settlement.transition_to(SettlementStatus::Locked).unwrap();
+        settlement.transition_to(SettlementStatus::Committing).unwrap();
+
+        // Simulate a crash landing after locks were consumed but before the
+        // `Committed` transition made it to disk: consume the lock for
+        // real, then persist the settlement still showing `Committing`.
+        lock_manager.consume_lock(&lock.id);
+        store.persist(&settlement).unwrap();
+
+        let processor = SettlementProcessor::new(lock_manager, participant_manager)
+            .with_settlement_store(store.clone());
+
+        let resumed = processor.resume(settlement).await.unwrap();
+        assert_eq!(resumed.status, SettlementStatus::PendingFinality);
+
+        let persisted = store.load(resumed.id).unwrap().unwrap();
+        assert_eq!(persisted.status, SettlementStatus::PendingFinality);
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_resume_is_a_no_op_for_an_already_terminal_settlement() {
+        let lock_manager = Arc::new(LockManager::new(LockConfig::default()));
+        let participant_manager = Arc::new(ParticipantManager::new());
+        let processor = SettlementProcessor::new(lock_manager, participant_manager);
+
+        let mut settlement = test_settlement();
+        settlement.reject(FailureCode::ComplianceRejected).unwrap();
+
+        let resumed = processor.resume(settlement.clone()).await.unwrap();
+        assert_eq!(resumed.status, SettlementStatus::Rejected);
+    }
+
+    #[tokio::test]
+    async fn test_step_advances_exactly_one_status_per_call() {
+        let lock_manager = Arc::new(LockManager::new(LockConfig::default()));
+        let participant_manager = Arc::new(ParticipantManager::new());
+
+        let bank_a = ParticipantId::new("BANK_A");
+        let bank_b = ParticipantId::new("BANK_B");
+        let _rx_a = participant_manager.register(bank_a.clone(), "1.0".to_string());
+        let _rx_b = participant_manager.register(bank_b.clone(), "1.0".to_string());
+        participant_manager.activate(&bank_a);
+        participant_manager.activate(&bank_b);
+
+        let processor = SettlementProcessor::new(lock_manager, participant_manager);
+
+        let mut settlement = test_settlement();
+        assert_eq!(settlement.status, SettlementStatus::Initiated);
+
+        assert!(processor.step(&mut settlement).await.unwrap());
+        assert_eq!(settlement.status, SettlementStatus::Validated);
+
+        assert!(processor.step(&mut settlement).await.unwrap());
+        assert_eq!(settlement.status, SettlementStatus::Locking);
+    }
 }
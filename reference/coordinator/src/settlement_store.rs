@@ -0,0 +1,213 @@
+//! Durable storage for settlements, so a coordinator crash mid-settlement
+//! doesn't silently lose an in-progress transfer or its idempotency
+//! guarantees.
+//!
+//! Modeled on Lightning's `ChannelMonitor`/`Persist` split: every state
+//! transition is written before `Coordinator`/`SettlementProcessor` act on
+//! it. A failed write from the `Coordinator` is surfaced and parks it in
+//! `Stalled`; `SettlementProcessor` only logs a failed write and carries on
+//! with the step.
+
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+use atomicsettle_common::{AtomicSettleError, Settlement, SettlementId, SettlementStatus};
+
+/// Error returned when a durable write fails. Distinct from
+/// `AtomicSettleError` so callers are forced to decide how to react (the
+/// coordinator parks itself in `Stalled` rather than proceeding with
+/// unpersisted state), mirroring Lightning's `MonitorUpdateError`.
+#[derive(Debug, thiserror::Error)]
+#[error("settlement store write failed: {0}")]
+pub struct MonitorUpdateError(pub String);
+
+impl From<MonitorUpdateError> for AtomicSettleError {
+    fn from(e: MonitorUpdateError) -> Self {
+        AtomicSettleError::DatabaseError(e.0)
+    }
+}
+
+/// Durable storage backend for settlements.
+pub trait SettlementStore: Send + Sync {
+    /// Persist the full current state of a settlement (insert or overwrite).
+    fn persist(&self, settlement: &Settlement) -> Result<(), MonitorUpdateError>;
+
+    /// Record a status change without rewriting the whole settlement.
+    fn update_status(
+        &self,
+        settlement_id: SettlementId,
+        status: SettlementStatus,
+    ) -> Result<(), MonitorUpdateError>;
+
+    /// Remove a settlement from durable storage (fully terminal and no
+    /// longer needed for recovery or idempotency).
+    fn remove(&self, settlement_id: SettlementId) -> Result<(), MonitorUpdateError>;
+
+    /// Load every settlement currently in durable storage.
+    fn load_all(&self) -> Result<Vec<Settlement>, MonitorUpdateError>;
+
+    /// Load a single settlement by ID, rebuilt from its own event log via
+    /// [`Settlement::rebuild_from_events`] rather than trusted verbatim
+    /// from whatever was last written -- the event log, not the snapshot,
+    /// is the source of truth. Returns `None` if no settlement with this
+    /// ID is in durable storage.
+    fn load(&self, settlement_id: SettlementId) -> Result<Option<Settlement>, MonitorUpdateError> {
+        Ok(self
+            .load_all()?
+            .into_iter()
+            .find(|settlement| settlement.id == settlement_id)
+            .map(Settlement::rebuild_from_events))
+    }
+
+    /// Flush any buffered writes to stable storage.
+    fn flush(&self) -> Result<(), MonitorUpdateError> {
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+enum JournalRecord {
+    Full(Settlement),
+    StatusUpdate(SettlementId, SettlementStatus),
+    Removed(SettlementId),
+}
+
+/// Append-only, write-ahead-journal backed `SettlementStore`.
+pub struct JournalSettlementStore {
+    path: PathBuf,
+    file: Mutex<File>,
+}
+
+impl JournalSettlementStore {
+    /// Open (creating if needed) a journal file at `path`.
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, MonitorUpdateError> {
+        let path = path.into();
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .read(true)
+            .open(&path)
+            .map_err(|e| MonitorUpdateError(format!("opening settlement journal: {e}")))?;
+
+        Ok(Self {
+            path,
+            file: Mutex::new(file),
+        })
+    }
+
+    fn append(&self, record: &JournalRecord) -> Result<(), MonitorUpdateError> {
+        let line = serde_json::to_string(record)
+            .map_err(|e| MonitorUpdateError(format!("encoding journal record: {e}")))?;
+
+        let mut file = self.file.lock().expect("settlement journal mutex poisoned");
+        writeln!(file, "{line}")
+            .map_err(|e| MonitorUpdateError(format!("appending to settlement journal: {e}")))?;
+        file.flush()
+            .map_err(|e| MonitorUpdateError(format!("flushing settlement journal: {e}")))
+    }
+}
+
+impl SettlementStore for JournalSettlementStore {
+    fn persist(&self, settlement: &Settlement) -> Result<(), MonitorUpdateError> {
+        self.append(&JournalRecord::Full(settlement.clone()))
+    }
+
+    fn update_status(
+        &self,
+        settlement_id: SettlementId,
+        status: SettlementStatus,
+    ) -> Result<(), MonitorUpdateError> {
+        self.append(&JournalRecord::StatusUpdate(settlement_id, status))
+    }
+
+    fn remove(&self, settlement_id: SettlementId) -> Result<(), MonitorUpdateError> {
+        self.append(&JournalRecord::Removed(settlement_id))
+    }
+
+    fn load_all(&self) -> Result<Vec<Settlement>, MonitorUpdateError> {
+        let file = File::open(&self.path)
+            .map_err(|e| MonitorUpdateError(format!("reopening settlement journal: {e}")))?;
+
+        let mut latest: std::collections::HashMap<SettlementId, Option<Settlement>> =
+            std::collections::HashMap::new();
+
+        for line in BufReader::new(file).lines() {
+            let line =
+                line.map_err(|e| MonitorUpdateError(format!("reading settlement journal: {e}")))?;
+            if line.is_empty() {
+                continue;
+            }
+            let record: JournalRecord = serde_json::from_str(&line)
+                .map_err(|e| MonitorUpdateError(format!("decoding journal record: {e}")))?;
+
+            match record {
+                JournalRecord::Full(settlement) => {
+                    latest.insert(settlement.id, Some(settlement));
+                }
+                JournalRecord::StatusUpdate(settlement_id, status) => {
+                    if let Some(Some(settlement)) = latest.get_mut(&settlement_id) {
+                        settlement.status = status;
+                    }
+                }
+                JournalRecord::Removed(settlement_id) => {
+                    latest.insert(settlement_id, None);
+                }
+            }
+        }
+
+        Ok(latest.into_values().flatten().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use atomicsettle_common::{AccountId, Currency, Money, ParticipantId, SettlementLeg, SettlementStatus};
+
+    fn open_test_store() -> (JournalSettlementStore, PathBuf) {
+        let path = std::env::temp_dir().join(format!("atomicsettle-test-{}.journal", SettlementId::new()));
+        (JournalSettlementStore::open(&path).unwrap(), path)
+    }
+
+    fn test_settlement() -> Settlement {
+        let leg = SettlementLeg::new(
+            1,
+            ParticipantId::new("BANK_A"),
+            AccountId::new(ParticipantId::new("BANK_A"), "111", "USD"),
+            ParticipantId::new("BANK_B"),
+            AccountId::new(ParticipantId::new("BANK_B"), "222", "USD"),
+            Money::new(rust_decimal::Decimal::from(1000), Currency::usd()),
+        );
+        Settlement::new("test-key".to_string(), vec![leg])
+    }
+
+    #[test]
+    fn test_load_rebuilds_status_from_the_settlements_own_event_log() {
+        let (store, path) = open_test_store();
+
+        let mut settlement = test_settlement();
+        settlement
+            .transition_to(SettlementStatus::Validated)
+            .unwrap();
+        store.persist(&settlement).unwrap();
+
+        let loaded = store.load(settlement.id).unwrap().unwrap();
+        assert_eq!(loaded.status, SettlementStatus::Validated);
+        assert_eq!(loaded.id, settlement.id);
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_load_returns_none_for_unknown_settlement() {
+        let (store, path) = open_test_store();
+
+        assert!(store.load(SettlementId::new()).unwrap().is_none());
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -15,6 +15,10 @@ pub enum CoordinatorState {
     Recovering,
     /// Coordinator is a follower (not accepting direct requests).
     Follower,
+    /// A durable-storage write failed; the coordinator has parked itself
+    /// and stopped accepting new work rather than proceed with unpersisted
+    /// state. Requires operator intervention to clear.
+    Stalled,
 }
 
 impl CoordinatorState {
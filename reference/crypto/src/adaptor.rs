@@ -0,0 +1,281 @@
+//! Adaptor (scriptless-script) Schnorr signatures over Ristretto25519.
+//!
+//! Lets two banks on distinct ledgers settle an atomic swap without a
+//! trusted coordinator, the way the xmr-btc atomic swap protocol links two
+//! otherwise-independent chains' settlements to the same secret via an
+//! adaptable Schnorr signature. Party A publishes an *encrypted* signature
+//! -- an ordinary Schnorr pre-signature offset by an adaptor point
+//! `T = y*G` -- that only becomes spendable once adapted with the scalar
+//! `y`. When A later broadcasts the completed signature to claim its own
+//! leg, B recovers `y` via [`recover_scalar`] by subtracting A's adaptor
+//! from the completed signature's scalar, then uses `y` to complete its
+//! own encrypted signature on the other leg via
+//! [`EncryptedSignature::decrypt_signature`].
+//!
+//! [`EncryptedSignature::verify`] lets either side confirm a received
+//! adaptor signature is well-formed *before* locking funds behind it, the
+//! same way [`crate::threshold::ThresholdSignature::verify`] lets a
+//! verifier check an aggregate signature without knowing who produced it.
+//! This lives alongside [`crate::threshold`] rather than extending
+//! [`crate::signing::SigningKey`] because both need raw Ristretto25519
+//! scalar/point arithmetic that `SigningKey`'s Ed25519/secp256k1 backends
+//! don't expose.
+
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+use curve25519_dalek::ristretto::{CompressedRistretto, RistrettoPoint};
+use curve25519_dalek::scalar::Scalar;
+use sha2::{Digest, Sha512};
+
+use crate::signing::Signature;
+use crate::{CryptoError, Result};
+
+/// A party's secret scalar `y` behind an [`EncryptionPoint`] `T = y*G`.
+/// Only the party that generated `T` holds this until it leaks; it's what
+/// [`EncryptedSignature::decrypt_signature`] needs to complete a
+/// pre-signature, and what [`recover_scalar`] extracts once a completed
+/// signature is public.
+#[derive(Clone, Copy)]
+pub struct AdaptorSecret(Scalar);
+
+impl AdaptorSecret {
+    /// Draw a fresh random secret `y`.
+    pub fn generate() -> Self {
+        Self(Scalar::random(&mut rand::thread_rng()))
+    }
+
+    /// The public adaptor point `T = y*G` a counterparty encrypts its
+    /// signature under.
+    pub fn encryption_point(&self) -> EncryptionPoint {
+        EncryptionPoint(RISTRETTO_BASEPOINT_POINT * self.0)
+    }
+}
+
+/// The public point `T = y*G` an [`EncryptedSignature`] is encrypted
+/// under. Handed to the signer so it can produce an adaptor signature
+/// over this point, and later to whoever holds the matching
+/// [`AdaptorSecret`] so it can decrypt that signature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EncryptionPoint(RistrettoPoint);
+
+impl EncryptionPoint {
+    /// Compressed, 32-byte encoding of `T`.
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.0.compress().to_bytes()
+    }
+}
+
+/// A signing key for the adaptor scheme, over the same Ristretto25519
+/// group [`crate::threshold`] uses.
+pub struct AdaptorSigningKey {
+    secret: Scalar,
+    public: RistrettoPoint,
+}
+
+impl AdaptorSigningKey {
+    /// Generate a fresh random signing key.
+    pub fn generate() -> Self {
+        let secret = Scalar::random(&mut rand::thread_rng());
+        Self {
+            secret,
+            public: RISTRETTO_BASEPOINT_POINT * secret,
+        }
+    }
+
+    /// This key's verifying key.
+    pub fn verifying_key(&self) -> AdaptorVerifyingKey {
+        AdaptorVerifyingKey(self.public)
+    }
+
+    /// Produce an encrypted ("adaptor") signature over `message`, under
+    /// `encryption_point`. The result satisfies
+    /// [`EncryptedSignature::verify`] but can't be used to move funds
+    /// until [`EncryptedSignature::decrypt_signature`] adapts it with the
+    /// matching [`AdaptorSecret`].
+    pub fn encrypt_signature(
+        &self,
+        message: &[u8],
+        encryption_point: &EncryptionPoint,
+    ) -> EncryptedSignature {
+        let nonce = Scalar::random(&mut rand::thread_rng());
+        let r_prime = RISTRETTO_BASEPOINT_POINT * nonce;
+        let r = r_prime + encryption_point.0;
+        let c = challenge(r, self.public, message);
+        let s_tilde = nonce + c * self.secret;
+
+        EncryptedSignature {
+            r_prime,
+            encryption_point: encryption_point.0,
+            s_tilde,
+        }
+    }
+}
+
+/// A verifying (public) key for the adaptor scheme.
+#[derive(Debug, Clone, Copy)]
+pub struct AdaptorVerifyingKey(RistrettoPoint);
+
+impl AdaptorVerifyingKey {
+    /// Verify an ordinary, already-completed Schnorr signature produced
+    /// by [`EncryptedSignature::decrypt_signature`] (or reconstructed with
+    /// a recovered scalar), the way a non-adaptor signature would be
+    /// verified.
+    pub fn verify_completed(&self, message: &[u8], signature: &Signature) -> Result<()> {
+        if signature.bytes.len() != 64 {
+            return Err(CryptoError::InvalidSignature);
+        }
+
+        let r_bytes: [u8; 32] = signature.bytes[..32]
+            .try_into()
+            .map_err(|_| CryptoError::InvalidSignature)?;
+        let s_bytes: [u8; 32] = signature.bytes[32..]
+            .try_into()
+            .map_err(|_| CryptoError::InvalidSignature)?;
+
+        let r = CompressedRistretto(r_bytes)
+            .decompress()
+            .ok_or(CryptoError::InvalidSignature)?;
+        let s = Scalar::from_canonical_bytes(s_bytes)
+            .into_option()
+            .ok_or(CryptoError::InvalidSignature)?;
+
+        let c = challenge(r, self.0, message);
+        if RISTRETTO_BASEPOINT_POINT * s == r + self.0 * c {
+            Ok(())
+        } else {
+            Err(CryptoError::InvalidSignature)
+        }
+    }
+}
+
+/// An encrypted ("adaptor") Schnorr signature: a pre-signature that only
+/// becomes valid once [`EncryptedSignature::decrypt_signature`]d with the
+/// scalar behind the [`EncryptionPoint`] it was produced under.
+#[derive(Debug, Clone)]
+pub struct EncryptedSignature {
+    r_prime: RistrettoPoint,
+    encryption_point: RistrettoPoint,
+    s_tilde: Scalar,
+}
+
+impl EncryptedSignature {
+    /// Check this encrypted signature is well-formed against
+    /// `verifying_key` over `message`, without needing the adaptor
+    /// secret -- lets a counterparty confirm A's encrypted signature is
+    /// good before locking its own leg behind the same encryption point.
+    pub fn verify(&self, verifying_key: &AdaptorVerifyingKey, message: &[u8]) -> Result<()> {
+        let r = self.r_prime + self.encryption_point;
+        let c = challenge(r, verifying_key.0, message);
+
+        if RISTRETTO_BASEPOINT_POINT * self.s_tilde == self.r_prime + verifying_key.0 * c {
+            Ok(())
+        } else {
+            Err(CryptoError::InvalidSignature)
+        }
+    }
+
+    /// Complete this encrypted signature into an ordinary, spendable
+    /// [`Signature`] using the secret scalar `y` behind its encryption
+    /// point.
+    pub fn decrypt_signature(&self, secret_scalar: &AdaptorSecret) -> Signature {
+        let s = self.s_tilde + secret_scalar.0;
+        let r = self.r_prime + self.encryption_point;
+
+        let mut bytes = Vec::with_capacity(64);
+        bytes.extend_from_slice(r.compress().as_bytes());
+        bytes.extend_from_slice(s.as_bytes());
+
+        Signature {
+            bytes,
+            key_id: "adaptor".to_string(),
+            algorithm: "Adaptor-Ristretto25519".to_string(),
+        }
+    }
+}
+
+/// Recover the adaptor secret `y` from an encrypted signature and its
+/// completed counterpart, the way a counterparty in an atomic swap learns
+/// the secret once the other leg's completed signature is broadcast:
+/// `y = s - s̃`.
+pub fn recover_scalar(encrypted: &EncryptedSignature, completed: &Signature) -> Result<AdaptorSecret> {
+    if completed.bytes.len() != 64 {
+        return Err(CryptoError::InvalidSignature);
+    }
+
+    let s_bytes: [u8; 32] = completed.bytes[32..]
+        .try_into()
+        .map_err(|_| CryptoError::InvalidSignature)?;
+    let s = Scalar::from_canonical_bytes(s_bytes)
+        .into_option()
+        .ok_or(CryptoError::InvalidSignature)?;
+
+    Ok(AdaptorSecret(s - encrypted.s_tilde))
+}
+
+/// Schnorr challenge `c = H(R, X, m)`, the same construction
+/// [`crate::threshold`] uses for its own Ristretto25519 Schnorr variant.
+fn challenge(r: RistrettoPoint, public: RistrettoPoint, message: &[u8]) -> Scalar {
+    let mut hasher = Sha512::new();
+    hasher.update(b"atomicsettle-adaptor-challenge");
+    hasher.update(r.compress().to_bytes());
+    hasher.update(public.compress().to_bytes());
+    hasher.update(message);
+    Scalar::from_hash(hasher)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypted_signature_verifies_without_secret() {
+        let signer = AdaptorSigningKey::generate();
+        let secret = AdaptorSecret::generate();
+        let point = secret.encryption_point();
+        let message = b"settle leg A: 1000 USD BANK_A -> BANK_B";
+
+        let encrypted = signer.encrypt_signature(message, &point);
+        assert!(encrypted.verify(&signer.verifying_key(), message).is_ok());
+    }
+
+    #[test]
+    fn test_decrypt_then_verify_completed_signature() {
+        let signer = AdaptorSigningKey::generate();
+        let secret = AdaptorSecret::generate();
+        let point = secret.encryption_point();
+        let message = b"settle leg A: 1000 USD BANK_A -> BANK_B";
+
+        let encrypted = signer.encrypt_signature(message, &point);
+        let completed = encrypted.decrypt_signature(&secret);
+
+        assert!(signer
+            .verifying_key()
+            .verify_completed(message, &completed)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_recover_scalar_extracts_secret_from_completed_signature() {
+        let signer = AdaptorSigningKey::generate();
+        let secret = AdaptorSecret::generate();
+        let point = secret.encryption_point();
+        let message = b"settle leg A: 1000 USD BANK_A -> BANK_B";
+
+        let encrypted = signer.encrypt_signature(message, &point);
+        let completed = encrypted.decrypt_signature(&secret);
+
+        let recovered = recover_scalar(&encrypted, &completed).unwrap();
+        assert_eq!(recovered.encryption_point(), point);
+    }
+
+    #[test]
+    fn test_encrypted_signature_rejects_wrong_message() {
+        let signer = AdaptorSigningKey::generate();
+        let secret = AdaptorSecret::generate();
+        let point = secret.encryption_point();
+
+        let encrypted = signer.encrypt_signature(b"settle 1000 USD", &point);
+        assert!(encrypted
+            .verify(&signer.verifying_key(), b"settle 2000 USD")
+            .is_err());
+    }
+}
@@ -1,67 +1,126 @@
-//! AES-GCM encryption support.
+//! AEAD encryption support, pluggable across algorithms via
+//! [`AeadAlgorithm`], with versioned keys so a payload carries its own
+//! [`EncryptedPayload::key_id`] and can be migrated from one key
+//! generation to the next via [`rotate`] without every consumer having to
+//! track which key produced which ciphertext externally.
+
+use std::collections::HashMap;
 
 use aes_gcm::{
-    aead::{Aead, KeyInit},
-    Aes256Gcm, Nonce,
+    aead::{Aead as _, KeyInit as _, Payload},
+    Aes256Gcm, Nonce as AesNonce,
 };
+use chacha20poly1305::{ChaCha20Poly1305, Nonce as ChaChaNonce};
 use rand::RngCore;
 use serde::{Deserialize, Serialize};
 
 use crate::{CryptoError, Result};
 
+/// The AEAD cipher an [`EncryptedPayload`] was encrypted under.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AeadAlgorithm {
+    /// AES-256 in GCM mode. Fastest where AES-NI hardware acceleration is
+    /// available.
+    Aes256Gcm,
+    /// ChaCha20-Poly1305. A pure-software, constant-time construction, so
+    /// it doesn't depend on AES hardware being present -- the same
+    /// rationale [`crate::backup`] uses for passphrase-derived keys.
+    ChaCha20Poly1305,
+}
+
+impl std::fmt::Display for AeadAlgorithm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::Aes256Gcm => "AES-256-GCM",
+            Self::ChaCha20Poly1305 => "ChaCha20-Poly1305",
+        };
+        write!(f, "{s}")
+    }
+}
+
+impl std::str::FromStr for AeadAlgorithm {
+    type Err = CryptoError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "AES-256-GCM" => Ok(Self::Aes256Gcm),
+            "ChaCha20-Poly1305" => Ok(Self::ChaCha20Poly1305),
+            other => Err(CryptoError::DecryptionFailed(format!(
+                "Unsupported algorithm: {other}"
+            ))),
+        }
+    }
+}
+
 /// Encrypted payload with metadata.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EncryptedPayload {
     /// Algorithm identifier.
     pub algorithm: String,
-    /// Nonce (12 bytes for AES-GCM).
+    /// Identifier of the key generation this payload was encrypted under,
+    /// so a [`KeyRegistry`] (or [`rotate`]) can select the right key
+    /// without the caller tracking key/generation mappings separately.
+    pub key_id: String,
+    /// Nonce (12 bytes, for both supported AEADs).
     pub nonce: Vec<u8>,
     /// Ciphertext.
     pub ciphertext: Vec<u8>,
 }
 
-/// Encrypt plaintext using AES-256-GCM.
+/// Encrypt `plaintext` under `algorithm`, stamping `key_id` onto the
+/// resulting payload.
 ///
 /// # Arguments
 /// * `key` - 32-byte encryption key
+/// * `key_id` - Identifier of `key`'s generation, recorded on the payload
 /// * `plaintext` - Data to encrypt
 /// * `aad` - Additional authenticated data (not encrypted, but authenticated)
-pub fn encrypt(key: &[u8; 32], plaintext: &[u8], aad: Option<&[u8]>) -> Result<EncryptedPayload> {
-    let cipher = Aes256Gcm::new_from_slice(key)
-        .map_err(|e| CryptoError::EncryptionFailed(e.to_string()))?;
-
-    // Generate random nonce
+/// * `algorithm` - Which AEAD cipher to use
+pub fn encrypt(
+    key: &[u8; 32],
+    key_id: &str,
+    plaintext: &[u8],
+    aad: Option<&[u8]>,
+    algorithm: AeadAlgorithm,
+) -> Result<EncryptedPayload> {
     let mut nonce_bytes = [0u8; 12];
     rand::thread_rng().fill_bytes(&mut nonce_bytes);
-    let nonce = Nonce::from_slice(&nonce_bytes);
 
-    let ciphertext = cipher
-        .encrypt(nonce, plaintext)
-        .map_err(|e| CryptoError::EncryptionFailed(e.to_string()))?;
+    let ciphertext = match algorithm {
+        AeadAlgorithm::Aes256Gcm => {
+            let cipher = Aes256Gcm::new_from_slice(key)
+                .map_err(|e| CryptoError::EncryptionFailed(e.to_string()))?;
+            let nonce = AesNonce::from_slice(&nonce_bytes);
+            cipher
+                .encrypt(nonce, Payload { msg: plaintext, aad: aad.unwrap_or(&[]) })
+                .map_err(|e| CryptoError::EncryptionFailed(e.to_string()))?
+        }
+        AeadAlgorithm::ChaCha20Poly1305 => {
+            let cipher = ChaCha20Poly1305::new_from_slice(key)
+                .map_err(|e| CryptoError::EncryptionFailed(e.to_string()))?;
+            let nonce = ChaChaNonce::from_slice(&nonce_bytes);
+            cipher
+                .encrypt(nonce, Payload { msg: plaintext, aad: aad.unwrap_or(&[]) })
+                .map_err(|e| CryptoError::EncryptionFailed(e.to_string()))?
+        }
+    };
 
     Ok(EncryptedPayload {
-        algorithm: "AES-256-GCM".to_string(),
+        algorithm: algorithm.to_string(),
+        key_id: key_id.to_string(),
         nonce: nonce_bytes.to_vec(),
         ciphertext,
     })
 }
 
-/// Decrypt ciphertext using AES-256-GCM.
+/// Decrypt `payload`, dispatching on its stamped `algorithm`.
 ///
 /// # Arguments
 /// * `key` - 32-byte encryption key
 /// * `payload` - Encrypted payload to decrypt
 /// * `aad` - Additional authenticated data (must match what was used during encryption)
 pub fn decrypt(key: &[u8; 32], payload: &EncryptedPayload, aad: Option<&[u8]>) -> Result<Vec<u8>> {
-    if payload.algorithm != "AES-256-GCM" {
-        return Err(CryptoError::DecryptionFailed(format!(
-            "Unsupported algorithm: {}",
-            payload.algorithm
-        )));
-    }
-
-    let cipher = Aes256Gcm::new_from_slice(key)
-        .map_err(|e| CryptoError::DecryptionFailed(e.to_string()))?;
+    let algorithm: AeadAlgorithm = payload.algorithm.parse()?;
 
     let nonce_bytes: [u8; 12] = payload
         .nonce
@@ -69,11 +128,24 @@ pub fn decrypt(key: &[u8; 32], payload: &EncryptedPayload, aad: Option<&[u8]>) -
         .try_into()
         .map_err(|_| CryptoError::DecryptionFailed("Invalid nonce length".to_string()))?;
 
-    let nonce = Nonce::from_slice(&nonce_bytes);
-
-    cipher
-        .decrypt(nonce, payload.ciphertext.as_slice())
-        .map_err(|_| CryptoError::DecryptionFailed("Decryption failed".to_string()))
+    match algorithm {
+        AeadAlgorithm::Aes256Gcm => {
+            let cipher = Aes256Gcm::new_from_slice(key)
+                .map_err(|e| CryptoError::DecryptionFailed(e.to_string()))?;
+            let nonce = AesNonce::from_slice(&nonce_bytes);
+            cipher
+                .decrypt(nonce, Payload { msg: payload.ciphertext.as_slice(), aad: aad.unwrap_or(&[]) })
+                .map_err(|_| CryptoError::DecryptionFailed("Decryption failed".to_string()))
+        }
+        AeadAlgorithm::ChaCha20Poly1305 => {
+            let cipher = ChaCha20Poly1305::new_from_slice(key)
+                .map_err(|e| CryptoError::DecryptionFailed(e.to_string()))?;
+            let nonce = ChaChaNonce::from_slice(&nonce_bytes);
+            cipher
+                .decrypt(nonce, Payload { msg: payload.ciphertext.as_slice(), aad: aad.unwrap_or(&[]) })
+                .map_err(|_| CryptoError::DecryptionFailed("Decryption failed".to_string()))
+        }
+    }
 }
 
 /// Derive an encryption key using HKDF.
@@ -89,18 +161,125 @@ pub fn derive_key(shared_secret: &[u8], salt: &[u8], info: &[u8]) -> Result<[u8;
     Ok(key)
 }
 
+/// Derive a generation-scoped encryption key: like [`derive_key`], but
+/// folds `generation` into the HKDF `info` so rotating to a new generation
+/// produces an unrelated key even from the same `shared_secret`/`salt`.
+pub fn derive_key_for_generation(
+    shared_secret: &[u8],
+    salt: &[u8],
+    info: &[u8],
+    generation: u64,
+) -> Result<[u8; 32]> {
+    let mut scoped_info = info.to_vec();
+    scoped_info.extend_from_slice(&generation.to_be_bytes());
+    derive_key(shared_secret, salt, &scoped_info)
+}
+
+/// A small registry of encryption keys by generation id, mirroring how
+/// cross-chain settlement routers support an "update key" operation
+/// without invalidating state encrypted under prior keys. New payloads are
+/// always encrypted under the active generation; older generations stay
+/// registered so ciphertext from before the last rotation still decrypts.
+pub struct KeyRegistry {
+    keys: HashMap<String, [u8; 32]>,
+    active_key_id: String,
+}
+
+impl KeyRegistry {
+    /// Start a registry with a single, active generation.
+    pub fn new(key_id: impl Into<String>, key: [u8; 32]) -> Self {
+        let key_id = key_id.into();
+        let mut keys = HashMap::new();
+        keys.insert(key_id.clone(), key);
+        Self {
+            keys,
+            active_key_id: key_id,
+        }
+    }
+
+    /// The generation new payloads are encrypted under.
+    pub fn active_key_id(&self) -> &str {
+        &self.active_key_id
+    }
+
+    /// Register `key` under `key_id` and make it the active generation.
+    /// Previously registered generations remain available for decrypting
+    /// their own payloads.
+    pub fn add_generation(&mut self, key_id: impl Into<String>, key: [u8; 32]) {
+        let key_id = key_id.into();
+        self.keys.insert(key_id.clone(), key);
+        self.active_key_id = key_id;
+    }
+
+    /// Encrypt under the active generation, stamping its `key_id` onto the
+    /// payload.
+    pub fn encrypt(
+        &self,
+        plaintext: &[u8],
+        aad: Option<&[u8]>,
+        algorithm: AeadAlgorithm,
+    ) -> Result<EncryptedPayload> {
+        let key = self.keys[&self.active_key_id];
+        encrypt(&key, &self.active_key_id, plaintext, aad, algorithm)
+    }
+
+    /// Decrypt `payload`, selecting the key registered under its own
+    /// `key_id` rather than requiring the caller to know which generation
+    /// produced it.
+    pub fn decrypt(&self, payload: &EncryptedPayload, aad: Option<&[u8]>) -> Result<Vec<u8>> {
+        let key = self.keys.get(&payload.key_id).ok_or_else(|| {
+            CryptoError::DecryptionFailed(format!(
+                "no key registered for key_id {}",
+                payload.key_id
+            ))
+        })?;
+        decrypt(key, payload, aad)
+    }
+}
+
+/// Migrate `payload` from `old_key` to `new_key_id`/`new_key`: decrypt
+/// under the old key and re-encrypt the recovered plaintext under the new
+/// one, preserving the original AEAD algorithm. Lets at-rest ciphertext be
+/// migrated generation-by-generation, one payload at a time, rather than
+/// needing every generation's key available simultaneously.
+pub fn rotate(
+    payload: &EncryptedPayload,
+    old_key: &[u8; 32],
+    new_key_id: &str,
+    new_key: &[u8; 32],
+) -> Result<EncryptedPayload> {
+    let algorithm: AeadAlgorithm = payload.algorithm.parse()?;
+    let plaintext = decrypt(old_key, payload, None)?;
+    encrypt(new_key, new_key_id, &plaintext, None, algorithm)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
-    fn test_encrypt_decrypt() {
+    fn test_encrypt_decrypt_aes256gcm() {
         let key = [0u8; 32]; // Zero key for testing only
         let plaintext = b"Hello, AtomicSettle!";
 
-        let encrypted = encrypt(&key, plaintext, None).unwrap();
+        let encrypted = encrypt(&key, "gen-0", plaintext, None, AeadAlgorithm::Aes256Gcm).unwrap();
+        assert_eq!(encrypted.algorithm, "AES-256-GCM");
+        assert_eq!(encrypted.key_id, "gen-0");
+
         let decrypted = decrypt(&key, &encrypted, None).unwrap();
+        assert_eq!(decrypted, plaintext);
+    }
 
+    #[test]
+    fn test_encrypt_decrypt_chacha20poly1305() {
+        let key = [0u8; 32];
+        let plaintext = b"Hello, AtomicSettle!";
+
+        let encrypted =
+            encrypt(&key, "gen-0", plaintext, None, AeadAlgorithm::ChaCha20Poly1305).unwrap();
+        assert_eq!(encrypted.algorithm, "ChaCha20-Poly1305");
+
+        let decrypted = decrypt(&key, &encrypted, None).unwrap();
         assert_eq!(decrypted, plaintext);
     }
 
@@ -109,8 +288,8 @@ mod tests {
         let key = [0u8; 32];
         let plaintext = b"Same message";
 
-        let enc1 = encrypt(&key, plaintext, None).unwrap();
-        let enc2 = encrypt(&key, plaintext, None).unwrap();
+        let enc1 = encrypt(&key, "gen-0", plaintext, None, AeadAlgorithm::Aes256Gcm).unwrap();
+        let enc2 = encrypt(&key, "gen-0", plaintext, None, AeadAlgorithm::Aes256Gcm).unwrap();
 
         // Nonces should be different
         assert_ne!(enc1.nonce, enc2.nonce);
@@ -124,9 +303,87 @@ mod tests {
         let key2 = [1u8; 32];
         let plaintext = b"Secret message";
 
-        let encrypted = encrypt(&key1, plaintext, None).unwrap();
+        let encrypted = encrypt(&key1, "gen-0", plaintext, None, AeadAlgorithm::Aes256Gcm).unwrap();
         let result = decrypt(&key2, &encrypted, None);
 
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_cross_algorithm_decryption_fails() {
+        let key = [0u8; 32];
+        let plaintext = b"Secret message";
+
+        let mut encrypted =
+            encrypt(&key, "gen-0", plaintext, None, AeadAlgorithm::Aes256Gcm).unwrap();
+        encrypted.algorithm = AeadAlgorithm::ChaCha20Poly1305.to_string();
+
+        let result = decrypt(&key, &encrypted, None);
+        assert!(matches!(result, Err(CryptoError::DecryptionFailed(_))));
+    }
+
+    #[test]
+    fn test_unknown_algorithm_identifier_rejected() {
+        let key = [0u8; 32];
+        let mut encrypted = encrypt(&key, "gen-0", b"data", None, AeadAlgorithm::Aes256Gcm).unwrap();
+        encrypted.algorithm = "ROT13".to_string();
+
+        let result = decrypt(&key, &encrypted, None);
+        assert!(matches!(result, Err(CryptoError::DecryptionFailed(_))));
+    }
+
+    #[test]
+    fn test_derive_key_for_generation_differs_per_generation() {
+        let secret = b"shared secret";
+        let salt = b"salt";
+        let info = b"atomicsettle-ledger";
+
+        let gen0 = derive_key_for_generation(secret, salt, info, 0).unwrap();
+        let gen1 = derive_key_for_generation(secret, salt, info, 1).unwrap();
+
+        assert_ne!(gen0, gen1);
+    }
+
+    #[test]
+    fn test_key_registry_encrypts_under_active_generation() {
+        let registry = KeyRegistry::new("gen-0", [1u8; 32]);
+        let encrypted = registry
+            .encrypt(b"payload", None, AeadAlgorithm::Aes256Gcm)
+            .unwrap();
+
+        assert_eq!(encrypted.key_id, "gen-0");
+        assert_eq!(registry.decrypt(&encrypted, None).unwrap(), b"payload");
+    }
+
+    #[test]
+    fn test_key_registry_decrypts_older_generation_after_rotation() {
+        let mut registry = KeyRegistry::new("gen-0", [1u8; 32]);
+        let encrypted = registry
+            .encrypt(b"payload", None, AeadAlgorithm::Aes256Gcm)
+            .unwrap();
+
+        registry.add_generation("gen-1", [2u8; 32]);
+        assert_eq!(registry.active_key_id(), "gen-1");
+
+        // The gen-0 payload still decrypts even though gen-1 is now active.
+        assert_eq!(registry.decrypt(&encrypted, None).unwrap(), b"payload");
+    }
+
+    #[test]
+    fn test_rotate_migrates_ciphertext_to_new_key() {
+        let old_key = [1u8; 32];
+        let new_key = [2u8; 32];
+        let plaintext = b"at-rest secret";
+
+        let encrypted =
+            encrypt(&old_key, "gen-0", plaintext, None, AeadAlgorithm::ChaCha20Poly1305).unwrap();
+
+        let rotated = rotate(&encrypted, &old_key, "gen-1", &new_key).unwrap();
+        assert_eq!(rotated.key_id, "gen-1");
+        assert_eq!(rotated.algorithm, encrypted.algorithm);
+
+        // The old key can no longer decrypt the rotated payload.
+        assert!(decrypt(&old_key, &rotated, None).is_err());
+        assert_eq!(decrypt(&new_key, &rotated, None).unwrap(), plaintext);
+    }
 }
@@ -2,13 +2,34 @@
 //!
 //! Provides signing, verification, and encryption for protocol messages.
 
+pub mod adaptor;
+pub mod backup;
 pub mod signing;
 pub mod encryption;
 pub mod hash;
-
-pub use signing::{SigningKey, VerifyingKey, Signature};
-pub use encryption::{encrypt, decrypt, EncryptedPayload};
+pub mod sign;
+pub mod threshold;
+
+pub use signing::{
+    KeyChain, KeyEpoch, RotationCertificate, Signature, SignatureAlgorithm, SigningKey,
+    VerifyingKey,
+};
+pub use encryption::{
+    encrypt, decrypt, rotate, derive_key, derive_key_for_generation, AeadAlgorithm,
+    EncryptedPayload, KeyRegistry,
+};
 pub use hash::{sha256, sha384};
+pub use sign::{Signer, Verifier, SignedMessage};
+pub use threshold::{
+    aggregate as aggregate_threshold_signature, generate_shares as generate_threshold_shares,
+    GroupVerifyingKey, KeyShare, NonceCommitment, PartialSignature, SignerIndex, SigningNonces,
+    ThresholdConfig, ThresholdSignature,
+};
+pub use adaptor::{
+    recover_scalar, AdaptorSecret, AdaptorSigningKey, AdaptorVerifyingKey, EncryptedSignature,
+    EncryptionPoint,
+};
+pub use backup::{encrypt_backup, restore_backup, EncryptedBackup};
 
 /// Errors from cryptographic operations.
 #[derive(Debug, thiserror::Error)]
@@ -27,6 +48,9 @@ pub enum CryptoError {
 
     #[error("Key generation failed: {0}")]
     KeyGenerationFailed(String),
+
+    #[error("Signature algorithm {0} is not yet supported")]
+    UnsupportedAlgorithm(signing::SignatureAlgorithm),
 }
 
 pub type Result<T> = std::result::Result<T, CryptoError>;
@@ -0,0 +1,171 @@
+//! secp256k1 ECDSA signing for protocol messages.
+//!
+//! Distinct from [`crate::signing`]'s Ed25519 keys: this module backs
+//! message-level authentication (settlement requests, heartbeats, balance
+//! queries) with the curve used elsewhere in the settlement-finality story.
+//! A `Signer` canonically serializes a payload, digests it with the
+//! existing [`crate::hash::sha256`], and produces an ECDSA signature over
+//! that digest alongside the signer's compressed public key. A `Verifier`
+//! recomputes the digest and checks the signature against the attached
+//! key; pairing a registered-key lookup with that check (see the
+//! coordinator's `PublicKeyRegistry`) is what authenticates the sender.
+
+use secp256k1::ecdsa::Signature as EcdsaSignature;
+use secp256k1::{Message, PublicKey, Secp256k1, SecretKey};
+use serde::{Deserialize, Serialize};
+
+use crate::hash::sha256;
+use crate::{CryptoError, Result};
+
+/// A payload paired with an ECDSA signature over its canonical digest and
+/// the compressed public key that produced it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedMessage<T> {
+    /// The signed payload.
+    pub payload: T,
+    /// Compact-encoded ECDSA signature (64 bytes).
+    pub signature: Vec<u8>,
+    /// Compressed secp256k1 public key (33 bytes) that produced `signature`.
+    pub public_key: Vec<u8>,
+}
+
+/// Digests a payload; shared by `Signer::sign` and `Verifier::verify` so
+/// both compute the same digest.
+fn canonical_digest<T: Serialize>(payload: &T) -> Result<[u8; 32]> {
+    let canonical = serde_json::to_vec(payload)
+        .map_err(|e| CryptoError::InvalidKey(format!("payload is not serializable: {e}")))?;
+    Ok(sha256(&canonical))
+}
+
+/// Holds a secp256k1 private key and signs message payloads with it.
+pub struct Signer {
+    secret_key: SecretKey,
+    public_key: PublicKey,
+}
+
+impl Signer {
+    /// Generate a new random signer.
+    pub fn generate() -> Self {
+        let secp = Secp256k1::new();
+        let secret_key = SecretKey::new(&mut rand::thread_rng());
+        let public_key = PublicKey::from_secret_key(&secp, &secret_key);
+        Self {
+            secret_key,
+            public_key,
+        }
+    }
+
+    /// Create from a raw 32-byte private key.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let secret_key =
+            SecretKey::from_slice(bytes).map_err(|e| CryptoError::InvalidKey(e.to_string()))?;
+        let secp = Secp256k1::new();
+        let public_key = PublicKey::from_secret_key(&secp, &secret_key);
+        Ok(Self {
+            secret_key,
+            public_key,
+        })
+    }
+
+    /// This signer's compressed public key (33 bytes).
+    pub fn public_key_bytes(&self) -> Vec<u8> {
+        self.public_key.serialize().to_vec()
+    }
+
+    /// Canonically serialize `payload` (serde), digest it with `sha256`,
+    /// and sign the digest, wrapping the result alongside the payload and
+    /// this signer's public key.
+    pub fn sign<T: Serialize>(&self, payload: T) -> Result<SignedMessage<T>> {
+        let digest = canonical_digest(&payload)?;
+        let secp = Secp256k1::new();
+        let message = Message::from_digest(digest);
+        let signature = secp.sign_ecdsa(&message, &self.secret_key);
+
+        Ok(SignedMessage {
+            payload,
+            signature: signature.serialize_compact().to_vec(),
+            public_key: self.public_key_bytes(),
+        })
+    }
+}
+
+/// Verifies `SignedMessage`s by recomputing the payload digest and checking
+/// the attached ECDSA signature against the attached public key. Does not
+/// by itself establish that the public key belongs to the claimed sender --
+/// callers that need sender authentication should additionally check the
+/// public key against a registry (see the coordinator's
+/// `PublicKeyRegistry`).
+pub struct Verifier;
+
+impl Verifier {
+    /// Verify that `message.signature` is a valid ECDSA signature over
+    /// `message.payload`'s canonical digest, produced by `message.public_key`.
+    pub fn verify<T: Serialize>(message: &SignedMessage<T>) -> Result<()> {
+        let digest = canonical_digest(&message.payload)?;
+        let secp = Secp256k1::new();
+        let msg = Message::from_digest(digest);
+
+        let public_key = PublicKey::from_slice(&message.public_key)
+            .map_err(|e| CryptoError::InvalidKey(e.to_string()))?;
+        let signature = EcdsaSignature::from_compact(&message.signature)
+            .map_err(|_| CryptoError::InvalidSignature)?;
+
+        secp.verify_ecdsa(&msg, &signature, &public_key)
+            .map_err(|_| CryptoError::InvalidSignature)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+    struct Payload {
+        to: String,
+        amount: u64,
+    }
+
+    #[test]
+    fn test_sign_verify_roundtrip() {
+        let signer = Signer::generate();
+        let signed = signer
+            .sign(Payload {
+                to: "BANK_B".to_string(),
+                amount: 100,
+            })
+            .unwrap();
+
+        assert!(Verifier::verify(&signed).is_ok());
+    }
+
+    #[test]
+    fn test_verify_rejects_tampered_payload() {
+        let signer = Signer::generate();
+        let mut signed = signer
+            .sign(Payload {
+                to: "BANK_B".to_string(),
+                amount: 100,
+            })
+            .unwrap();
+
+        signed.payload.amount = 1_000_000;
+
+        assert!(Verifier::verify(&signed).is_err());
+    }
+
+    #[test]
+    fn test_verify_rejects_wrong_public_key() {
+        let signer = Signer::generate();
+        let other = Signer::generate();
+        let mut signed = signer
+            .sign(Payload {
+                to: "BANK_B".to_string(),
+                amount: 100,
+            })
+            .unwrap();
+
+        signed.public_key = other.public_key_bytes();
+
+        assert!(Verifier::verify(&signed).is_err());
+    }
+}
@@ -1,46 +1,158 @@
-//! Digital signature support using Ed25519.
+//! Digital signature support, pluggable across algorithms, with key
+//! rotation so operators can roll a compromised key without invalidating
+//! settlements signed under its earlier epochs.
+//!
+//! [`SigningKey`]/[`VerifyingKey`] dispatch over [`SignatureAlgorithm`]:
+//! `Ed25519` (the original scheme here) and `Secp256k1Schnorr` (BIP-340
+//! Schnorr, compatible with an on-chain verifier such as the Serai
+//! Ethereum Router's key-handoff checks) are fully implemented.
+//! `Schnorrkel` is a recognized algorithm identifier reserved for a future
+//! sr25519/Substrate-side signer; generating or signing with it fails with
+//! [`CryptoError::UnsupportedAlgorithm`] until that backend lands.
+//!
+//! Rotation is modeled on Serai's `updateSeraiKey`: [`SigningKey::rotate`]
+//! produces a successor key and a [`RotationCertificate`] proving the
+//! predecessor key signed off on it. A [`KeyChain`] tracks a signer
+//! identity's full epoch history and verifies an incoming [`Signature`]
+//! against whichever epoch produced it, as long as that epoch hasn't been
+//! [`KeyChain::revoke`]d -- so a signature made under epoch 3 still
+//! verifies after the identity has rotated to epoch 7.
 
 use ed25519_dalek::{
-    Signer, SigningKey as Ed25519SigningKey, Verifier, VerifyingKey as Ed25519VerifyingKey,
+    Signer as _, SigningKey as Ed25519SigningKey, Verifier as _, VerifyingKey as Ed25519VerifyingKey,
 };
 use rand::rngs::OsRng;
+use secp256k1::{schnorr, Keypair, Secp256k1, XOnlyPublicKey};
 use serde::{Deserialize, Serialize};
 
 use crate::{CryptoError, Result};
 
-/// A signing key (private key) for creating signatures.
+/// The scheme a [`SigningKey`]/[`VerifyingKey`] pair was generated under.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SignatureAlgorithm {
+    /// The original Ed25519 scheme.
+    Ed25519,
+    /// sr25519 (Substrate/Schnorrkel) -- identifier reserved, not yet
+    /// implemented.
+    Schnorrkel,
+    /// BIP-340 Schnorr over secp256k1, verifiable by an on-chain verifier
+    /// such as the Serai Ethereum Router.
+    Secp256k1Schnorr,
+}
+
+impl std::fmt::Display for SignatureAlgorithm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::Ed25519 => "Ed25519",
+            Self::Schnorrkel => "Schnorrkel",
+            Self::Secp256k1Schnorr => "Secp256k1Schnorr",
+        };
+        write!(f, "{s}")
+    }
+}
+
+/// A key's position in its identity's rotation history. The key an
+/// identity is created with is epoch 0; each [`SigningKey::rotate`] call
+/// produces the next epoch.
+pub type KeyEpoch = u64;
+
+enum SigningKeyInner {
+    Ed25519(Ed25519SigningKey),
+    Secp256k1Schnorr(Keypair),
+}
+
+enum VerifyingKeyInner {
+    Ed25519(Ed25519VerifyingKey),
+    Secp256k1Schnorr(XOnlyPublicKey),
+}
+
+/// A signing key (private key) for creating signatures under some
+/// [`SignatureAlgorithm`], at some [`KeyEpoch`] in its identity's
+/// rotation history.
 pub struct SigningKey {
-    inner: Ed25519SigningKey,
+    inner: SigningKeyInner,
+    algorithm: SignatureAlgorithm,
     key_id: String,
+    epoch: KeyEpoch,
 }
 
 impl SigningKey {
-    /// Generate a new random signing key.
-    pub fn generate() -> Result<Self> {
-        let mut csprng = OsRng;
-        let inner = Ed25519SigningKey::generate(&mut csprng);
-        let key_id = hex::encode(&inner.verifying_key().as_bytes()[..8]);
-
-        Ok(Self { inner, key_id })
+    /// Generate a new random epoch-0 signing key under `algorithm`.
+    pub fn generate(algorithm: SignatureAlgorithm) -> Result<Self> {
+        let inner = match algorithm {
+            SignatureAlgorithm::Ed25519 => {
+                let mut csprng = OsRng;
+                SigningKeyInner::Ed25519(Ed25519SigningKey::generate(&mut csprng))
+            }
+            SignatureAlgorithm::Secp256k1Schnorr => {
+                let secp = Secp256k1::new();
+                SigningKeyInner::Secp256k1Schnorr(Keypair::new(&secp, &mut rand::thread_rng()))
+            }
+            SignatureAlgorithm::Schnorrkel => {
+                return Err(CryptoError::UnsupportedAlgorithm(algorithm));
+            }
+        };
+
+        let key_id = key_id_for(&inner, 0);
+        Ok(Self {
+            inner,
+            algorithm,
+            key_id,
+            epoch: 0,
+        })
     }
 
-    /// Create from raw bytes.
+    /// Create an Ed25519 epoch-0 key from raw bytes, for compatibility
+    /// with callers that only ever used the original scheme.
     pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
         let bytes: [u8; 32] = bytes
             .try_into()
             .map_err(|_| CryptoError::InvalidKey("Invalid key length".to_string()))?;
 
-        let inner = Ed25519SigningKey::from_bytes(&bytes);
-        let key_id = hex::encode(&inner.verifying_key().as_bytes()[..8]);
+        let inner = SigningKeyInner::Ed25519(Ed25519SigningKey::from_bytes(&bytes));
+        let key_id = key_id_for(&inner, 0);
 
-        Ok(Self { inner, key_id })
+        Ok(Self {
+            inner,
+            algorithm: SignatureAlgorithm::Ed25519,
+            key_id,
+            epoch: 0,
+        })
+    }
+
+    /// Derive a deterministic Ed25519 epoch-0 key from a BIP39 mnemonic
+    /// and an account index, so an operator holding only a written seed
+    /// phrase can recover every key a bank was issued without any of them
+    /// having been separately backed up. The same `(phrase, account_index)`
+    /// pair always derives the same key; different indices derive
+    /// unrelated keys from the same phrase.
+    pub fn from_mnemonic(phrase: &str, account_index: u32) -> Result<Self> {
+        let mnemonic = bip39::Mnemonic::parse(phrase)
+            .map_err(|e| CryptoError::InvalidKey(format!("invalid mnemonic: {e}")))?;
+        let seed = mnemonic.to_seed("");
+        let info = format!("atomicsettle-signing-key/{account_index}");
+        let key_bytes = crate::encryption::derive_key(&seed, b"atomicsettle-mnemonic", info.as_bytes())?;
+        Self::from_bytes(&key_bytes)
+    }
+
+    /// This key's algorithm.
+    pub fn algorithm(&self) -> SignatureAlgorithm {
+        self.algorithm
+    }
+
+    /// This key's epoch.
+    pub fn epoch(&self) -> KeyEpoch {
+        self.epoch
     }
 
     /// Get the corresponding verifying key.
     pub fn verifying_key(&self) -> VerifyingKey {
         VerifyingKey {
-            inner: self.inner.verifying_key(),
+            inner: self.public_inner(),
+            algorithm: self.algorithm,
             key_id: self.key_id.clone(),
+            epoch: self.epoch,
+            revoked: false,
         }
     }
 
@@ -51,76 +163,183 @@ impl SigningKey {
 
     /// Sign a message.
     pub fn sign(&self, message: &[u8]) -> Signature {
-        let sig = self.inner.sign(message);
+        let bytes = match &self.inner {
+            SigningKeyInner::Ed25519(key) => key.sign(message).to_bytes().to_vec(),
+            SigningKeyInner::Secp256k1Schnorr(keypair) => {
+                let secp = Secp256k1::new();
+                let digest = crate::hash::sha256(message);
+                let message = secp256k1::Message::from_digest(digest);
+                secp.sign_schnorr(&message, keypair).as_ref().to_vec()
+            }
+        };
+
         Signature {
-            bytes: sig.to_bytes().to_vec(),
+            bytes,
             key_id: self.key_id.clone(),
-            algorithm: "Ed25519".to_string(),
+            algorithm: self.algorithm.to_string(),
         }
     }
 
-    /// Get raw key bytes.
-    pub fn to_bytes(&self) -> [u8; 32] {
-        self.inner.to_bytes()
+    /// Produce a successor key one epoch ahead of this one, plus a
+    /// [`RotationCertificate`] proving this (predecessor) key signed off
+    /// on the handoff. Rolling a compromised key means generating a
+    /// rotation, distributing the certificate to verifiers, and revoking
+    /// the compromised epoch in their [`KeyChain`] -- signatures made
+    /// under earlier, non-revoked epochs remain valid.
+    pub fn rotate(&self) -> Result<(SigningKey, RotationCertificate)> {
+        let successor = SigningKey::generate(self.algorithm)?;
+        let successor = SigningKey {
+            epoch: self.epoch + 1,
+            ..successor
+        };
+
+        let successor_verifying_key = successor.verifying_key();
+        let signature = self.sign(&successor_verifying_key.canonical_bytes());
+
+        let certificate = RotationCertificate {
+            predecessor_key_id: self.key_id.clone(),
+            successor_verifying_key,
+            signature,
+        };
+
+        Ok((successor, certificate))
+    }
+
+    /// Get raw key bytes. Only meaningful for Ed25519 keys.
+    pub fn to_bytes(&self) -> Result<[u8; 32]> {
+        match &self.inner {
+            SigningKeyInner::Ed25519(key) => Ok(key.to_bytes()),
+            SigningKeyInner::Secp256k1Schnorr(_) => Err(CryptoError::UnsupportedAlgorithm(
+                SignatureAlgorithm::Secp256k1Schnorr,
+            )),
+        }
+    }
+
+    fn public_inner(&self) -> VerifyingKeyInner {
+        match &self.inner {
+            SigningKeyInner::Ed25519(key) => VerifyingKeyInner::Ed25519(key.verifying_key()),
+            SigningKeyInner::Secp256k1Schnorr(keypair) => {
+                VerifyingKeyInner::Secp256k1Schnorr(keypair.x_only_public_key().0)
+            }
+        }
     }
 }
 
-/// A verifying key (public key) for verifying signatures.
+/// A verifying key (public key) for verifying signatures, at a specific
+/// [`KeyEpoch`] in its identity's rotation history.
 #[derive(Clone)]
 pub struct VerifyingKey {
-    inner: Ed25519VerifyingKey,
+    inner: VerifyingKeyInner,
+    algorithm: SignatureAlgorithm,
     key_id: String,
+    epoch: KeyEpoch,
+    revoked: bool,
+}
+
+impl Clone for VerifyingKeyInner {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Ed25519(key) => Self::Ed25519(*key),
+            Self::Secp256k1Schnorr(key) => Self::Secp256k1Schnorr(*key),
+        }
+    }
 }
 
 impl VerifyingKey {
-    /// Create from raw bytes.
+    /// Create an Ed25519 epoch-0 verifying key from raw bytes.
     pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
-        let bytes: [u8; 32] = bytes
+        let array: [u8; 32] = bytes
             .try_into()
             .map_err(|_| CryptoError::InvalidKey("Invalid key length".to_string()))?;
 
-        let inner = Ed25519VerifyingKey::from_bytes(&bytes)
+        let inner = Ed25519VerifyingKey::from_bytes(&array)
             .map_err(|e| CryptoError::InvalidKey(e.to_string()))?;
+        let inner = VerifyingKeyInner::Ed25519(inner);
+        let key_id = key_id_for(&inner, 0);
 
-        let key_id = hex::encode(&bytes[..8]);
-
-        Ok(Self { inner, key_id })
+        Ok(Self {
+            inner,
+            algorithm: SignatureAlgorithm::Ed25519,
+            key_id,
+            epoch: 0,
+            revoked: false,
+        })
     }
 
-    /// Get the key ID.
+    /// The key ID.
     pub fn key_id(&self) -> &str {
         &self.key_id
     }
 
+    /// This key's algorithm.
+    pub fn algorithm(&self) -> SignatureAlgorithm {
+        self.algorithm
+    }
+
+    /// This key's epoch.
+    pub fn epoch(&self) -> KeyEpoch {
+        self.epoch
+    }
+
+    /// Bytes identifying this key's public material and epoch, signed by
+    /// a predecessor key during rotation.
+    fn canonical_bytes(&self) -> Vec<u8> {
+        let mut bytes = raw_public_bytes(&self.inner);
+        bytes.extend_from_slice(&self.epoch.to_be_bytes());
+        bytes
+    }
+
     /// Verify a signature.
     pub fn verify(&self, message: &[u8], signature: &Signature) -> Result<()> {
-        let sig_bytes: [u8; 64] = signature
-            .bytes
-            .as_slice()
-            .try_into()
-            .map_err(|_| CryptoError::InvalidSignature)?;
-
-        let sig = ed25519_dalek::Signature::from_bytes(&sig_bytes);
+        if signature.algorithm != self.algorithm.to_string() {
+            return Err(CryptoError::InvalidSignature);
+        }
 
-        self.inner
-            .verify(message, &sig)
-            .map_err(|_| CryptoError::InvalidSignature)
+        match &self.inner {
+            VerifyingKeyInner::Ed25519(key) => {
+                let sig_bytes: [u8; 64] = signature
+                    .bytes
+                    .as_slice()
+                    .try_into()
+                    .map_err(|_| CryptoError::InvalidSignature)?;
+                let sig = ed25519_dalek::Signature::from_bytes(&sig_bytes);
+                key.verify(message, &sig)
+                    .map_err(|_| CryptoError::InvalidSignature)
+            }
+            VerifyingKeyInner::Secp256k1Schnorr(key) => {
+                let secp = Secp256k1::new();
+                let digest = crate::hash::sha256(message);
+                let msg = secp256k1::Message::from_digest(digest);
+                let sig = schnorr::Signature::from_slice(&signature.bytes)
+                    .map_err(|_| CryptoError::InvalidSignature)?;
+                secp.verify_schnorr(&sig, &msg, key)
+                    .map_err(|_| CryptoError::InvalidSignature)
+            }
+        }
     }
 
-    /// Get raw key bytes.
-    pub fn to_bytes(&self) -> [u8; 32] {
-        self.inner.to_bytes()
+    /// Get raw key bytes. Only meaningful for Ed25519 keys.
+    pub fn to_bytes(&self) -> Result<[u8; 32]> {
+        match &self.inner {
+            VerifyingKeyInner::Ed25519(key) => Ok(key.to_bytes()),
+            VerifyingKeyInner::Secp256k1Schnorr(_) => Err(CryptoError::UnsupportedAlgorithm(
+                SignatureAlgorithm::Secp256k1Schnorr,
+            )),
+        }
     }
 }
 
-/// A digital signature.
+/// A digital signature, tagged with the algorithm that produced it and
+/// the `key_id` of the key that signed it.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Signature {
     /// Raw signature bytes.
     pub bytes: Vec<u8>,
     /// ID of the key that created this signature.
     pub key_id: String,
-    /// Algorithm used (always "Ed25519" for now).
+    /// Algorithm used to produce this signature (the name of a
+    /// [`SignatureAlgorithm`] variant, or another scheme's own identifier
+    /// -- e.g. FROST signatures tag themselves "FROST-Ristretto25519").
     pub algorithm: String,
 }
 
@@ -131,18 +350,130 @@ impl Signature {
     }
 
     /// Create from hex string.
-    pub fn from_hex(hex_str: &str, key_id: impl Into<String>) -> Result<Self> {
-        let bytes =
-            hex::decode(hex_str).map_err(|e| CryptoError::InvalidSignature)?;
+    pub fn from_hex(
+        hex_str: &str,
+        key_id: impl Into<String>,
+        algorithm: SignatureAlgorithm,
+    ) -> Result<Self> {
+        let bytes = hex::decode(hex_str).map_err(|_| CryptoError::InvalidSignature)?;
 
         Ok(Self {
             bytes,
             key_id: key_id.into(),
-            algorithm: "Ed25519".to_string(),
+            algorithm: algorithm.to_string(),
         })
     }
 }
 
+/// Proof that a predecessor key signed off on a successor key taking over
+/// at the next epoch, produced by [`SigningKey::rotate`].
+#[derive(Clone)]
+pub struct RotationCertificate {
+    pub predecessor_key_id: String,
+    pub successor_verifying_key: VerifyingKey,
+    pub signature: Signature,
+}
+
+impl RotationCertificate {
+    /// Check that the predecessor's signature actually covers the
+    /// successor key it claims to hand off to.
+    fn verify_against(&self, predecessor: &VerifyingKey) -> Result<()> {
+        if self.predecessor_key_id != predecessor.key_id {
+            return Err(CryptoError::InvalidKey(
+                "rotation certificate predecessor does not match".to_string(),
+            ));
+        }
+        if self.successor_verifying_key.epoch != predecessor.epoch + 1 {
+            return Err(CryptoError::InvalidKey(
+                "rotation certificate does not advance to the next epoch".to_string(),
+            ));
+        }
+        predecessor.verify(&self.successor_verifying_key.canonical_bytes(), &self.signature)
+    }
+}
+
+/// An identity's full key-rotation history: every epoch it has held,
+/// whether each has been revoked, in order. Verifying a [`Signature`]
+/// checks it against whichever epoch's key produced it, so a signature
+/// made under an earlier, non-revoked epoch remains valid after later
+/// rotations.
+pub struct KeyChain {
+    epochs: Vec<VerifyingKey>,
+}
+
+impl KeyChain {
+    /// Start a key chain rooted at an identity's epoch-0 key.
+    pub fn new(root: VerifyingKey) -> Result<Self> {
+        if root.epoch != 0 {
+            return Err(CryptoError::InvalidKey(
+                "a key chain must be rooted at epoch 0".to_string(),
+            ));
+        }
+        Ok(Self { epochs: vec![root] })
+    }
+
+    /// Apply a rotation certificate signed by the current head epoch,
+    /// extending the chain by one epoch.
+    pub fn apply_rotation(&mut self, certificate: RotationCertificate) -> Result<()> {
+        let head = self.epochs.last().expect("chain is never empty");
+        certificate.verify_against(head)?;
+        self.epochs.push(certificate.successor_verifying_key);
+        Ok(())
+    }
+
+    /// Mark an epoch as revoked. Signatures produced under it no longer
+    /// verify, even though they predate the revocation.
+    pub fn revoke(&mut self, epoch: KeyEpoch) -> Result<()> {
+        let entry = self
+            .epochs
+            .iter_mut()
+            .find(|k| k.epoch == epoch)
+            .ok_or_else(|| CryptoError::InvalidKey(format!("no such epoch {epoch}")))?;
+        entry.revoked = true;
+        Ok(())
+    }
+
+    /// Verify `signature` over `message` against whichever epoch's key
+    /// produced it. Fails if no epoch's `key_id` matches, or if the
+    /// matching epoch has been revoked.
+    pub fn verify(&self, message: &[u8], signature: &Signature) -> Result<()> {
+        let epoch = self
+            .epochs
+            .iter()
+            .find(|k| k.key_id == signature.key_id)
+            .ok_or(CryptoError::InvalidSignature)?;
+
+        if epoch.revoked {
+            return Err(CryptoError::InvalidSignature);
+        }
+
+        epoch.verify(message, signature)
+    }
+
+    /// The current (latest, non-superseded) epoch in the chain.
+    pub fn current_epoch(&self) -> &VerifyingKey {
+        self.epochs.last().expect("chain is never empty")
+    }
+}
+
+fn raw_public_bytes(inner: &VerifyingKeyInner) -> Vec<u8> {
+    match inner {
+        VerifyingKeyInner::Ed25519(key) => key.to_bytes().to_vec(),
+        VerifyingKeyInner::Secp256k1Schnorr(key) => key.serialize().to_vec(),
+    }
+}
+
+fn key_id_for(inner: &SigningKeyInner, epoch: KeyEpoch) -> String {
+    let public = match inner {
+        SigningKeyInner::Ed25519(key) => VerifyingKeyInner::Ed25519(key.verifying_key()),
+        SigningKeyInner::Secp256k1Schnorr(keypair) => {
+            VerifyingKeyInner::Secp256k1Schnorr(keypair.x_only_public_key().0)
+        }
+    };
+    let bytes = raw_public_bytes(&public);
+    format!("{}-e{}", hex::encode(&bytes[..8]), epoch)
+}
+
 // Add hex dependency inline for this module
 mod hex {
     pub fn encode(bytes: &[u8]) -> String {
@@ -166,8 +497,8 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_sign_verify() {
-        let signing_key = SigningKey::generate().unwrap();
+    fn test_sign_verify_ed25519() {
+        let signing_key = SigningKey::generate(SignatureAlgorithm::Ed25519).unwrap();
         let verifying_key = signing_key.verifying_key();
 
         let message = b"Hello, AtomicSettle!";
@@ -176,9 +507,40 @@ mod tests {
         assert!(verifying_key.verify(message, &signature).is_ok());
     }
 
+    #[test]
+    fn test_sign_verify_secp256k1_schnorr() {
+        let signing_key = SigningKey::generate(SignatureAlgorithm::Secp256k1Schnorr).unwrap();
+        let verifying_key = signing_key.verifying_key();
+
+        let message = b"settle 100 USD BANK_A -> BANK_B";
+        let signature = signing_key.sign(message);
+
+        assert!(verifying_key.verify(message, &signature).is_ok());
+    }
+
+    #[test]
+    fn test_from_mnemonic_is_deterministic() {
+        let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+        let key_a = SigningKey::from_mnemonic(phrase, 0).unwrap();
+        let key_b = SigningKey::from_mnemonic(phrase, 0).unwrap();
+        assert_eq!(key_a.to_bytes().unwrap(), key_b.to_bytes().unwrap());
+
+        let key_other_index = SigningKey::from_mnemonic(phrase, 1).unwrap();
+        assert_ne!(key_a.to_bytes().unwrap(), key_other_index.to_bytes().unwrap());
+    }
+
+    #[test]
+    fn test_schnorrkel_is_not_yet_supported() {
+        assert!(matches!(
+            SigningKey::generate(SignatureAlgorithm::Schnorrkel),
+            Err(CryptoError::UnsupportedAlgorithm(SignatureAlgorithm::Schnorrkel))
+        ));
+    }
+
     #[test]
     fn test_invalid_signature() {
-        let signing_key = SigningKey::generate().unwrap();
+        let signing_key = SigningKey::generate(SignatureAlgorithm::Ed25519).unwrap();
         let verifying_key = signing_key.verifying_key();
 
         let message = b"Hello, AtomicSettle!";
@@ -192,10 +554,66 @@ mod tests {
 
     #[test]
     fn test_key_serialization() {
-        let signing_key = SigningKey::generate().unwrap();
-        let bytes = signing_key.to_bytes();
+        let signing_key = SigningKey::generate(SignatureAlgorithm::Ed25519).unwrap();
+        let bytes = signing_key.to_bytes().unwrap();
 
         let restored = SigningKey::from_bytes(&bytes).unwrap();
         assert_eq!(signing_key.key_id(), restored.key_id());
     }
+
+    #[test]
+    fn test_rotation_advances_epoch_and_signs_successor() {
+        let root = SigningKey::generate(SignatureAlgorithm::Ed25519).unwrap();
+        let (rotated, certificate) = root.rotate().unwrap();
+
+        assert_eq!(rotated.epoch(), 1);
+        assert_eq!(certificate.successor_verifying_key.epoch(), 1);
+        assert!(certificate
+            .verify_against(&root.verifying_key())
+            .is_ok());
+    }
+
+    #[test]
+    fn test_key_chain_verifies_signature_from_earlier_non_revoked_epoch() {
+        let epoch0 = SigningKey::generate(SignatureAlgorithm::Ed25519).unwrap();
+        let message = b"settle 250 EUR BANK_C -> BANK_D";
+        let historical_signature = epoch0.sign(message);
+
+        let (epoch1, certificate) = epoch0.rotate().unwrap();
+        let mut chain = KeyChain::new(epoch0.verifying_key()).unwrap();
+        chain.apply_rotation(certificate).unwrap();
+
+        assert_eq!(chain.current_epoch().epoch(), 1);
+        // A signature made under epoch 0, before rotation, still verifies.
+        assert!(chain.verify(message, &historical_signature).is_ok());
+
+        let _ = epoch1; // epoch1 would sign future messages under epoch 1.
+    }
+
+    #[test]
+    fn test_key_chain_rejects_signature_from_revoked_epoch() {
+        let epoch0 = SigningKey::generate(SignatureAlgorithm::Ed25519).unwrap();
+        let message = b"settle 250 EUR BANK_C -> BANK_D";
+        let historical_signature = epoch0.sign(message);
+
+        let (_epoch1, certificate) = epoch0.rotate().unwrap();
+        let mut chain = KeyChain::new(epoch0.verifying_key()).unwrap();
+        chain.apply_rotation(certificate).unwrap();
+        chain.revoke(0).unwrap();
+
+        assert!(chain.verify(message, &historical_signature).is_err());
+    }
+
+    #[test]
+    fn test_key_chain_rejects_rotation_not_signed_by_head() {
+        let epoch0 = SigningKey::generate(SignatureAlgorithm::Ed25519).unwrap();
+        let imposter = SigningKey::generate(SignatureAlgorithm::Ed25519).unwrap();
+
+        let (_successor, mut certificate) = imposter.rotate().unwrap();
+        // Graft the imposter's certificate onto epoch0's chain.
+        certificate.predecessor_key_id = epoch0.key_id().to_string();
+
+        let mut chain = KeyChain::new(epoch0.verifying_key()).unwrap();
+        assert!(chain.apply_rotation(certificate).is_err());
+    }
 }
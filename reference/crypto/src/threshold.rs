@@ -0,0 +1,493 @@
+//! FROST (Flexible Round-Optimized Schnorr Threshold signatures), t-of-n,
+//! over the Ristretto25519 group.
+//!
+//! Extends [`crate::signing`]'s single-key Ed25519 scheme so a settlement
+//! can require `k` independent bank signers before the coordinator accepts
+//! it, instead of trusting any one signer's key: no single compromised
+//! bank key can unilaterally authorize a large settlement. Key generation
+//! here uses a trusted dealer (Shamir-splits a random group secret into
+//! per-signer shares `s_i`) rather than a fully distributed DKG, which is
+//! out of scope for this module; everything downstream -- signing and
+//! verification -- is the real two-round FROST protocol.
+//!
+//! Signing is two rounds, run by the `k` signers who will participate:
+//!
+//! 1. Each signer draws a nonce pair `(d_i, e_i)` and publishes commitments
+//!    `(D_i, E_i) = (d_i·G, E_i = e_i·G)` via [`SigningNonces::generate`].
+//! 2. Given the full commitment set `B`, each signer computes a binding
+//!    factor `ρ_i = H(i, m, B)`, the group commitment
+//!    `R = Σ(D_i + ρ_i·E_i)`, the challenge `c = H(R, Y, m)`, and a partial
+//!    signature `z_i = d_i + e_i·ρ_i + λ_i·s_i·c` via
+//!    [`SigningNonces::sign`], where `λ_i` is `i`'s Lagrange coefficient
+//!    over the participating signer set.
+//!
+//! [`aggregate`] sums the partial signatures into `z = Σ z_i`, yielding an
+//! ordinary Schnorr signature `(R, z)` over the group key `Y` -- it
+//! verifies exactly like a single-party signature, so downstream code
+//! (anything calling [`ThresholdSignature::verify`]) is agnostic to how
+//! many signers actually produced it.
+
+use std::collections::BTreeSet;
+
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+use curve25519_dalek::ristretto::{CompressedRistretto, RistrettoPoint};
+use curve25519_dalek::scalar::Scalar;
+use curve25519_dalek::traits::Identity;
+use sha2::{Digest, Sha512};
+
+use crate::signing::Signature;
+use crate::{CryptoError, Result};
+
+/// A signer's position in the group, 1-based. Position 0 is never assigned
+/// so it can't collide with the "no signers" bitmap state.
+pub type SignerIndex = u16;
+
+/// The largest group size supported by [`ThresholdSignature`]'s bitmap.
+const MAX_PARTICIPANTS: u16 = 64;
+
+/// Parameters for a threshold group: `threshold`-of-`participants` signers
+/// must cooperate to produce a valid signature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ThresholdConfig {
+    pub threshold: u16,
+    pub participants: u16,
+}
+
+impl ThresholdConfig {
+    /// Create a new config, rejecting degenerate or oversized groups.
+    pub fn new(threshold: u16, participants: u16) -> Result<Self> {
+        if threshold == 0 || threshold > participants {
+            return Err(CryptoError::InvalidKey(format!(
+                "threshold {threshold} must be between 1 and the participant count {participants}"
+            )));
+        }
+        if participants == 0 || participants > MAX_PARTICIPANTS {
+            return Err(CryptoError::InvalidKey(format!(
+                "participant count {participants} must be between 1 and {MAX_PARTICIPANTS}"
+            )));
+        }
+        Ok(Self {
+            threshold,
+            participants,
+        })
+    }
+}
+
+/// The group's shared public key `Y`, against which any valid threshold
+/// signature from this group verifies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GroupVerifyingKey {
+    point: RistrettoPoint,
+}
+
+impl GroupVerifyingKey {
+    /// Compressed, 32-byte encoding of `Y`.
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.point.compress().to_bytes()
+    }
+}
+
+/// One signer's secret share `s_i` of the group secret, plus the group key
+/// `Y` it was split from. Produced by [`generate_shares`]; must be kept
+/// confidential by the signer it was issued to.
+#[derive(Debug, Clone, Copy)]
+pub struct KeyShare {
+    index: SignerIndex,
+    secret: Scalar,
+    group_key: GroupVerifyingKey,
+}
+
+impl KeyShare {
+    /// This share's signer index.
+    pub fn index(&self) -> SignerIndex {
+        self.index
+    }
+
+    /// The group verifying key this share participates in.
+    pub fn group_key(&self) -> GroupVerifyingKey {
+        self.group_key
+    }
+}
+
+/// Dealer-based distributed key generation: split a fresh random group
+/// secret into `config.participants` Shamir shares of degree
+/// `config.threshold - 1`, so that any `config.threshold` of them can
+/// reconstruct a signature (but not the secret itself) via FROST signing.
+pub fn generate_shares(config: ThresholdConfig) -> (GroupVerifyingKey, Vec<KeyShare>) {
+    let coefficients: Vec<Scalar> = (0..config.threshold)
+        .map(|_| Scalar::random(&mut rand::thread_rng()))
+        .collect();
+
+    let group_key = GroupVerifyingKey {
+        point: RISTRETTO_BASEPOINT_POINT * coefficients[0],
+    };
+
+    let shares = (1..=config.participants)
+        .map(|index| KeyShare {
+            index,
+            secret: evaluate_polynomial(&coefficients, Scalar::from(index as u64)),
+            group_key,
+        })
+        .collect();
+
+    (group_key, shares)
+}
+
+/// Evaluate the dealer's polynomial at `x` using Horner's method.
+fn evaluate_polynomial(coefficients: &[Scalar], x: Scalar) -> Scalar {
+    coefficients
+        .iter()
+        .rev()
+        .fold(Scalar::ZERO, |acc, coefficient| acc * x + coefficient)
+}
+
+/// `i`'s Lagrange coefficient `λ_i` for interpolating the polynomial's
+/// value at 0 from the given signer set.
+fn lagrange_coefficient(index: SignerIndex, signer_set: &[SignerIndex]) -> Scalar {
+    let x_i = Scalar::from(index as u64);
+    let mut numerator = Scalar::ONE;
+    let mut denominator = Scalar::ONE;
+
+    for &j in signer_set {
+        if j == index {
+            continue;
+        }
+        let x_j = Scalar::from(j as u64);
+        numerator *= x_j;
+        denominator *= x_j - x_i;
+    }
+
+    numerator * denominator.invert()
+}
+
+/// A signer's round-1 nonce commitments `(D_i, E_i)`, published to the
+/// other participating signers before round 2.
+#[derive(Debug, Clone, Copy)]
+pub struct NonceCommitment {
+    pub index: SignerIndex,
+    d: RistrettoPoint,
+    e: RistrettoPoint,
+}
+
+/// A signer's private round-1 nonce pair `(d_i, e_i)`. Consumed by
+/// [`SigningNonces::sign`] -- a nonce pair must never be reused across two
+/// signing attempts, on pain of leaking the signer's secret share.
+pub struct SigningNonces {
+    index: SignerIndex,
+    d: Scalar,
+    e: Scalar,
+}
+
+impl SigningNonces {
+    /// Round 1: draw a fresh nonce pair and the commitments to publish.
+    pub fn generate(index: SignerIndex) -> (Self, NonceCommitment) {
+        let d = Scalar::random(&mut rand::thread_rng());
+        let e = Scalar::random(&mut rand::thread_rng());
+        let commitment = NonceCommitment {
+            index,
+            d: RISTRETTO_BASEPOINT_POINT * d,
+            e: RISTRETTO_BASEPOINT_POINT * e,
+        };
+        (Self { index, d, e }, commitment)
+    }
+
+    /// Round 2: compute this signer's partial signature `z_i` over
+    /// `message`, given the full set of round-1 commitments (including
+    /// this signer's own).
+    pub fn sign(
+        self,
+        share: &KeyShare,
+        message: &[u8],
+        commitments: &[NonceCommitment],
+    ) -> Result<PartialSignature> {
+        if share.index != self.index {
+            return Err(CryptoError::InvalidKey(
+                "nonces were generated for a different signer index than the key share"
+                    .to_string(),
+            ));
+        }
+
+        let signer_set: Vec<SignerIndex> = commitments.iter().map(|c| c.index).collect();
+        if !signer_set.contains(&self.index) {
+            return Err(CryptoError::InvalidKey(
+                "signer's own commitment is missing from the commitment set".to_string(),
+            ));
+        }
+
+        let rho_i = binding_factor(self.index, message, commitments);
+        let r = group_commitment(commitments, message);
+        let c = challenge(r, share.group_key.point, message);
+        let lambda_i = lagrange_coefficient(self.index, &signer_set);
+
+        let z = self.d + self.e * rho_i + lambda_i * share.secret * c;
+
+        Ok(PartialSignature {
+            index: self.index,
+            z,
+        })
+    }
+}
+
+/// One signer's contribution `z_i` to the aggregate signature.
+#[derive(Debug, Clone, Copy)]
+pub struct PartialSignature {
+    pub index: SignerIndex,
+    z: Scalar,
+}
+
+/// Binding factor `ρ_i = H(i, m, B)`, binding a signer's nonce pair to the
+/// message and the full commitment set so commitments can't be mixed
+/// across signing sessions.
+fn binding_factor(index: SignerIndex, message: &[u8], commitments: &[NonceCommitment]) -> Scalar {
+    let mut hasher = Sha512::new();
+    hasher.update(b"atomicsettle-frost-rho");
+    hasher.update(index.to_be_bytes());
+    hasher.update(message);
+    for commitment in commitments {
+        hasher.update(commitment.index.to_be_bytes());
+        hasher.update(commitment.d.compress().to_bytes());
+        hasher.update(commitment.e.compress().to_bytes());
+    }
+    Scalar::from_hash(hasher)
+}
+
+/// Group commitment `R = Σ(D_i + ρ_i·E_i)` over every signer in `B`.
+fn group_commitment(commitments: &[NonceCommitment], message: &[u8]) -> RistrettoPoint {
+    commitments.iter().fold(RistrettoPoint::identity(), |acc, commitment| {
+        let rho = binding_factor(commitment.index, message, commitments);
+        acc + commitment.d + commitment.e * rho
+    })
+}
+
+/// Challenge `c = H(R, Y, m)`, the same Schnorr challenge a single-party
+/// signature would use.
+fn challenge(r: RistrettoPoint, y: RistrettoPoint, message: &[u8]) -> Scalar {
+    let mut hasher = Sha512::new();
+    hasher.update(b"atomicsettle-frost-challenge");
+    hasher.update(r.compress().to_bytes());
+    hasher.update(y.compress().to_bytes());
+    hasher.update(message);
+    Scalar::from_hash(hasher)
+}
+
+/// The aggregate of `threshold`-or-more signers' partial signatures: an
+/// ordinary Schnorr signature `(R, z)` that verifies against the group key
+/// exactly like a single-party signature, plus a bitmap recording which
+/// signer indices (1-64) actually participated.
+#[derive(Debug, Clone)]
+pub struct ThresholdSignature {
+    signature: Signature,
+    signer_bitmap: u64,
+}
+
+/// Round 2's final step: sum the participating signers' partial
+/// signatures into `z = Σ z_i` and pair it with the group commitment `R`.
+pub fn aggregate(
+    message: &[u8],
+    commitments: &[NonceCommitment],
+    partials: &[PartialSignature],
+) -> Result<ThresholdSignature> {
+    if partials.len() != commitments.len()
+        || partials.iter().any(|p| {
+            !commitments.iter().any(|c| c.index == p.index)
+        })
+    {
+        return Err(CryptoError::InvalidSignature);
+    }
+
+    let mut signer_bitmap: u64 = 0;
+    for commitment in commitments {
+        if commitment.index == 0 || commitment.index > MAX_PARTICIPANTS {
+            return Err(CryptoError::InvalidKey(format!(
+                "signer index {} is out of range for a threshold bitmap",
+                commitment.index
+            )));
+        }
+        signer_bitmap |= 1u64 << (commitment.index - 1);
+    }
+
+    let r = group_commitment(commitments, message);
+    let z = partials.iter().fold(Scalar::ZERO, |acc, partial| acc + partial.z);
+
+    let mut bytes = Vec::with_capacity(64);
+    bytes.extend_from_slice(r.compress().as_bytes());
+    bytes.extend_from_slice(z.as_bytes());
+
+    Ok(ThresholdSignature {
+        signature: Signature {
+            bytes,
+            key_id: format!("frost-{}-of-{}", partials.len(), MAX_PARTICIPANTS),
+            algorithm: "FROST-Ristretto25519".to_string(),
+        },
+        signer_bitmap,
+    })
+}
+
+impl ThresholdSignature {
+    /// How many signers' indices are recorded in the bitmap.
+    pub fn signer_count(&self) -> u32 {
+        self.signer_bitmap.count_ones()
+    }
+
+    /// Whether `index` participated in producing this signature.
+    pub fn participated(&self, index: SignerIndex) -> bool {
+        index != 0
+            && index <= MAX_PARTICIPANTS
+            && (self.signer_bitmap >> (index - 1)) & 1 == 1
+    }
+
+    /// The distinct signer indices recorded in the bitmap.
+    pub fn signers(&self) -> BTreeSet<SignerIndex> {
+        (1..=MAX_PARTICIPANTS)
+            .filter(|&index| self.participated(index))
+            .collect()
+    }
+
+    /// Verify this is a valid Schnorr signature over `message` against
+    /// `group_key`, AND that at least `required_threshold` distinct
+    /// signers contributed to it. Identical in shape to verifying a
+    /// single-party [`Signature`] -- downstream code doesn't need to know
+    /// how many signers were involved, only that the threshold was met.
+    pub fn verify(
+        &self,
+        group_key: &GroupVerifyingKey,
+        message: &[u8],
+        required_threshold: u16,
+    ) -> Result<()> {
+        if self.signer_count() < required_threshold as u32 {
+            return Err(CryptoError::InvalidSignature);
+        }
+
+        if self.signature.bytes.len() != 64 {
+            return Err(CryptoError::InvalidSignature);
+        }
+
+        let r_bytes: [u8; 32] = self.signature.bytes[..32]
+            .try_into()
+            .map_err(|_| CryptoError::InvalidSignature)?;
+        let z_bytes: [u8; 32] = self.signature.bytes[32..]
+            .try_into()
+            .map_err(|_| CryptoError::InvalidSignature)?;
+
+        let r = CompressedRistretto(r_bytes)
+            .decompress()
+            .ok_or(CryptoError::InvalidSignature)?;
+        let z = Scalar::from_canonical_bytes(z_bytes)
+            .into_option()
+            .ok_or(CryptoError::InvalidSignature)?;
+
+        let c = challenge(r, group_key.point, message);
+        if RISTRETTO_BASEPOINT_POINT * z == r + group_key.point * c {
+            Ok(())
+        } else {
+            Err(CryptoError::InvalidSignature)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Run the full two-round protocol for the given signer indices and
+    /// return the aggregated signature.
+    fn sign_with(
+        shares: &[KeyShare],
+        signer_indices: &[SignerIndex],
+        message: &[u8],
+    ) -> ThresholdSignature {
+        let signers: Vec<&KeyShare> = shares
+            .iter()
+            .filter(|s| signer_indices.contains(&s.index))
+            .collect();
+
+        let mut nonces = Vec::new();
+        let mut commitments = Vec::new();
+        for share in &signers {
+            let (n, c) = SigningNonces::generate(share.index);
+            nonces.push(n);
+            commitments.push(c);
+        }
+
+        let partials: Vec<PartialSignature> = nonces
+            .into_iter()
+            .zip(signers.iter())
+            .map(|(nonce, share)| nonce.sign(share, message, &commitments).unwrap())
+            .collect();
+
+        aggregate(message, &commitments, &partials).unwrap()
+    }
+
+    #[test]
+    fn test_threshold_signature_verifies_with_exact_quorum() {
+        let config = ThresholdConfig::new(3, 5).unwrap();
+        let (group_key, shares) = generate_shares(config);
+        let message = b"settle 1,000,000 USD BANK_A -> BANK_B";
+
+        let signature = sign_with(&shares, &[1, 3, 5], message);
+
+        assert_eq!(signature.signer_count(), 3);
+        assert!(signature.verify(&group_key, message, 3).is_ok());
+    }
+
+    #[test]
+    fn test_any_quorum_of_threshold_signers_verifies() {
+        let config = ThresholdConfig::new(2, 4).unwrap();
+        let (group_key, shares) = generate_shares(config);
+        let message = b"settle 500 EUR BANK_C -> BANK_D";
+
+        let a = sign_with(&shares, &[1, 2], message);
+        let b = sign_with(&shares, &[3, 4], message);
+
+        assert!(a.verify(&group_key, message, 2).is_ok());
+        assert!(b.verify(&group_key, message, 2).is_ok());
+    }
+
+    #[test]
+    fn test_verify_rejects_below_threshold_signer_count() {
+        let config = ThresholdConfig::new(3, 5).unwrap();
+        let (group_key, shares) = generate_shares(config);
+        let message = b"settle 1,000,000 USD BANK_A -> BANK_B";
+
+        // Only 2 signers cooperate even though the group key requires 3.
+        let signature = sign_with(&shares, &[1, 2], message);
+
+        assert!(signature.verify(&group_key, message, 3).is_err());
+    }
+
+    #[test]
+    fn test_verify_rejects_tampered_message() {
+        let config = ThresholdConfig::new(2, 3).unwrap();
+        let (group_key, shares) = generate_shares(config);
+        let message = b"settle 200 GBP BANK_E -> BANK_F";
+
+        let signature = sign_with(&shares, &[1, 2], message);
+
+        assert!(signature
+            .verify(&group_key, b"settle 200000 GBP BANK_E -> BANK_F", 2)
+            .is_err());
+    }
+
+    #[test]
+    fn test_verify_rejects_wrong_group_key() {
+        let config = ThresholdConfig::new(2, 3).unwrap();
+        let (group_key, shares) = generate_shares(config);
+        let (other_group_key, _) = generate_shares(config);
+        let message = b"settle 75 JPY BANK_G -> BANK_H";
+
+        let signature = sign_with(&shares, &[1, 2], message);
+
+        assert_ne!(group_key.to_bytes(), other_group_key.to_bytes());
+        assert!(signature.verify(&other_group_key, message, 2).is_err());
+    }
+
+    #[test]
+    fn test_threshold_config_rejects_zero_threshold() {
+        assert!(ThresholdConfig::new(0, 5).is_err());
+    }
+
+    #[test]
+    fn test_threshold_config_rejects_threshold_above_participants() {
+        assert!(ThresholdConfig::new(6, 5).is_err());
+    }
+}
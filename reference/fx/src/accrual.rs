@@ -0,0 +1,209 @@
+//! Covered-interest-parity forward rate accrual.
+//!
+//! A spot [`FxRate`] is only correct for an instantaneous conversion. A
+//! rate held for a longer duration -- as `RateLockManager::create_forward_lock`
+//! does -- should reflect the time value of money between the two
+//! currencies: covered interest parity says the forward rate is the spot
+//! rate scaled by the ratio of the two currencies' compounded interest
+//! over the lock's duration. [`AccrualRate`] wraps a spot rate with that
+//! accumulated adjustment so the two stay distinguishable -- `inner` is
+//! never mutated, `acc` is the multiplier applied to both bid and ask to
+//! get the forward.
+
+use chrono::Duration;
+use rust_decimal::Decimal;
+
+use atomicsettle_common::FxRate;
+
+use crate::error::{FxError, FxResult};
+
+/// Periods per year used to convert an annualized [`RateDifferential`]
+/// into a per-period rate; one period is one whole day.
+const PERIODS_PER_YEAR: i64 = 365;
+
+/// Annualized interest-rate differential between a currency pair's base
+/// and quote currencies (e.g. `0.05` for 5%), used to accrue a spot rate
+/// forward over a lock's duration.
+#[derive(Debug, Clone, Copy)]
+pub struct RateDifferential {
+    /// Base currency's annualized interest rate.
+    pub base: Decimal,
+    /// Quote currency's annualized interest rate.
+    pub quote: Decimal,
+}
+
+/// A spot [`FxRate`] plus its accumulated forward adjustment. `inner` is
+/// the spot rate a lock was opened against; `acc` is the multiplier
+/// covered interest parity derives for the lock's duration, applied
+/// identically to `inner`'s bid and ask to produce the forward rate
+/// actually stored on the `RateLock`.
+#[derive(Debug, Clone)]
+pub struct AccrualRate {
+    /// The unmodified spot rate this accrual was derived from.
+    pub inner: FxRate,
+    /// The forward adjustment multiplier.
+    pub acc: Decimal,
+}
+
+impl AccrualRate {
+    /// Accrue `spot` forward over `duration` using `differential`,
+    /// following covered interest parity compounded over whole days:
+    /// `F = S * (1 + r_quote/365)^n / (1 + r_base/365)^n`, where `n` is
+    /// the number of whole days in `duration`. When `duration` rounds
+    /// down to zero whole days, `n == 0` and `acc` is exactly `1` -- the
+    /// forward equals the spot.
+    pub fn accrue(
+        spot: &FxRate,
+        duration: Duration,
+        differential: RateDifferential,
+    ) -> FxResult<Self> {
+        let periods = duration.num_days().max(0) as u32;
+        let periods_per_year = Decimal::from(PERIODS_PER_YEAR);
+
+        let quote_per_period = differential.quote / periods_per_year;
+        let base_per_period = differential.base / periods_per_year;
+
+        let numerator = checked_pow(Decimal::ONE + quote_per_period, periods)?;
+        let denominator = checked_pow(Decimal::ONE + base_per_period, periods)?;
+
+        if denominator.is_zero() {
+            return Err(FxError::ArithmeticOverflow);
+        }
+
+        let acc = numerator
+            .checked_div(denominator)
+            .ok_or(FxError::ArithmeticOverflow)?;
+
+        Ok(Self {
+            inner: spot.clone(),
+            acc,
+        })
+    }
+
+    /// The forward rate this accrual produces: `inner`'s bid/ask each
+    /// scaled by `acc`. An extreme enough differential can drive `acc`
+    /// negative, which would otherwise invert the spread -- the scaled
+    /// bid/ask are re-sorted so bid never ends up above ask.
+    pub fn forward_rate(&self, valid_for_seconds: i64) -> FxResult<FxRate> {
+        let bid = self
+            .inner
+            .bid
+            .checked_mul(self.acc)
+            .ok_or(FxError::ArithmeticOverflow)?;
+        let ask = self
+            .inner
+            .ask
+            .checked_mul(self.acc)
+            .ok_or(FxError::ArithmeticOverflow)?;
+        let (bid, ask) = if bid > ask { (ask, bid) } else { (bid, ask) };
+
+        Ok(FxRate::new(
+            self.inner.pair.clone(),
+            bid,
+            ask,
+            valid_for_seconds,
+            "forward-accrual",
+        ))
+    }
+}
+
+/// Raise `base` to the `exponent`-th power via checked exponentiation by
+/// squaring, returning [`FxError::ArithmeticOverflow`] instead of
+/// panicking if any intermediate multiplication overflows `Decimal`.
+pub fn checked_pow(base: Decimal, exponent: u32) -> FxResult<Decimal> {
+    let mut result = Decimal::ONE;
+    let mut b = base;
+    let mut e = exponent;
+
+    while e > 0 {
+        if e & 1 == 1 {
+            result = result.checked_mul(b).ok_or(FxError::ArithmeticOverflow)?;
+        }
+        e >>= 1;
+        if e > 0 {
+            b = b.checked_mul(b).ok_or(FxError::ArithmeticOverflow)?;
+        }
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use atomicsettle_common::{Currency, CurrencyPair};
+    use rust_decimal_macros::dec;
+
+    fn make_spot() -> FxRate {
+        FxRate::new(
+            CurrencyPair::new(Currency::usd(), Currency::eur()),
+            dec!(0.91),
+            dec!(0.93),
+            30,
+            "TEST",
+        )
+    }
+
+    #[test]
+    fn test_checked_pow() {
+        assert_eq!(checked_pow(dec!(1.1), 0).unwrap(), Decimal::ONE);
+        assert_eq!(checked_pow(dec!(2), 10).unwrap(), dec!(1024));
+    }
+
+    #[test]
+    fn test_checked_pow_overflow_is_explicit_error() {
+        assert!(matches!(
+            checked_pow(Decimal::MAX, 2),
+            Err(FxError::ArithmeticOverflow)
+        ));
+    }
+
+    #[test]
+    fn test_zero_periods_forward_equals_spot_exactly() {
+        let spot = make_spot();
+        let differential = RateDifferential {
+            base: dec!(0.01),
+            quote: dec!(0.05),
+        };
+
+        // Well under one day: rounds down to zero whole periods.
+        let accrual = AccrualRate::accrue(&spot, Duration::hours(12), differential).unwrap();
+        assert_eq!(accrual.acc, Decimal::ONE);
+
+        let forward = accrual.forward_rate(3600).unwrap();
+        assert_eq!(forward.bid, spot.bid);
+        assert_eq!(forward.ask, spot.ask);
+    }
+
+    #[test]
+    fn test_positive_quote_rate_pushes_forward_above_spot() {
+        let spot = make_spot();
+        let differential = RateDifferential {
+            base: dec!(0.0),
+            quote: dec!(0.10),
+        };
+
+        let accrual = AccrualRate::accrue(&spot, Duration::days(30), differential).unwrap();
+        let forward = accrual.forward_rate(2_592_000).unwrap();
+
+        assert!(forward.bid > spot.bid);
+        assert!(forward.ask > spot.ask);
+        assert!(forward.bid <= forward.ask);
+    }
+
+    #[test]
+    fn test_extreme_negative_differential_keeps_bid_le_ask() {
+        let spot = make_spot();
+        // Large enough negative base-rate differential to flip `acc`
+        // negative and exercise the bid/ask re-sort.
+        let differential = RateDifferential {
+            base: dec!(-400.0),
+            quote: dec!(0.0),
+        };
+
+        let accrual = AccrualRate::accrue(&spot, Duration::days(1), differential).unwrap();
+        let forward = accrual.forward_rate(86_400).unwrap();
+
+        assert!(forward.bid <= forward.ask);
+    }
+}
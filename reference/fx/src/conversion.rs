@@ -23,11 +23,26 @@ pub struct Conversion {
     pub rate_lock_id: Option<Uuid>,
     /// When the conversion was executed.
     pub executed_at: DateTime<Utc>,
+    /// Dutch-auction holding premium in basis points accrued on the rate
+    /// lock used, if any; zero when no lock backed this conversion.
+    pub premium_bps: u32,
+    /// Fixed-cost-per-conversion fee charged on top of FX margin, in
+    /// `output.currency`; zero when the engine has no `FeeModel`
+    /// configured.
+    pub fee: Money,
+    /// Ordered per-leg conversions this conversion was executed through,
+    /// e.g. `SRC/USD` then `USD/TGT` when triangulated via a vehicle
+    /// currency. Empty for a conversion executed directly against a single
+    /// `CurrencyPair`. The product of each hop's `effective_rate()` always
+    /// reconciles with this conversion's own `effective_rate()`, since each
+    /// hop's output is literally the next hop's input.
+    pub hops: Vec<Conversion>,
 }
 
 impl Conversion {
     /// Create a new conversion record.
     pub fn new(input: Money, output: Money, rate: FxRate, rate_lock_id: Option<Uuid>) -> Self {
+        let fee = Money::new(Decimal::ZERO, output.currency.clone());
         Self {
             id: Uuid::now_v7(),
             input,
@@ -35,9 +50,45 @@ impl Conversion {
             rate,
             rate_lock_id,
             executed_at: Utc::now(),
+            premium_bps: 0,
+            fee,
+            hops: Vec::new(),
         }
     }
 
+    /// Record the ordered hop conversions this conversion was triangulated
+    /// through.
+    pub fn with_hops(mut self, hops: Vec<Conversion>) -> Self {
+        self.hops = hops;
+        self
+    }
+
+    /// Whether this conversion was executed directly against a single
+    /// `CurrencyPair`, rather than triangulated through a vehicle currency.
+    pub fn is_direct(&self) -> bool {
+        self.hops.is_empty()
+    }
+
+    /// Record the rate lock's accrued holding premium on this conversion,
+    /// for the settlement layer to debit alongside the converted amount.
+    pub fn with_premium_bps(mut self, premium_bps: u32) -> Self {
+        self.premium_bps = premium_bps;
+        self
+    }
+
+    /// Record the fee charged on this conversion.
+    pub fn with_fee(mut self, fee: Money) -> Self {
+        self.fee = fee;
+        self
+    }
+
+    /// Output amount net of `fee`. Falls back to the gross `output` if
+    /// `fee` somehow ended up in a different currency than `output`,
+    /// rather than panicking on a currency mismatch at the call site.
+    pub fn net_output(&self) -> Money {
+        (self.output.clone() - self.fee.clone()).unwrap_or_else(|_| self.output.clone())
+    }
+
     /// Get the effective rate used.
     pub fn effective_rate(&self) -> Decimal {
         if self.input.value.is_zero() {
@@ -63,6 +114,11 @@ pub struct ConversionRequest {
     pub rate_lock: Option<RateLock>,
     /// Whether to use bid or ask rate.
     pub rate_side: RateSide,
+    /// Force triangulation through this vehicle currency instead of using
+    /// a direct quote or the engine's configured bridge currencies. Only
+    /// honored for unlocked conversions -- a `rate_lock` is always redeemed
+    /// directly against its own locked rate.
+    pub via: Option<Currency>,
 }
 
 impl ConversionRequest {
@@ -73,6 +129,7 @@ impl ConversionRequest {
             target_currency,
             rate_lock: None,
             rate_side: RateSide::Mid,
+            via: None,
         }
     }
 
@@ -82,6 +139,12 @@ impl ConversionRequest {
         self
     }
 
+    /// Force triangulation through `currency` instead of a direct quote.
+    pub fn via(mut self, currency: Currency) -> Self {
+        self.via = Some(currency);
+        self
+    }
+
     /// Use bid rate (for selling base currency).
     pub fn at_bid(mut self) -> Self {
         self.rate_side = RateSide::Bid;
@@ -123,6 +186,7 @@ pub struct ConversionBuilder {
     target_currency: Option<Currency>,
     rate_lock: Option<RateLock>,
     rate_side: RateSide,
+    via: Option<Currency>,
 }
 
 impl ConversionBuilder {
@@ -133,6 +197,7 @@ impl ConversionBuilder {
             target_currency: None,
             rate_lock: None,
             rate_side: RateSide::Mid,
+            via: None,
         }
     }
 
@@ -166,6 +231,12 @@ impl ConversionBuilder {
         self
     }
 
+    /// Force triangulation through `currency` instead of a direct quote.
+    pub fn via(mut self, currency: Currency) -> Self {
+        self.via = Some(currency);
+        self
+    }
+
     /// Build the conversion request.
     pub fn build(self) -> Option<ConversionRequest> {
         Some(ConversionRequest {
@@ -173,6 +244,7 @@ impl ConversionBuilder {
             target_currency: self.target_currency?,
             rate_lock: self.rate_lock,
             rate_side: self.rate_side,
+            via: self.via,
         })
     }
 }
@@ -233,4 +305,34 @@ mod tests {
         assert_eq!(request.target_currency, Currency::eur());
         assert_eq!(request.rate_side, RateSide::Bid);
     }
+
+    #[test]
+    fn test_conversion_builder_via_sets_forced_pivot() {
+        let request = ConversionBuilder::new()
+            .amount(Money::new(dec!(1000), Currency::new("JPY")))
+            .to(Currency::new("BHD"))
+            .via(Currency::usd())
+            .build()
+            .unwrap();
+
+        assert_eq!(request.via, Some(Currency::usd()));
+    }
+
+    #[test]
+    fn test_direct_conversion_has_no_hops() {
+        let input = Money::new(dec!(1000), Currency::usd());
+        let output = Money::new(dec!(920), Currency::eur());
+        let rate = FxRate::new(
+            CurrencyPair::new(Currency::usd(), Currency::eur()),
+            dec!(0.91),
+            dec!(0.93),
+            30,
+            "TEST",
+        );
+
+        let conversion = Conversion::new(input, output, rate, None);
+
+        assert!(conversion.is_direct());
+        assert!(conversion.hops.is_empty());
+    }
 }
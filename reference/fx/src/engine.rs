@@ -1,10 +1,14 @@
 //! Main FX engine implementation.
 
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 
 use atomicsettle_common::{Currency, CurrencyPair, FxRate, Money};
 use chrono::Duration;
-use tracing::{debug, info, instrument};
+use rust_decimal::Decimal;
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+use tracing::{debug, info, instrument, warn};
 use uuid::Uuid;
 
 use crate::cache::{RateCache, RateCacheConfig};
@@ -20,10 +24,31 @@ pub struct FxEngineConfig {
     pub cache: RateCacheConfig,
     /// Rate lock configuration.
     pub rate_lock: RateLockConfig,
-    /// Maximum allowed spread in basis points.
+    /// Maximum allowed spread in basis points on the *provider's* raw
+    /// bid/ask, checked when a rate is fetched.
     pub max_spread_bps: u32,
+    /// The operator's own customer-facing markup in basis points, applied
+    /// on top of the provider's mid-market rate: the quoted ask widens to
+    /// `mid * (1 + ask_spread_bps / 10_000)` and the quoted bid narrows to
+    /// `mid * (1 - ask_spread_bps / 10_000)`. This is the engine's margin,
+    /// distinct from `max_spread_bps`, which only sanity-checks the
+    /// provider's own spread.
+    pub ask_spread_bps: u32,
+    /// Maximum amount a single conversion may move, denominated in the
+    /// conversion's input currency. `None` means no cap. Conversions in a
+    /// different currency than the configured cap are not checked against
+    /// it.
+    pub max_convert_amount: Option<Money>,
     /// Whether to use cached rates.
     pub use_cache: bool,
+    /// Currencies tried as a bridge when no direct quote exists for a
+    /// requested pair, in order. A cross-rate is synthesized from
+    /// `base/bridge` and `bridge/quote` the first time both legs are
+    /// available.
+    pub bridge_currencies: Vec<Currency>,
+    /// Fixed-cost-per-conversion fee, distinct from and additive with the
+    /// `ask_spread_bps` FX margin.
+    pub fee_model: FeeModel,
 }
 
 impl Default for FxEngineConfig {
@@ -32,17 +57,60 @@ impl Default for FxEngineConfig {
             cache: RateCacheConfig::default(),
             rate_lock: RateLockConfig::default(),
             max_spread_bps: 200, // 2% max spread
+            ask_spread_bps: 0,
+            max_convert_amount: None,
             use_cache: true,
+            bridge_currencies: vec![Currency::usd(), Currency::eur()],
+            fee_model: FeeModel::default(),
         }
     }
 }
 
+/// A per-conversion fee, charged on top of FX margin: a flat amount in a
+/// configured currency plus an optional percentage of the converted
+/// output, combined additively.
+#[derive(Debug, Clone, Default)]
+pub struct FeeModel {
+    /// Flat fee charged per conversion, if any. Only applied when its
+    /// currency matches the conversion's output currency -- the fee model
+    /// charges in the settlement's own terms, not a currency that would
+    /// need converting itself.
+    pub fixed: Option<Money>,
+    /// Percentage fee in basis points, applied to the converted output
+    /// amount.
+    pub percentage_bps: u32,
+}
+
+impl FeeModel {
+    /// Total fee for a conversion whose output is `output`, denominated in
+    /// `output.currency` and rounded to that currency's native precision.
+    fn compute(&self, output: &Money) -> Money {
+        let mut total = match &self.fixed {
+            Some(fixed) if fixed.currency == output.currency => fixed.value,
+            _ => Decimal::ZERO,
+        };
+
+        let percentage = Decimal::from(self.percentage_bps) / Decimal::from(10_000);
+        total += output.value * percentage;
+
+        Money::new(total.round_dp(output.currency.decimal_places()), output.currency.clone())
+    }
+}
+
 /// The main FX engine.
 pub struct FxEngine {
     provider: Arc<dyn RateProvider>,
-    cache: RateCache,
+    cache: Arc<RateCache>,
     lock_manager: RateLockManager,
     config: FxEngineConfig,
+    /// Number of rate updates a [`start_streaming`](FxEngine::start_streaming)
+    /// task has folded into `cache`.
+    streamed_updates: Arc<AtomicU64>,
+    /// Whether the engine is currently accepting new quotes and locks.
+    /// Cleared by `set_resume_only(true)` to put the engine into
+    /// resume-only mode ahead of maintenance: already-issued `RateLock`s
+    /// still redeem, but nothing new is quoted.
+    accept_new: AtomicBool,
 }
 
 impl FxEngine {
@@ -50,15 +118,49 @@ impl FxEngine {
     pub fn new(provider: Arc<dyn RateProvider>, config: FxEngineConfig) -> Self {
         Self {
             provider,
-            cache: RateCache::with_config(config.cache.clone()),
+            cache: Arc::new(RateCache::with_config(config.cache.clone())),
             lock_manager: RateLockManager::with_config(config.rate_lock.clone()),
             config,
+            streamed_updates: Arc::new(AtomicU64::new(0)),
+            accept_new: AtomicBool::new(true),
         }
     }
 
-    /// Get the current rate for a currency pair.
+    /// Put the engine into resume-only mode, or return it to normal
+    /// operation. In resume-only mode, `get_rate`, `create_rate_lock`, and
+    /// `convert` calls that don't redeem an existing `RateLock` are
+    /// rejected with `FxError::QuotesSuspended`; `convert` calls that do
+    /// present a valid lock still succeed, so in-flight settlements can
+    /// drain without interruption while an operator takes the service
+    /// down for maintenance.
+    pub fn set_resume_only(&self, resume_only: bool) {
+        self.accept_new.store(!resume_only, Ordering::Relaxed);
+    }
+
+    /// Whether the engine is currently accepting new quotes and locks.
+    pub fn is_accepting_new(&self) -> bool {
+        self.accept_new.load(Ordering::Relaxed)
+    }
+
+    /// Get the current rate for a currency pair. Falls back to
+    /// triangulating a cross-rate through a configured bridge currency if
+    /// no direct quote is cached or provided.
     #[instrument(skip(self), fields(pair = %pair))]
     pub async fn get_rate(&self, pair: &CurrencyPair) -> FxResult<FxRate> {
+        if !self.is_accepting_new() {
+            return Err(FxError::QuotesSuspended);
+        }
+
+        match self.fetch_direct(pair).await {
+            Ok(rate) => Ok(rate),
+            Err(FxError::RateNotAvailable(_)) => self.triangulate(pair).await,
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Fetch a rate for `pair` directly from the cache or the provider,
+    /// without attempting cross-rate triangulation.
+    async fn fetch_direct(&self, pair: &CurrencyPair) -> FxResult<FxRate> {
         // Check cache first
         if self.config.use_cache {
             if let Some(cached) = self.cache.get(pair) {
@@ -81,6 +183,56 @@ impl FxEngine {
         Ok(rate)
     }
 
+    /// Synthesize a cross-rate for `pair` by composing `base/bridge` and
+    /// `bridge/quote` legs for the first configured bridge currency where
+    /// both legs are available, e.g. `JPY/BHD` via `JPY/USD x USD/BHD`.
+    async fn triangulate(&self, pair: &CurrencyPair) -> FxResult<FxRate> {
+        for bridge in &self.config.bridge_currencies {
+            if *bridge == pair.base || *bridge == pair.quote {
+                continue;
+            }
+
+            let base_leg_pair = CurrencyPair::new(pair.base.clone(), bridge.clone());
+            let bridge_leg_pair = CurrencyPair::new(bridge.clone(), pair.quote.clone());
+
+            let (base_leg, bridge_leg) = match (
+                self.fetch_direct(&base_leg_pair).await,
+                self.fetch_direct(&bridge_leg_pair).await,
+            ) {
+                (Ok(a), Ok(b)) if a.is_valid() && b.is_valid() => (a, b),
+                _ => continue,
+            };
+
+            let cross = Self::compose_cross_rate(pair.clone(), &base_leg, &bridge_leg);
+
+            if self.config.use_cache {
+                self.cache.insert(cross.clone());
+            }
+
+            return Ok(cross);
+        }
+
+        Err(FxError::RateNotAvailable(pair.clone()))
+    }
+
+    /// Compose two legs into a synthetic rate for `pair`: multiply mid
+    /// rates, and conservatively compound the worst-case bid/ask bounds
+    /// across both legs. `valid_until` is the earlier of the two legs'
+    /// expiries, since the synthetic rate can't outlive either of them.
+    fn compose_cross_rate(pair: CurrencyPair, base_leg: &FxRate, bridge_leg: &FxRate) -> FxRate {
+        let decimal_places = pair.quote.decimal_places();
+
+        FxRate {
+            pair,
+            bid: (base_leg.bid * bridge_leg.bid).round_dp(decimal_places),
+            ask: (base_leg.ask * bridge_leg.ask).round_dp(decimal_places),
+            mid: (base_leg.mid * bridge_leg.mid).round_dp(decimal_places),
+            quoted_at: chrono::Utc::now(),
+            valid_until: base_leg.valid_until.min(bridge_leg.valid_until),
+            source: format!("{}×{}", base_leg.pair, bridge_leg.pair),
+        }
+    }
+
     /// Get rate between two currencies.
     pub async fn get_rate_for(
         &self,
@@ -98,20 +250,86 @@ impl FxEngine {
         amount = %request.amount.value
     ))]
     pub async fn convert(&self, request: ConversionRequest) -> FxResult<Conversion> {
+        if request.rate_lock.is_none() && !self.is_accepting_new() {
+            return Err(FxError::QuotesSuspended);
+        }
+
+        if let Some(max) = &self.config.max_convert_amount {
+            if request.amount.currency == max.currency && request.amount.value > max.value {
+                return Err(FxError::AmountExceedsMax {
+                    amount: request.amount.clone(),
+                    max: max.clone(),
+                });
+            }
+        }
+
         let pair = CurrencyPair::new(
             request.amount.currency.clone(),
             request.target_currency.clone(),
         );
 
-        // Get rate (from lock or fresh)
-        let (rate, lock_id) = if let Some(lock) = request.rate_lock {
+        // A rate lock always redeems against its own locked rate -- forced
+        // triangulation via `request.via` only applies to unlocked quotes.
+        if let Some(lock) = request.rate_lock {
             let used_lock = self.lock_manager.use_lock(lock.id)?;
-            (used_lock.rate, Some(lock.id))
+            let rate = used_lock.lock.rate;
+
+            if request.amount.currency != rate.pair.base {
+                return Err(FxError::CurrencyMismatch {
+                    expected: rate.pair.base.clone(),
+                    actual: request.amount.currency.clone(),
+                });
+            }
+
+            let quote = self.apply_markup(&rate);
+            let conversion_rate = request.rate_side.get_rate(&quote);
+            let output_value = (request.amount.value * conversion_rate)
+                .round_dp(request.target_currency.decimal_places());
+            let output = Money::new(output_value, request.target_currency);
+            let fee = self.config.fee_model.compute(&output);
+
+            let conversion = Conversion::new(request.amount, output, quote, Some(lock.id))
+                .with_premium_bps(used_lock.premium_bps)
+                .with_fee(fee);
+
+            info!(
+                conversion_id = %conversion.id,
+                effective_rate = %conversion.effective_rate(),
+                fee = %conversion.fee,
+                net_output = %conversion.net_output(),
+                "Conversion completed"
+            );
+
+            return Ok(conversion);
+        }
+
+        let conversion = if let Some(pivot) = request.via.clone() {
+            self.convert_via_hops(request, pivot).await?
         } else {
-            (self.get_rate(&pair).await?, None)
+            match self.fetch_direct(&pair).await {
+                Ok(rate) => self.convert_direct(request, rate)?,
+                Err(FxError::RateNotAvailable(_)) => {
+                    let pivot = self.find_bridge(&pair).await?;
+                    self.convert_via_hops(request, pivot).await?
+                }
+                Err(e) => return Err(e),
+            }
         };
 
-        // Validate currencies
+        info!(
+            conversion_id = %conversion.id,
+            effective_rate = %conversion.effective_rate(),
+            fee = %conversion.fee,
+            net_output = %conversion.net_output(),
+            "Conversion completed"
+        );
+
+        Ok(conversion)
+    }
+
+    /// Complete a conversion against a single already-fetched direct rate,
+    /// with no triangulation involved.
+    fn convert_direct(&self, request: ConversionRequest, rate: FxRate) -> FxResult<Conversion> {
         if request.amount.currency != rate.pair.base {
             return Err(FxError::CurrencyMismatch {
                 expected: rate.pair.base.clone(),
@@ -119,22 +337,81 @@ impl FxEngine {
             });
         }
 
-        // Calculate output
-        let conversion_rate = request.rate_side.get_rate(&rate);
-        let output_value = (request.amount.value * conversion_rate).round_dp(
-            request.target_currency.decimal_places(),
-        );
+        // Derive our own customer-facing quote from the mid-market rate
+        // rather than trusting the provider's bid/ask directly.
+        let quote = self.apply_markup(&rate);
+
+        let conversion_rate = request.rate_side.get_rate(&quote);
+        let output_value = (request.amount.value * conversion_rate)
+            .round_dp(request.target_currency.decimal_places());
         let output = Money::new(output_value, request.target_currency);
+        let fee = self.config.fee_model.compute(&output);
 
-        let conversion = Conversion::new(request.amount, output, rate, lock_id);
+        Ok(Conversion::new(request.amount, output, quote, None).with_fee(fee))
+    }
 
-        info!(
-            conversion_id = %conversion.id,
-            effective_rate = %conversion.effective_rate(),
-            "Conversion completed"
-        );
+    /// Find a bridge currency, from the engine's configured
+    /// `bridge_currencies`, through which both legs of `pair` are
+    /// available, without composing or caching a synthetic rate for it.
+    /// Used ahead of [`Self::convert_via_hops`], which needs the *currency*
+    /// to triangulate through rather than a pre-composed [`FxRate`].
+    async fn find_bridge(&self, pair: &CurrencyPair) -> FxResult<Currency> {
+        for bridge in &self.config.bridge_currencies {
+            if *bridge == pair.base || *bridge == pair.quote {
+                continue;
+            }
 
-        Ok(conversion)
+            let base_leg_pair = CurrencyPair::new(pair.base.clone(), bridge.clone());
+            let bridge_leg_pair = CurrencyPair::new(bridge.clone(), pair.quote.clone());
+
+            if self.fetch_direct(&base_leg_pair).await.is_ok()
+                && self.fetch_direct(&bridge_leg_pair).await.is_ok()
+            {
+                return Ok(bridge.clone());
+            }
+        }
+
+        Err(FxError::RateNotAvailable(pair.clone()))
+    }
+
+    /// Execute a conversion as two sequential hops through `pivot`, e.g.
+    /// `JPY -> USD -> BHD`, recording each leg as its own [`Conversion`] in
+    /// the result's `hops`. Each leg applies `request.rate_side` to its own
+    /// quoted rate -- selling a hop's base currency uses its bid, buying it
+    /// uses its ask -- so the combined rate reflects the true round-trip
+    /// spread rather than a mid-market composite. The top-level
+    /// `Conversion`'s `effective_rate()` is the product of both hops' rates
+    /// by construction, since the first hop's output is the second hop's
+    /// input.
+    async fn convert_via_hops(&self, request: ConversionRequest, pivot: Currency) -> FxResult<Conversion> {
+        let base_leg_pair = CurrencyPair::new(request.amount.currency.clone(), pivot.clone());
+        let bridge_leg_pair = CurrencyPair::new(pivot.clone(), request.target_currency.clone());
+
+        let base_leg_rate = self.fetch_direct(&base_leg_pair).await?;
+        let bridge_leg_rate = self.fetch_direct(&bridge_leg_pair).await?;
+
+        let base_leg_quote = self.apply_markup(&base_leg_rate);
+        let bridge_leg_quote = self.apply_markup(&bridge_leg_rate);
+
+        let hop1_rate = request.rate_side.get_rate(&base_leg_quote);
+        let intermediate_value =
+            (request.amount.value * hop1_rate).round_dp(pivot.decimal_places());
+        let intermediate = Money::new(intermediate_value, pivot);
+        let hop1 = Conversion::new(request.amount.clone(), intermediate.clone(), base_leg_quote, None);
+
+        let hop2_rate = request.rate_side.get_rate(&bridge_leg_quote);
+        let output_value = (intermediate.value * hop2_rate)
+            .round_dp(request.target_currency.decimal_places());
+        let output = Money::new(output_value, request.target_currency.clone());
+        let hop2 = Conversion::new(intermediate, output.clone(), bridge_leg_quote, None);
+
+        let pair = CurrencyPair::new(request.amount.currency.clone(), request.target_currency);
+        let composed_quote = self.apply_markup(&Self::compose_cross_rate(pair, &base_leg_rate, &bridge_leg_rate));
+        let fee = self.config.fee_model.compute(&output);
+
+        Ok(Conversion::new(request.amount, output, composed_quote, None)
+            .with_fee(fee)
+            .with_hops(vec![hop1, hop2]))
     }
 
     /// Simple conversion using mid-market rate.
@@ -169,6 +446,49 @@ impl FxEngine {
         Ok(lock)
     }
 
+    /// Quote and lock a conversion in a single atomic step: fetches the
+    /// rate once, validates it, and derives both the previewed output and
+    /// the created [`RateLock`] from that one fetch, so the figure shown
+    /// to the participant and the rate the lock guarantees can never drift
+    /// apart the way two separate `get_rate`/`create_rate_lock` calls
+    /// could. This mirrors collapsing a separate spot-price step and
+    /// lock-setup step into one protocol exchange.
+    #[instrument(skip(self), fields(pair = %pair, amount = %amount.value))]
+    pub async fn quote_and_lock(
+        &self,
+        pair: &CurrencyPair,
+        amount: Money,
+        duration: Option<Duration>,
+        participant_id: String,
+    ) -> FxResult<(Conversion, RateLock)> {
+        if amount.currency != pair.base {
+            return Err(FxError::CurrencyMismatch {
+                expected: pair.base.clone(),
+                actual: amount.currency.clone(),
+            });
+        }
+
+        let rate = self.get_rate(pair).await?;
+        let quote = self.apply_markup(&rate);
+
+        let conversion_rate = RateSide::Mid.get_rate(&quote);
+        let output_value = (amount.value * conversion_rate).round_dp(pair.quote.decimal_places());
+        let output = Money::new(output_value, pair.quote.clone());
+
+        let fee = self.config.fee_model.compute(&output);
+        let lock = self.lock_manager.create_lock(rate, duration, participant_id)?;
+        let preview = Conversion::new(amount, output, quote, Some(lock.id)).with_fee(fee);
+
+        info!(
+            lock_id = %lock.id,
+            pair = %pair,
+            expires_at = %lock.expires_at,
+            "Quoted and locked rate atomically"
+        );
+
+        Ok((preview, lock))
+    }
+
     /// Get a rate lock by ID.
     pub fn get_rate_lock(&self, lock_id: Uuid) -> Option<RateLock> {
         self.lock_manager.get_lock(lock_id)
@@ -194,6 +514,8 @@ impl FxEngine {
         FxEngineStats {
             cache_stats: self.cache.stats(),
             lock_stats: self.lock_manager.stats(),
+            streamed_updates: self.streamed_updates.load(Ordering::Relaxed),
+            resume_only: !self.is_accepting_new(),
         }
     }
 
@@ -203,21 +525,94 @@ impl FxEngine {
         self.lock_manager.cleanup_expired();
     }
 
+    /// Derive the customer-facing bid/ask for `rate` by widening its
+    /// mid-market rate by `ask_spread_bps`, instead of passing the
+    /// provider's own bid/ask through to the customer. `mid`, `pair`, and
+    /// validity are carried over unchanged.
+    fn apply_markup(&self, rate: &FxRate) -> FxRate {
+        let decimal_places = rate.pair.quote.decimal_places();
+        let spread = Decimal::from(self.config.ask_spread_bps) / Decimal::from(10_000);
+
+        FxRate {
+            bid: (rate.mid * (Decimal::ONE - spread)).round_dp(decimal_places),
+            ask: (rate.mid * (Decimal::ONE + spread)).round_dp(decimal_places),
+            ..rate.clone()
+        }
+    }
+
     /// Validate that spread is within acceptable limits.
     fn validate_spread(&self, rate: &FxRate) -> FxResult<()> {
+        Self::check_spread(rate, self.config.max_spread_bps)
+    }
+
+    /// Standalone spread check usable without borrowing `self`, so the
+    /// [`start_streaming`](FxEngine::start_streaming) background task can
+    /// run it on a `'static` clone of just the spread limit it needs.
+    fn check_spread(rate: &FxRate, max_spread_bps: u32) -> FxResult<()> {
         let spread_bps = rate.spread_bps();
         let spread_u32 = spread_bps.trunc().to_string().parse::<u32>().unwrap_or(0);
 
-        if spread_u32 > self.config.max_spread_bps {
+        if spread_u32 > max_spread_bps {
             return Err(FxError::SpreadTooWide {
                 pair: rate.pair.clone(),
                 spread_bps: spread_u32,
-                max_bps: self.config.max_spread_bps,
+                max_bps: max_spread_bps,
             });
         }
 
         Ok(())
     }
+
+    /// Start consuming `provider`'s live push feed for `pairs`, keeping
+    /// `cache` continuously warm so `get_rate` can serve near-real-time
+    /// rates with no provider round-trip on the hot path. Each received
+    /// rate is still run through the configured `max_spread_bps` check
+    /// before being cached; a rate that fails it is dropped and logged
+    /// rather than poisoning the cache. Returns a [`StreamingHandle`] the
+    /// caller uses to stop the background task.
+    pub async fn start_streaming(&self, pairs: Vec<CurrencyPair>) -> FxResult<StreamingHandle> {
+        let mut rx = self.provider.subscribe(&pairs).await?;
+        let cache = self.cache.clone();
+        let max_spread_bps = self.config.max_spread_bps;
+        let streamed_updates = self.streamed_updates.clone();
+        let (stop_tx, mut stop_rx) = oneshot::channel();
+
+        let handle = tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    maybe_rate = rx.recv() => {
+                        let Some(rate) = maybe_rate else { break };
+                        match Self::check_spread(&rate, max_spread_bps) {
+                            Ok(()) => {
+                                cache.insert(rate);
+                                streamed_updates.fetch_add(1, Ordering::Relaxed);
+                            }
+                            Err(e) => warn!(error = %e, "Discarding streamed rate"),
+                        }
+                    }
+                    _ = &mut stop_rx => break,
+                }
+            }
+        });
+
+        Ok(StreamingHandle { handle, stop_tx })
+    }
+}
+
+/// Handle to a background task started by
+/// [`FxEngine::start_streaming`](FxEngine::start_streaming), modeled on
+/// the coordinator's `BackgroundProcessor`.
+pub struct StreamingHandle {
+    handle: JoinHandle<()>,
+    stop_tx: oneshot::Sender<()>,
+}
+
+impl StreamingHandle {
+    /// Signal the streaming task to stop and wait for it to finish.
+    pub async fn stop(self) {
+        let _ = self.stop_tx.send(());
+        let _ = self.handle.await;
+    }
 }
 
 /// Engine statistics.
@@ -225,6 +620,12 @@ impl FxEngine {
 pub struct FxEngineStats {
     pub cache_stats: crate::cache::CacheStats,
     pub lock_stats: crate::rate_lock::RateLockStats,
+    /// Number of rate updates applied to the cache by a streaming task
+    /// started via `start_streaming`.
+    pub streamed_updates: u64,
+    /// Whether the engine is currently in resume-only mode (see
+    /// `FxEngine::set_resume_only`).
+    pub resume_only: bool,
 }
 
 #[cfg(test)]
@@ -305,6 +706,142 @@ mod tests {
         assert_eq!(conversion.output.currency, Currency::eur());
     }
 
+    #[tokio::test]
+    async fn test_quote_and_lock_preview_matches_later_locked_conversion() {
+        let engine = setup_engine();
+        let pair = CurrencyPair::new(Currency::usd(), Currency::eur());
+        let amount = Money::new(dec!(1000), Currency::usd());
+
+        let (preview, lock) = engine
+            .quote_and_lock(&pair, amount.clone(), None, "BANK_A".to_string())
+            .await
+            .unwrap();
+
+        assert_eq!(preview.rate_lock_id, Some(lock.id));
+        assert_eq!(preview.output.currency, Currency::eur());
+        // Mid rate is 0.92, so 1000 USD previews as 920 EUR.
+        assert_eq!(preview.output.value, dec!(920));
+
+        // Redeeming the returned lock through the normal convert() path
+        // reaches the same output the preview promised.
+        let request = ConversionRequest::new(amount, Currency::eur()).with_rate_lock(lock.clone());
+        let conversion = engine.convert(request).await.unwrap();
+
+        assert_eq!(conversion.output.value, preview.output.value);
+    }
+
+    #[tokio::test]
+    async fn test_quote_and_lock_rejects_currency_mismatch() {
+        let engine = setup_engine();
+        let pair = CurrencyPair::new(Currency::usd(), Currency::eur());
+        let amount = Money::new(dec!(1000), Currency::gbp());
+
+        let result = engine
+            .quote_and_lock(&pair, amount, None, "BANK_A".to_string())
+            .await;
+
+        assert!(matches!(result, Err(FxError::CurrencyMismatch { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_resume_only_suspends_new_quotes_but_honors_existing_locks() {
+        let engine = setup_engine();
+        let pair = CurrencyPair::new(Currency::usd(), Currency::eur());
+
+        let lock = engine
+            .create_rate_lock(&pair, None, "BANK_A".to_string())
+            .await
+            .unwrap();
+
+        engine.set_resume_only(true);
+        assert!(!engine.is_accepting_new());
+        assert!(engine.stats().resume_only);
+
+        // New quoting is shut off.
+        assert!(matches!(
+            engine.get_rate(&pair).await,
+            Err(FxError::QuotesSuspended)
+        ));
+        assert!(matches!(
+            engine.create_rate_lock(&pair, None, "BANK_B".to_string()).await,
+            Err(FxError::QuotesSuspended)
+        ));
+        let unlocked = ConversionRequest::new(Money::new(dec!(1000), Currency::usd()), Currency::eur());
+        assert!(matches!(
+            engine.convert(unlocked).await,
+            Err(FxError::QuotesSuspended)
+        ));
+
+        // But the already-issued lock still redeems.
+        let locked = ConversionRequest::new(Money::new(dec!(1000), Currency::usd()), Currency::eur())
+            .with_rate_lock(lock.clone());
+        let conversion = engine.convert(locked).await.unwrap();
+        assert_eq!(conversion.rate_lock_id, Some(lock.id));
+
+        // Returning to normal operation re-enables quoting.
+        engine.set_resume_only(false);
+        assert!(engine.get_rate(&pair).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_fee_model_combines_fixed_and_percentage() {
+        let provider = Arc::new(MockRateProvider::new("test"));
+        provider.set_rate(FxRate::new(
+            CurrencyPair::new(Currency::usd(), Currency::eur()),
+            dec!(0.91),
+            dec!(0.93),
+            30,
+            "TEST",
+        ));
+
+        let config = FxEngineConfig {
+            max_spread_bps: 300,
+            fee_model: FeeModel {
+                fixed: Some(Money::new(dec!(5), Currency::eur())),
+                percentage_bps: 100, // 1%
+            },
+            ..Default::default()
+        };
+        let engine = FxEngine::new(provider, config);
+
+        let request = ConversionRequest::new(Money::new(dec!(1000), Currency::usd()), Currency::eur());
+        let conversion = engine.convert(request).await.unwrap();
+
+        // Output is 1000 * 0.92 = 920; fee is a flat 5 EUR plus 1% of 920
+        // (9.20) = 14.20, rounded to EUR's 2 decimal places.
+        assert_eq!(conversion.output.value, dec!(920));
+        assert_eq!(conversion.fee.value, dec!(14.20));
+        assert_eq!(conversion.net_output().value, dec!(905.80));
+    }
+
+    #[tokio::test]
+    async fn test_fee_model_ignores_fixed_fee_in_a_different_currency() {
+        let provider = Arc::new(MockRateProvider::new("test"));
+        provider.set_rate(FxRate::new(
+            CurrencyPair::new(Currency::usd(), Currency::eur()),
+            dec!(0.91),
+            dec!(0.93),
+            30,
+            "TEST",
+        ));
+
+        let config = FxEngineConfig {
+            max_spread_bps: 300,
+            fee_model: FeeModel {
+                fixed: Some(Money::new(dec!(5), Currency::usd())),
+                percentage_bps: 0,
+            },
+            ..Default::default()
+        };
+        let engine = FxEngine::new(provider, config);
+
+        let request = ConversionRequest::new(Money::new(dec!(1000), Currency::usd()), Currency::eur());
+        let conversion = engine.convert(request).await.unwrap();
+
+        assert_eq!(conversion.fee.value, dec!(0));
+        assert_eq!(conversion.net_output().value, conversion.output.value);
+    }
+
     #[tokio::test]
     async fn test_rate_not_available() {
         let engine = setup_engine();
@@ -332,6 +869,197 @@ mod tests {
         assert_eq!(engine.cache.len(), 1);
     }
 
+    #[tokio::test]
+    async fn test_triangulates_via_bridge_currency_when_no_direct_rate() {
+        let provider = Arc::new(MockRateProvider::new("test"));
+
+        provider.set_rate(FxRate::new(
+            CurrencyPair::new(Currency::new("JPY"), Currency::usd()),
+            dec!(0.0064),
+            dec!(0.0066),
+            30,
+            "TEST",
+        ));
+        provider.set_rate(FxRate::new(
+            CurrencyPair::new(Currency::usd(), Currency::new("BHD")),
+            dec!(0.37),
+            dec!(0.38),
+            30,
+            "TEST",
+        ));
+
+        let config = FxEngineConfig {
+            max_spread_bps: 1_000,
+            ..Default::default()
+        };
+        let engine = FxEngine::new(provider, config);
+        let pair = CurrencyPair::new(Currency::new("JPY"), Currency::new("BHD"));
+
+        let rate = engine.get_rate(&pair).await.unwrap();
+
+        assert_eq!(rate.pair, pair);
+        assert_eq!(rate.source, "JPY/USD×USD/BHD");
+        // base mid 0.0065 x bridge mid 0.375 = 0.0024375, rounded to BHD's
+        // 3 decimal places.
+        assert_eq!(rate.mid, dec!(0.002));
+        assert_eq!(rate.mid.scale(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_triangulation_fails_without_any_bridge_route() {
+        let engine = setup_engine();
+        let pair = CurrencyPair::new(Currency::new("JPY"), Currency::new("BHD"));
+
+        let result = engine.get_rate(&pair).await;
+
+        assert!(matches!(result, Err(FxError::RateNotAvailable(_))));
+    }
+
+    #[tokio::test]
+    async fn test_convert_triangulates_via_bridge_and_records_hops() {
+        let provider = Arc::new(MockRateProvider::new("test"));
+
+        provider.set_rate(FxRate::new(
+            CurrencyPair::new(Currency::new("JPY"), Currency::usd()),
+            dec!(0.0064),
+            dec!(0.0066),
+            30,
+            "TEST",
+        ));
+        provider.set_rate(FxRate::new(
+            CurrencyPair::new(Currency::usd(), Currency::new("BHD")),
+            dec!(0.37),
+            dec!(0.38),
+            30,
+            "TEST",
+        ));
+
+        let config = FxEngineConfig {
+            max_spread_bps: 1_000,
+            ..Default::default()
+        };
+        let engine = FxEngine::new(provider, config);
+
+        let request = ConversionRequest::new(
+            Money::new(dec!(10_000), Currency::new("JPY")),
+            Currency::new("BHD"),
+        );
+        let conversion = engine.convert(request).await.unwrap();
+
+        assert_eq!(conversion.hops.len(), 2);
+        assert_eq!(conversion.hops[0].output.currency, Currency::usd());
+        assert_eq!(conversion.hops[1].input, conversion.hops[0].output);
+        assert_eq!(conversion.hops[1].output, conversion.output);
+
+        // The product of the two hops' own effective rates reconciles with
+        // the top-level conversion's effective rate.
+        let hop_product = conversion.hops[0].effective_rate() * conversion.hops[1].effective_rate();
+        assert_eq!(conversion.effective_rate(), hop_product);
+    }
+
+    #[tokio::test]
+    async fn test_convert_via_forces_a_pivot_even_with_a_direct_rate_available() {
+        let provider = Arc::new(MockRateProvider::new("test"));
+        provider.set_rate(FxRate::new(
+            CurrencyPair::new(Currency::gbp(), Currency::eur()),
+            dec!(1.14),
+            dec!(1.16),
+            30,
+            "DIRECT",
+        ));
+        provider.set_rate(FxRate::new(
+            CurrencyPair::new(Currency::gbp(), Currency::usd()),
+            dec!(1.26),
+            dec!(1.28),
+            30,
+            "TEST",
+        ));
+        provider.set_rate(FxRate::new(
+            CurrencyPair::new(Currency::usd(), Currency::eur()),
+            dec!(0.91),
+            dec!(0.93),
+            30,
+            "TEST",
+        ));
+
+        let config = FxEngineConfig {
+            max_spread_bps: 300,
+            ..Default::default()
+        };
+        let engine = FxEngine::new(provider, config);
+
+        let request = ConversionRequest::new(Money::new(dec!(1000), Currency::gbp()), Currency::eur())
+            .via(Currency::usd());
+        let conversion = engine.convert(request).await.unwrap();
+
+        // Triangulated via USD despite a direct GBP/EUR quote being available.
+        assert_eq!(conversion.hops.len(), 2);
+        assert_eq!(conversion.hops[0].output.currency, Currency::usd());
+    }
+
+    #[tokio::test]
+    async fn test_convert_via_fails_clearly_when_a_leg_is_missing() {
+        let engine = setup_engine();
+
+        let request = ConversionRequest::new(Money::new(dec!(1000), Currency::usd()), Currency::eur())
+            .via(Currency::gbp());
+        let result = engine.convert(request).await;
+
+        assert!(matches!(result, Err(FxError::RateNotAvailable(_))));
+    }
+
+    #[tokio::test]
+    async fn test_ask_spread_bps_widens_quote_from_mid() {
+        let provider = Arc::new(MockRateProvider::new("test"));
+        provider.set_rate(FxRate::new(
+            CurrencyPair::new(Currency::usd(), Currency::eur()),
+            dec!(0.91),
+            dec!(0.93),
+            30,
+            "TEST",
+        ));
+
+        let config = FxEngineConfig {
+            max_spread_bps: 300,
+            ask_spread_bps: 500, // 5% markup on top of the 0.92 mid
+            ..Default::default()
+        };
+        let engine = FxEngine::new(provider, config);
+
+        let request = ConversionRequest::new(Money::new(dec!(1000), Currency::usd()), Currency::eur())
+            .at_ask();
+        let conversion = engine.convert(request).await.unwrap();
+
+        // Quoted ask is 0.92 * 1.05 = 0.966, rounded to EUR's 2 decimal
+        // places — not the provider's raw 0.93.
+        assert_eq!(conversion.rate.ask, dec!(0.97));
+        assert_eq!(conversion.output.value, dec!(970.00));
+    }
+
+    #[tokio::test]
+    async fn test_max_convert_amount_rejects_oversized_request() {
+        let provider = Arc::new(MockRateProvider::new("test"));
+        provider.set_rate(FxRate::new(
+            CurrencyPair::new(Currency::usd(), Currency::eur()),
+            dec!(0.91),
+            dec!(0.93),
+            30,
+            "TEST",
+        ));
+
+        let config = FxEngineConfig {
+            max_spread_bps: 300,
+            max_convert_amount: Some(Money::new(dec!(500), Currency::usd())),
+            ..Default::default()
+        };
+        let engine = FxEngine::new(provider, config);
+
+        let request = ConversionRequest::new(Money::new(dec!(1000), Currency::usd()), Currency::eur());
+        let result = engine.convert(request).await;
+
+        assert!(matches!(result, Err(FxError::AmountExceedsMax { .. })));
+    }
+
     #[tokio::test]
     async fn test_spread_validation() {
         let provider = Arc::new(MockRateProvider::new("test"));
@@ -357,4 +1085,66 @@ mod tests {
 
         assert!(matches!(result, Err(FxError::SpreadTooWide { .. })));
     }
+
+    #[tokio::test]
+    async fn test_start_streaming_keeps_cache_warm_from_pushed_rates() {
+        let provider = Arc::new(MockRateProvider::new("test"));
+        let pair = CurrencyPair::new(Currency::usd(), Currency::eur());
+        let config = FxEngineConfig {
+            max_spread_bps: 300,
+            ..Default::default()
+        };
+        let engine = FxEngine::new(provider.clone(), config);
+
+        let handle = engine.start_streaming(vec![pair.clone()]).await.unwrap();
+        provider.push_streamed_rate(FxRate::new(pair.clone(), dec!(0.91), dec!(0.93), 30, "LIVE"));
+
+        // Poll until the background task has folded the push into the cache.
+        for _ in 0..50 {
+            if engine.cache.len() == 1 {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+
+        let rate = engine.get_rate(&pair).await.unwrap();
+        assert_eq!(rate.source, "LIVE");
+        assert_eq!(engine.stats().streamed_updates, 1);
+
+        handle.stop().await;
+    }
+
+    #[tokio::test]
+    async fn test_start_streaming_drops_rates_with_excessive_spread() {
+        let provider = Arc::new(MockRateProvider::new("test"));
+        let pair = CurrencyPair::new(Currency::usd(), Currency::eur());
+        let config = FxEngineConfig {
+            max_spread_bps: 200, // 2% max
+            ..Default::default()
+        };
+        let engine = FxEngine::new(provider.clone(), config);
+
+        let handle = engine.start_streaming(vec![pair.clone()]).await.unwrap();
+        // 10% spread -- should be discarded, not cached.
+        provider.push_streamed_rate(FxRate::new(pair.clone(), dec!(0.85), dec!(0.95), 30, "LIVE"));
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+
+        assert_eq!(engine.cache.len(), 0);
+        assert_eq!(engine.stats().streamed_updates, 0);
+
+        handle.stop().await;
+    }
+
+    #[tokio::test]
+    async fn test_start_streaming_errors_for_non_streaming_provider() {
+        // AggregatedRateProvider has no streaming support and falls back
+        // to the trait's default `subscribe` implementation.
+        let provider = Arc::new(crate::provider::AggregatedRateProvider::new(vec![]));
+        let engine = FxEngine::new(provider, FxEngineConfig::default());
+
+        let pair = CurrencyPair::new(Currency::usd(), Currency::eur());
+        let result = engine.start_streaming(vec![pair]).await;
+
+        assert!(matches!(result, Err(FxError::ProviderError(_))));
+    }
 }
@@ -1,6 +1,6 @@
 //! FX engine error types.
 
-use atomicsettle_common::{Currency, CurrencyPair};
+use atomicsettle_common::{Currency, CurrencyPair, Money};
 use thiserror::Error;
 
 /// Errors that can occur in the FX engine.
@@ -48,6 +48,22 @@ pub enum FxError {
         pair: CurrencyPair,
         deviation_bps: u32,
     },
+
+    /// A rate computation (e.g. forward-rate accrual) overflowed
+    /// `Decimal`'s range.
+    #[error("arithmetic overflow computing rate")]
+    ArithmeticOverflow,
+
+    /// Requested conversion amount exceeds the engine's configured
+    /// per-conversion notional cap.
+    #[error("amount {amount} exceeds maximum convertible amount {max}")]
+    AmountExceedsMax { amount: Money, max: Money },
+
+    /// The engine is in resume-only mode and isn't issuing new quotes or
+    /// locks; only `convert` calls that redeem an existing `RateLock` are
+    /// still honored.
+    #[error("quotes suspended: engine is in resume-only mode")]
+    QuotesSuspended,
 }
 
 /// Result type for FX operations.
@@ -30,11 +30,15 @@ pub mod provider;
 pub mod cache;
 pub mod conversion;
 pub mod rate_lock;
+pub mod accrual;
+pub mod triangulation;
 pub mod error;
 
-pub use engine::{FxEngine, FxEngineConfig};
+pub use engine::{FxEngine, FxEngineConfig, StreamingHandle};
 pub use provider::{RateProvider, AggregatedRateProvider};
 pub use cache::RateCache;
 pub use conversion::Conversion;
 pub use rate_lock::{RateLock, RateLockManager};
+pub use accrual::{AccrualRate, RateDifferential};
+pub use triangulation::TriangulatingRateProvider;
 pub use error::FxError;
@@ -3,7 +3,9 @@
 use async_trait::async_trait;
 use atomicsettle_common::{Currency, CurrencyPair, FxRate};
 use rust_decimal::Decimal;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
+use tokio::sync::mpsc;
 use tracing::{debug, warn};
 
 use crate::error::{FxError, FxResult};
@@ -22,6 +24,20 @@ pub trait RateProvider: Send + Sync {
 
     /// Get all supported currency pairs.
     fn supported_pairs(&self) -> Vec<CurrencyPair>;
+
+    /// Subscribe to a live push feed of rate updates for `pairs`, for
+    /// providers backed by a streaming source (e.g. a WebSocket
+    /// market-data connection) rather than request/response polling.
+    /// Providers that are purely pull-based return a
+    /// [`FxError::ProviderError`]; callers should fall back to polling
+    /// `get_rate`.
+    async fn subscribe(&self, pairs: &[CurrencyPair]) -> FxResult<mpsc::Receiver<FxRate>> {
+        let _ = pairs;
+        Err(FxError::ProviderError(format!(
+            "{} does not support streaming rate updates",
+            self.name()
+        )))
+    }
 }
 
 /// Aggregates multiple rate providers and returns median rate.
@@ -29,6 +45,13 @@ pub struct AggregatedRateProvider {
     providers: Vec<Arc<dyn RateProvider>>,
     min_providers: usize,
     max_deviation_bps: u32,
+    /// Outlier rejection threshold: a provider whose mid deviates from the
+    /// batch median by more than `mad_threshold * MAD` is discarded before
+    /// the median and `min_providers` checks run.
+    mad_threshold: Decimal,
+    /// Number of providers discarded as outliers on the most recent
+    /// `get_rate` call, for operators to monitor feed quality.
+    last_rejected: AtomicUsize,
 }
 
 impl AggregatedRateProvider {
@@ -38,6 +61,8 @@ impl AggregatedRateProvider {
             providers,
             min_providers: 1,
             max_deviation_bps: 100, // 1% max deviation
+            mad_threshold: Decimal::from(3),
+            last_rejected: AtomicUsize::new(0),
         }
     }
 
@@ -53,6 +78,58 @@ impl AggregatedRateProvider {
         self
     }
 
+    /// Set the MAD outlier rejection threshold `k` (default `3.0`): a
+    /// provider whose mid is more than `k` median-absolute-deviations from
+    /// the batch median is discarded before the median is taken.
+    pub fn with_mad_threshold(mut self, k: Decimal) -> Self {
+        self.mad_threshold = k;
+        self
+    }
+
+    /// Number of providers discarded as outliers on the most recent
+    /// `get_rate` call.
+    pub fn last_rejected(&self) -> usize {
+        self.last_rejected.load(Ordering::Relaxed)
+    }
+
+    /// Discard providers whose mid is an outlier relative to the batch,
+    /// modeled on oracle price-sanitization: take the median `m` of all
+    /// mids, then the median of the absolute deviations `|x_i - m|` (the
+    /// MAD), then drop any provider whose deviation exceeds
+    /// `mad_threshold * MAD`. A single stale or glitched feed is removed
+    /// here instead of poisoning the whole batch's median, while
+    /// `min_providers` still fails closed if too few survive. Returns the
+    /// survivors and how many were rejected.
+    fn reject_outliers(&self, rates: Vec<FxRate>) -> (Vec<FxRate>, usize) {
+        let mut mids: Vec<Decimal> = rates.iter().map(|r| r.mid).collect();
+        let median_mid = median_decimal(&mut mids);
+
+        let mut deviations: Vec<Decimal> =
+            mids.iter().map(|mid| (*mid - median_mid).abs()).collect();
+        let mad = median_decimal(&mut deviations);
+
+        // A MAD of zero means every mid agrees exactly with the median --
+        // nothing to reject, and dividing by it would be meaningless.
+        if mad.is_zero() {
+            return (rates, 0);
+        }
+
+        let threshold = self.mad_threshold * mad;
+        let mut rejected = 0usize;
+        let survivors = rates
+            .into_iter()
+            .filter(|r| {
+                let keep = (r.mid - median_mid).abs() <= threshold;
+                if !keep {
+                    rejected += 1;
+                }
+                keep
+            })
+            .collect();
+
+        (survivors, rejected)
+    }
+
     /// Calculate median of rates.
     fn calculate_median(&self, rates: &mut [FxRate]) -> FxRate {
         rates.sort_by(|a, b| a.mid.cmp(&b.mid));
@@ -143,11 +220,17 @@ impl RateProvider for AggregatedRateProvider {
             }
         }
 
+        let (mut rates, rejected) = self.reject_outliers(rates);
+        self.last_rejected.store(rejected, Ordering::Relaxed);
+        if rejected > 0 {
+            debug!(pair = %pair, rejected, "Discarded outlier providers before aggregation");
+        }
+
         if rates.len() < self.min_providers {
             return Err(FxError::RateNotAvailable(pair.clone()));
         }
 
-        // Check for excessive deviation
+        // Check for excessive deviation among the survivors
         self.check_deviation(&rates, pair)?;
 
         // Return median rate
@@ -170,11 +253,30 @@ impl RateProvider for AggregatedRateProvider {
     }
 }
 
+/// Median of `values`, sorting in place. The empty slice returns zero --
+/// callers only ever pass mids/deviations derived from a non-empty rate
+/// batch, so this is a defensive fallback, not a case meant to be hit.
+fn median_decimal(values: &mut [Decimal]) -> Decimal {
+    values.sort();
+    let len = values.len();
+    if len == 0 {
+        return Decimal::ZERO;
+    }
+
+    let mid_idx = len / 2;
+    if len % 2 == 0 && len > 1 {
+        (values[mid_idx - 1] + values[mid_idx]) / Decimal::TWO
+    } else {
+        values[mid_idx]
+    }
+}
+
 /// Mock rate provider for testing.
 #[cfg(any(test, feature = "test-utils"))]
 pub struct MockRateProvider {
     name: String,
     rates: dashmap::DashMap<String, FxRate>,
+    streams: dashmap::DashMap<String, mpsc::Sender<FxRate>>,
 }
 
 #[cfg(any(test, feature = "test-utils"))]
@@ -184,6 +286,7 @@ impl MockRateProvider {
         Self {
             name: name.into(),
             rates: dashmap::DashMap::new(),
+            streams: dashmap::DashMap::new(),
         }
     }
 
@@ -192,6 +295,15 @@ impl MockRateProvider {
         let key = format!("{}", rate.pair);
         self.rates.insert(key, rate);
     }
+
+    /// Push a rate update to any active `subscribe` stream for its pair,
+    /// for tests driving [`crate::engine::FxEngine::start_streaming`].
+    pub fn push_streamed_rate(&self, rate: FxRate) {
+        let key = format!("{}", rate.pair);
+        if let Some(tx) = self.streams.get(&key) {
+            let _ = tx.try_send(rate);
+        }
+    }
 }
 
 #[cfg(any(test, feature = "test-utils"))]
@@ -217,6 +329,14 @@ impl RateProvider for MockRateProvider {
     fn supported_pairs(&self) -> Vec<CurrencyPair> {
         self.rates.iter().map(|r| r.pair.clone()).collect()
     }
+
+    async fn subscribe(&self, pairs: &[CurrencyPair]) -> FxResult<mpsc::Receiver<FxRate>> {
+        let (tx, rx) = mpsc::channel(pairs.len().max(1) * 4 + 4);
+        for pair in pairs {
+            self.streams.insert(format!("{}", pair), tx.clone());
+        }
+        Ok(rx)
+    }
 }
 
 #[cfg(test)]
@@ -267,5 +387,73 @@ mod tests {
         assert_eq!(result.bid, dec!(0.91));
         assert_eq!(result.ask, dec!(0.93));
         assert_eq!(result.source, "AGGREGATED");
+        assert_eq!(aggregated.last_rejected(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_aggregated_provider_rejects_single_glitched_feed() {
+        let p1 = Arc::new(MockRateProvider::new("p1"));
+        let p2 = Arc::new(MockRateProvider::new("p2"));
+        let p3 = Arc::new(MockRateProvider::new("p3"));
+        let glitched = Arc::new(MockRateProvider::new("glitched"));
+
+        p1.set_rate(make_test_rate("USD", "EUR", dec!(0.90), dec!(0.92)));
+        p2.set_rate(make_test_rate("USD", "EUR", dec!(0.91), dec!(0.93)));
+        p3.set_rate(make_test_rate("USD", "EUR", dec!(0.92), dec!(0.94)));
+        // Wildly off from the other three -- a stale or glitched feed.
+        glitched.set_rate(make_test_rate("USD", "EUR", dec!(5.00), dec!(5.02)));
+
+        let aggregated = AggregatedRateProvider::new(vec![p1, p2, p3, glitched]);
+
+        let pair = CurrencyPair::new(Currency::usd(), Currency::eur());
+        let result = aggregated.get_rate(&pair).await.unwrap();
+
+        // The glitched feed should have been dropped before the median
+        // was taken, so the result looks like the healthy quorum's.
+        assert_eq!(result.bid, dec!(0.91));
+        assert_eq!(result.ask, dec!(0.93));
+        assert_eq!(aggregated.last_rejected(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_aggregated_provider_fails_closed_when_outliers_break_quorum() {
+        let p1 = Arc::new(MockRateProvider::new("p1"));
+        let glitched = Arc::new(MockRateProvider::new("glitched"));
+
+        p1.set_rate(make_test_rate("USD", "EUR", dec!(0.91), dec!(0.93)));
+        glitched.set_rate(make_test_rate("USD", "EUR", dec!(5.00), dec!(5.02)));
+
+        // With only two feeds, neither is an outlier by MAD (both tie for
+        // the single deviation), so both survive and the whole-batch
+        // deviation check should fail the batch instead.
+        let aggregated = AggregatedRateProvider::new(vec![p1, glitched])
+            .with_min_providers(2)
+            .with_max_deviation(500);
+
+        let pair = CurrencyPair::new(Currency::usd(), Currency::eur());
+        assert!(aggregated.get_rate(&pair).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_with_mad_threshold_is_configurable() {
+        let p1 = Arc::new(MockRateProvider::new("p1"));
+        let p2 = Arc::new(MockRateProvider::new("p2"));
+        let p3 = Arc::new(MockRateProvider::new("p3"));
+        let slightly_off = Arc::new(MockRateProvider::new("slightly_off"));
+
+        p1.set_rate(make_test_rate("USD", "EUR", dec!(0.90), dec!(0.92)));
+        p2.set_rate(make_test_rate("USD", "EUR", dec!(0.91), dec!(0.93)));
+        p3.set_rate(make_test_rate("USD", "EUR", dec!(0.92), dec!(0.94)));
+        slightly_off.set_rate(make_test_rate("USD", "EUR", dec!(0.95), dec!(0.97)));
+
+        // A tight threshold rejects even a modest outlier while still
+        // tolerating the tight cluster's own small deviations.
+        let aggregated = AggregatedRateProvider::new(vec![p1, p2, p3, slightly_off])
+            .with_mad_threshold(dec!(2.0));
+
+        let pair = CurrencyPair::new(Currency::usd(), Currency::eur());
+        aggregated.get_rate(&pair).await.unwrap();
+
+        assert_eq!(aggregated.last_rejected(), 1);
     }
 }
@@ -4,12 +4,30 @@ use atomicsettle_common::FxRate;
 use chrono::{DateTime, Duration, Utc};
 use dashmap::DashMap;
 use serde::{Deserialize, Serialize};
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
+use std::time::Duration as StdDuration;
+use tokio::task::JoinHandle;
 use tracing::debug;
 use uuid::Uuid;
 
+use crate::accrual::{AccrualRate, RateDifferential};
 use crate::error::{FxError, FxResult};
 
+/// Decay curve for a [`RateLock`]'s Dutch-auction holding premium: how
+/// `premium_bps` grows from `premium_floor_bps` toward `premium_ceiling_bps`
+/// as the lock's elapsed lifetime fraction grows from `0` to `1`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PremiumCurve {
+    /// Premium grows proportionally to elapsed-time fraction.
+    Linear,
+    /// Premium grows with the square of elapsed-time fraction, so it
+    /// stays near the floor for most of the lock's life and rises sharply
+    /// near expiry.
+    Exponential,
+}
+
 /// A locked FX rate that can be used for a guaranteed conversion.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RateLock {
@@ -25,11 +43,35 @@ pub struct RateLock {
     pub participant_id: String,
     /// Whether the lock has been used.
     pub used: bool,
+    /// Holding-premium floor in basis points, charged at creation.
+    pub premium_floor_bps: u32,
+    /// Holding-premium ceiling in basis points, charged at expiry.
+    pub premium_ceiling_bps: u32,
+    /// Curve the premium decays along between floor and ceiling.
+    pub premium_curve: PremiumCurve,
 }
 
 impl RateLock {
-    /// Create a new rate lock.
+    /// Create a new rate lock with no holding premium (floor and ceiling
+    /// both zero). Participants who want free optionality still go
+    /// through [`RateLockManager::create_lock`], which applies the
+    /// manager's configured premium schedule instead.
     pub fn new(rate: FxRate, duration: Duration, participant_id: String) -> Self {
+        Self::with_premium_schedule(rate, duration, participant_id, 0, 0, PremiumCurve::Linear)
+    }
+
+    /// Create a new rate lock with an explicit Dutch-auction premium
+    /// schedule: `premium_bps` starts at `premium_floor_bps` and grows
+    /// toward `premium_ceiling_bps` along `premium_curve` as the lock
+    /// approaches `expires_at`.
+    pub fn with_premium_schedule(
+        rate: FxRate,
+        duration: Duration,
+        participant_id: String,
+        premium_floor_bps: u32,
+        premium_ceiling_bps: u32,
+        premium_curve: PremiumCurve,
+    ) -> Self {
         let now = Utc::now();
         Self {
             id: Uuid::now_v7(),
@@ -38,6 +80,9 @@ impl RateLock {
             expires_at: now + duration,
             participant_id,
             used: false,
+            premium_floor_bps,
+            premium_ceiling_bps,
+            premium_curve,
         }
     }
 
@@ -56,6 +101,35 @@ impl RateLock {
         }
     }
 
+    /// Current Dutch-auction holding premium in basis points: `premium_floor_bps`
+    /// at creation, growing along `premium_curve` to `premium_ceiling_bps`
+    /// by `expires_at`. Monotonic in elapsed time and clamped to the
+    /// lock's lifetime, so a lock queried after expiry still reports exactly
+    /// the ceiling rather than extrapolating past it.
+    pub fn premium_bps(&self) -> u32 {
+        let total_ms = self
+            .expires_at
+            .signed_duration_since(self.created_at)
+            .num_milliseconds()
+            .max(1);
+        let elapsed_ms = Utc::now()
+            .signed_duration_since(self.created_at)
+            .num_milliseconds()
+            .clamp(0, total_ms);
+
+        let fraction = elapsed_ms as f64 / total_ms as f64;
+        let curved_fraction = match self.premium_curve {
+            PremiumCurve::Linear => fraction,
+            PremiumCurve::Exponential => fraction * fraction,
+        };
+
+        let span = self
+            .premium_ceiling_bps
+            .saturating_sub(self.premium_floor_bps) as f64;
+
+        self.premium_floor_bps + (span * curved_fraction).round() as u32
+    }
+
     /// Mark the lock as used.
     pub fn mark_used(&mut self) {
         self.used = true;
@@ -71,6 +145,13 @@ pub struct RateLockConfig {
     pub max_duration: Duration,
     /// Maximum locks per participant.
     pub max_locks_per_participant: usize,
+    /// Holding-premium floor in basis points, charged at lock creation.
+    pub premium_floor_bps: u32,
+    /// Holding-premium ceiling in basis points, charged once a lock is
+    /// used right at expiry.
+    pub premium_ceiling_bps: u32,
+    /// Curve the premium decays along between floor and ceiling.
+    pub premium_curve: PremiumCurve,
 }
 
 impl Default for RateLockConfig {
@@ -79,6 +160,9 @@ impl Default for RateLockConfig {
             default_duration: Duration::seconds(30),
             max_duration: Duration::minutes(5),
             max_locks_per_participant: 100,
+            premium_floor_bps: 0,
+            premium_ceiling_bps: 0,
+            premium_curve: PremiumCurve::Linear,
         }
     }
 }
@@ -88,6 +172,9 @@ pub struct RateLockManager {
     locks: DashMap<Uuid, RateLock>,
     participant_locks: DashMap<String, Vec<Uuid>>,
     config: RateLockConfig,
+    /// Total locks reclaimed by `cleanup_expired` and the epoch collector,
+    /// across the manager's lifetime.
+    reclaimed_total: AtomicUsize,
 }
 
 impl RateLockManager {
@@ -102,6 +189,7 @@ impl RateLockManager {
             locks: DashMap::new(),
             participant_locks: DashMap::new(),
             config,
+            reclaimed_total: AtomicUsize::new(0),
         }
     }
 
@@ -134,8 +222,15 @@ impl RateLockManager {
             lock_duration
         };
 
-        // Create lock
-        let lock = RateLock::new(rate, lock_duration, participant_id.clone());
+        // Create lock, carrying the manager's configured premium schedule
+        let lock = RateLock::with_premium_schedule(
+            rate,
+            lock_duration,
+            participant_id.clone(),
+            self.config.premium_floor_bps,
+            self.config.premium_ceiling_bps,
+            self.config.premium_curve,
+        );
         let lock_id = lock.id;
 
         debug!(
@@ -157,13 +252,34 @@ impl RateLockManager {
         Ok(lock)
     }
 
+    /// Create a rate lock whose stored rate is a forward, not the spot:
+    /// accrues `rate` forward over `duration` per covered interest parity
+    /// using `rate_differential` (see [`AccrualRate`]), then locks the
+    /// resulting forward rate through the same path [`Self::create_lock`]
+    /// would the spot. Guaranteed conversions over the multi-minute
+    /// horizons `RateLockConfig::max_duration` allows should reflect the
+    /// time value of money rather than freeze the instantaneous rate.
+    pub fn create_forward_lock(
+        &self,
+        rate: FxRate,
+        duration: Duration,
+        participant_id: String,
+        rate_differential: RateDifferential,
+    ) -> FxResult<RateLock> {
+        let accrual = AccrualRate::accrue(&rate, duration, rate_differential)?;
+        let forward = accrual.forward_rate(duration.num_seconds().max(1))?;
+        self.create_lock(forward, Some(duration), participant_id)
+    }
+
     /// Get a rate lock by ID.
     pub fn get_lock(&self, lock_id: Uuid) -> Option<RateLock> {
         self.locks.get(&lock_id).map(|r| r.clone())
     }
 
-    /// Use a rate lock (marks it as used).
-    pub fn use_lock(&self, lock_id: Uuid) -> FxResult<RateLock> {
+    /// Use a rate lock (marks it as used), returning the locked rate
+    /// alongside the Dutch-auction holding premium accrued since it was
+    /// created, for the settlement layer to debit.
+    pub fn use_lock(&self, lock_id: Uuid) -> FxResult<LockUsage> {
         let mut lock = self
             .locks
             .get_mut(&lock_id)
@@ -180,10 +296,14 @@ impl RateLockManager {
             return Err(FxError::RateLockExpired(lock_id.to_string()));
         }
 
+        let premium_bps = lock.premium_bps();
         lock.mark_used();
-        debug!(lock_id = %lock_id, "Rate lock used");
+        debug!(lock_id = %lock_id, premium_bps, "Rate lock used");
 
-        Ok(lock.clone())
+        Ok(LockUsage {
+            lock: lock.clone(),
+            premium_bps,
+        })
     }
 
     /// Cancel a rate lock.
@@ -212,24 +332,74 @@ impl RateLockManager {
         Ok(())
     }
 
-    /// Clean up expired locks.
+    /// Clean up expired locks with a full scan of the lock map. For
+    /// high lock counts, prefer [`Self::start_collector`], which amortizes
+    /// this cost across ticks instead of scanning everything at once.
     pub fn cleanup_expired(&self) {
+        let reclaimed = self.sweep(|_| true);
+        self.reclaimed_total.fetch_add(reclaimed, Ordering::Relaxed);
+    }
+
+    /// Remove every expired lock whose ID satisfies `in_scope`, cleaning up
+    /// participant tracking alongside it. Returns the number reclaimed.
+    /// Shared by the full-scan [`Self::cleanup_expired`] and the
+    /// per-bucket epoch collector.
+    fn sweep(&self, in_scope: impl Fn(&Uuid) -> bool) -> usize {
         let expired: Vec<Uuid> = self
             .locks
             .iter()
-            .filter(|entry| !entry.value().is_valid())
+            .filter(|entry| in_scope(entry.key()) && !entry.value().is_valid())
             .map(|entry| *entry.key())
             .collect();
 
-        for lock_id in expired {
-            if let Some((_, lock)) = self.locks.remove(&lock_id) {
+        for lock_id in &expired {
+            if let Some((_, lock)) = self.locks.remove(lock_id) {
                 // Remove from participant tracking
                 if let Some(mut locks) = self.participant_locks.get_mut(&lock.participant_id) {
-                    locks.retain(|id| *id != lock_id);
+                    locks.retain(|id| id != lock_id);
                 }
                 debug!(lock_id = %lock_id, "Expired rate lock cleaned up");
             }
         }
+
+        expired.len()
+    }
+
+    /// Start a background sweeper that amortizes cleanup cost, modeled on
+    /// Solana-style epoch rent collection: the lock keyspace is partitioned
+    /// into `buckets` by a hash of each lock's `Uuid`, an epoch counter
+    /// advances once per `interval` tick, and each tick sweeps only the
+    /// bucket due this epoch instead of the whole map. This bounds both
+    /// per-tick work (roughly `len / buckets`) and the staleness of any
+    /// expired lock (at most `buckets` ticks). Returns a [`CollectorHandle`]
+    /// that stops the task when dropped.
+    pub fn start_collector(self: &Arc<Self>, interval: StdDuration, buckets: u64) -> CollectorHandle {
+        assert!(buckets > 0, "epoch collector needs at least one bucket");
+
+        let manager = Arc::clone(self);
+        let handle = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            let mut epoch: u64 = 0;
+
+            loop {
+                ticker.tick().await;
+                let bucket = epoch % buckets;
+                epoch = epoch.wrapping_add(1);
+
+                let reclaimed = manager.sweep(|id| bucket_of(id, buckets) == bucket);
+                manager
+                    .reclaimed_total
+                    .fetch_add(reclaimed, Ordering::Relaxed);
+
+                if reclaimed > 0 {
+                    debug!(bucket, reclaimed, "Epoch collector swept bucket");
+                }
+            }
+        });
+
+        CollectorHandle {
+            handle: Some(handle),
+        }
     }
 
     /// Get all locks for a participant.
@@ -257,6 +427,7 @@ impl RateLockManager {
             valid_locks: valid,
             expired_locks: total - valid,
             used_locks: used,
+            reclaimed_total: self.reclaimed_total.load(Ordering::Relaxed),
         }
     }
 }
@@ -267,6 +438,15 @@ impl Default for RateLockManager {
     }
 }
 
+/// The result of successfully using a [`RateLock`]: the lock itself (now
+/// marked used) plus the Dutch-auction holding premium, in basis points,
+/// accrued between its creation and this use.
+#[derive(Debug, Clone)]
+pub struct LockUsage {
+    pub lock: RateLock,
+    pub premium_bps: u32,
+}
+
 /// Rate lock statistics.
 #[derive(Debug, Clone)]
 pub struct RateLockStats {
@@ -274,11 +454,39 @@ pub struct RateLockStats {
     pub valid_locks: usize,
     pub expired_locks: usize,
     pub used_locks: usize,
+    /// Locks reclaimed over the manager's lifetime by `cleanup_expired`
+    /// and the epoch collector combined.
+    pub reclaimed_total: usize,
 }
 
 /// Shared rate lock manager.
 pub type SharedRateLockManager = Arc<RateLockManager>;
 
+/// Which bucket of `buckets` a lock's keyspace partition falls into, by
+/// hashing its `Uuid`. Used by [`RateLockManager::start_collector`] to
+/// assign each lock to exactly one epoch's sweep.
+fn bucket_of(id: &Uuid, buckets: u64) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    id.hash(&mut hasher);
+    hasher.finish() % buckets
+}
+
+/// Handle to a running [`RateLockManager::start_collector`] task. Stops
+/// the sweeper when dropped: `Drop` can't await the task's current tick
+/// finishing, so this aborts it outright rather than leaving it running
+/// detached.
+pub struct CollectorHandle {
+    handle: Option<JoinHandle<()>>,
+}
+
+impl Drop for CollectorHandle {
+    fn drop(&mut self) {
+        if let Some(handle) = self.handle.take() {
+            handle.abort();
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -319,7 +527,7 @@ mod tests {
             .unwrap();
 
         let used_lock = manager.use_lock(lock.id).unwrap();
-        assert!(used_lock.used);
+        assert!(used_lock.lock.used);
 
         // Can't use again
         assert!(manager.use_lock(lock.id).is_err());
@@ -344,6 +552,51 @@ mod tests {
         assert!(manager.get_lock(lock.id).is_none());
     }
 
+    #[test]
+    fn test_create_forward_lock_adjusts_rate() {
+        let manager = RateLockManager::new();
+        let rate = make_test_rate();
+        let differential = RateDifferential {
+            base: dec!(0.0),
+            quote: dec!(0.10),
+        };
+
+        let lock = manager
+            .create_forward_lock(
+                rate.clone(),
+                Duration::days(30),
+                "BANK_A".to_string(),
+                differential,
+            )
+            .unwrap();
+
+        assert!(lock.rate.bid > rate.bid);
+        assert!(lock.rate.ask > rate.ask);
+        assert!(lock.rate.bid <= lock.rate.ask);
+    }
+
+    #[test]
+    fn test_create_forward_lock_zero_periods_equals_spot() {
+        let manager = RateLockManager::new();
+        let rate = make_test_rate();
+        let differential = RateDifferential {
+            base: dec!(0.01),
+            quote: dec!(0.05),
+        };
+
+        let lock = manager
+            .create_forward_lock(
+                rate.clone(),
+                Duration::hours(12),
+                "BANK_A".to_string(),
+                differential,
+            )
+            .unwrap();
+
+        assert_eq!(lock.rate.bid, rate.bid);
+        assert_eq!(lock.rate.ask, rate.ask);
+    }
+
     #[test]
     fn test_participant_lock_limit() {
         let config = RateLockConfig {
@@ -369,4 +622,124 @@ mod tests {
             .create_lock(make_test_rate(), None, "BANK_B".to_string())
             .is_ok());
     }
+
+    #[test]
+    fn test_cleanup_expired_reclaims_and_counts() {
+        let manager = RateLockManager::new();
+        manager
+            .create_lock(
+                make_test_rate(),
+                Some(Duration::milliseconds(1)),
+                "BANK_A".to_string(),
+            )
+            .unwrap();
+
+        std::thread::sleep(StdDuration::from_millis(20));
+        manager.cleanup_expired();
+
+        let stats = manager.stats();
+        assert_eq!(stats.total_locks, 0);
+        assert_eq!(stats.reclaimed_total, 1);
+    }
+
+    #[tokio::test]
+    async fn test_start_collector_reclaims_expired_locks() {
+        let manager = Arc::new(RateLockManager::new());
+        manager
+            .create_lock(
+                make_test_rate(),
+                Some(Duration::milliseconds(1)),
+                "BANK_A".to_string(),
+            )
+            .unwrap();
+
+        // A single bucket means every tick sweeps the whole (tiny) map.
+        let _collector = manager.start_collector(StdDuration::from_millis(5), 1);
+        tokio::time::sleep(StdDuration::from_millis(50)).await;
+
+        let stats = manager.stats();
+        assert_eq!(stats.total_locks, 0);
+        assert!(stats.reclaimed_total >= 1);
+    }
+
+    #[test]
+    fn test_premium_equals_floor_at_creation() {
+        let config = RateLockConfig {
+            premium_floor_bps: 5,
+            premium_ceiling_bps: 50,
+            ..Default::default()
+        };
+        let manager = RateLockManager::with_config(config);
+
+        let lock = manager
+            .create_lock(make_test_rate(), None, "BANK_A".to_string())
+            .unwrap();
+
+        assert_eq!(lock.premium_bps(), 5);
+    }
+
+    #[test]
+    fn test_premium_is_monotonic_in_elapsed_time() {
+        let config = RateLockConfig {
+            premium_floor_bps: 0,
+            premium_ceiling_bps: 100,
+            premium_curve: PremiumCurve::Linear,
+            ..Default::default()
+        };
+        let manager = RateLockManager::with_config(config);
+
+        let lock = manager
+            .create_lock(
+                make_test_rate(),
+                Some(Duration::milliseconds(40)),
+                "BANK_A".to_string(),
+            )
+            .unwrap();
+
+        let early = lock.premium_bps();
+        std::thread::sleep(StdDuration::from_millis(20));
+        let later = lock.premium_bps();
+
+        assert!(later >= early);
+        assert!(later <= 100);
+    }
+
+    #[test]
+    fn test_premium_caps_at_ceiling_past_expiry() {
+        let config = RateLockConfig {
+            premium_floor_bps: 10,
+            premium_ceiling_bps: 80,
+            ..Default::default()
+        };
+        let manager = RateLockManager::with_config(config);
+
+        let lock = manager
+            .create_lock(
+                make_test_rate(),
+                Some(Duration::milliseconds(1)),
+                "BANK_A".to_string(),
+            )
+            .unwrap();
+
+        std::thread::sleep(StdDuration::from_millis(20));
+        assert_eq!(lock.premium_bps(), 80);
+    }
+
+    #[test]
+    fn test_use_lock_returns_accrued_premium() {
+        let config = RateLockConfig {
+            premium_floor_bps: 10,
+            premium_ceiling_bps: 10,
+            ..Default::default()
+        };
+        let manager = RateLockManager::with_config(config);
+
+        let lock = manager
+            .create_lock(make_test_rate(), None, "BANK_A".to_string())
+            .unwrap();
+
+        let used = manager.use_lock(lock.id).unwrap();
+        assert_eq!(used.premium_bps, 10);
+        assert!(used.lock.used);
+    }
 }
@@ -0,0 +1,245 @@
+//! Cross-rate triangulation through a pivot currency.
+//!
+//! Most rate sources only quote a handful of direct pairs. A
+//! [`TriangulatingRateProvider`] wraps another [`RateProvider`] and fills
+//! in the gaps: for a pair `A/B` with no direct quote, it searches its
+//! configured pivot currencies for a `C` such that `A/C` and `C/B` (or
+//! their inverses) are both available, and composes the cross rate from
+//! those two legs.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use atomicsettle_common::{Currency, CurrencyPair, FxRate};
+use rust_decimal::Decimal;
+
+use crate::error::{FxError, FxResult};
+use crate::provider::RateProvider;
+
+/// Below this magnitude a rate is treated as zero for the purposes of
+/// inversion -- dividing by it would blow up into a meaningless number
+/// rather than a genuine rate.
+const NEAR_ZERO: Decimal = Decimal::from_parts(1, 0, 0, false, 9);
+
+fn is_near_zero(value: Decimal) -> bool {
+    value.abs() < NEAR_ZERO
+}
+
+/// Wraps an inner [`RateProvider`], deriving cross rates through
+/// configured pivot currencies when the inner provider has no direct
+/// quote for the requested pair.
+pub struct TriangulatingRateProvider {
+    inner: Arc<dyn RateProvider>,
+    pivots: Vec<Currency>,
+}
+
+impl TriangulatingRateProvider {
+    /// Wrap `inner`, trying each of `pivots` in order when a pair has no
+    /// direct quote.
+    pub fn new(inner: Arc<dyn RateProvider>, pivots: Vec<Currency>) -> Self {
+        Self { inner, pivots }
+    }
+
+    /// Get the `base/quote` leg from the inner provider, inverting a
+    /// `quote/base` quote if that's what's actually available. Errs if
+    /// neither direction is quoted, or if inverting would divide by a
+    /// zero/near-zero rate.
+    async fn leg(&self, base: &Currency, quote: &Currency) -> FxResult<FxRate> {
+        let direct = CurrencyPair::new(base.clone(), quote.clone());
+        if let Ok(rate) = self.inner.get_rate(&direct).await {
+            return Ok(rate);
+        }
+
+        let inverse_pair = CurrencyPair::new(quote.clone(), base.clone());
+        let inverse = self.inner.get_rate(&inverse_pair).await?;
+
+        if is_near_zero(inverse.bid) || is_near_zero(inverse.ask) {
+            return Err(FxError::ProviderError(format!(
+                "cannot invert near-zero rate for {inverse_pair}"
+            )));
+        }
+
+        Ok(FxRate {
+            pair: direct,
+            bid: Decimal::ONE / inverse.ask,
+            ask: Decimal::ONE / inverse.bid,
+            mid: Decimal::ONE / inverse.mid,
+            quoted_at: inverse.quoted_at,
+            valid_until: inverse.valid_until,
+            source: format!("INVERTED:{}", inverse.source),
+        })
+    }
+}
+
+#[async_trait]
+impl RateProvider for TriangulatingRateProvider {
+    fn name(&self) -> &str {
+        "TRIANGULATING"
+    }
+
+    async fn get_rate(&self, pair: &CurrencyPair) -> FxResult<FxRate> {
+        if let Ok(rate) = self.inner.get_rate(pair).await {
+            return Ok(rate);
+        }
+
+        for pivot in &self.pivots {
+            if *pivot == pair.base || *pivot == pair.quote {
+                continue;
+            }
+
+            let (leg_a, leg_b) = match (
+                self.leg(&pair.base, pivot).await,
+                self.leg(pivot, &pair.quote).await,
+            ) {
+                (Ok(a), Ok(b)) => (a, b),
+                _ => continue,
+            };
+
+            let bid = leg_a
+                .bid
+                .checked_mul(leg_b.bid)
+                .ok_or_else(|| FxError::ProviderError(format!("overflow triangulating {pair}")))?;
+            let ask = leg_a
+                .ask
+                .checked_mul(leg_b.ask)
+                .ok_or_else(|| FxError::ProviderError(format!("overflow triangulating {pair}")))?;
+
+            // The derived rate can't outlive the legs it was built from.
+            let valid_until = leg_a.valid_until.min(leg_b.valid_until);
+
+            return Ok(FxRate {
+                pair: pair.clone(),
+                bid,
+                ask,
+                mid: (bid + ask) / Decimal::TWO,
+                quoted_at: chrono::Utc::now(),
+                valid_until,
+                source: format!(
+                    "TRIANGULATED:{}-{}-{}",
+                    pair.base.code(),
+                    pivot.code(),
+                    pair.quote.code()
+                ),
+            });
+        }
+
+        Err(FxError::RateNotAvailable(pair.clone()))
+    }
+
+    fn supports_pair(&self, pair: &CurrencyPair) -> bool {
+        if self.inner.supports_pair(pair) {
+            return true;
+        }
+
+        self.pivots.iter().any(|pivot| {
+            if *pivot == pair.base || *pivot == pair.quote {
+                return false;
+            }
+
+            let leg_a = self.inner.supports_pair(&CurrencyPair::new(pair.base.clone(), pivot.clone()))
+                || self
+                    .inner
+                    .supports_pair(&CurrencyPair::new(pivot.clone(), pair.base.clone()));
+            let leg_b = self.inner.supports_pair(&CurrencyPair::new(pivot.clone(), pair.quote.clone()))
+                || self
+                    .inner
+                    .supports_pair(&CurrencyPair::new(pair.quote.clone(), pivot.clone()));
+
+            leg_a && leg_b
+        })
+    }
+
+    fn supported_pairs(&self) -> Vec<CurrencyPair> {
+        self.inner.supported_pairs()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::MockRateProvider;
+    use rust_decimal_macros::dec;
+
+    fn make_test_rate(base: &str, quote: &str, bid: Decimal, ask: Decimal) -> FxRate {
+        FxRate::new(
+            CurrencyPair::new(Currency::new(base), Currency::new(quote)),
+            bid,
+            ask,
+            30,
+            "TEST",
+        )
+    }
+
+    #[tokio::test]
+    async fn test_triangulates_through_pivot() {
+        let provider = Arc::new(MockRateProvider::new("test"));
+        provider.set_rate(make_test_rate("USD", "EUR", dec!(0.90), dec!(0.92)));
+        provider.set_rate(make_test_rate("EUR", "JPY", dec!(160.0), dec!(161.0)));
+
+        let triangulating = TriangulatingRateProvider::new(provider, vec![Currency::eur()]);
+
+        let pair = CurrencyPair::new(Currency::usd(), Currency::new("JPY"));
+        let rate = triangulating.get_rate(&pair).await.unwrap();
+
+        assert_eq!(rate.bid, dec!(0.90) * dec!(160.0));
+        assert_eq!(rate.ask, dec!(0.92) * dec!(161.0));
+        assert!(rate.source.starts_with("TRIANGULATED:"));
+    }
+
+    #[tokio::test]
+    async fn test_triangulates_using_inverse_leg() {
+        let provider = Arc::new(MockRateProvider::new("test"));
+        provider.set_rate(make_test_rate("USD", "EUR", dec!(0.90), dec!(0.92)));
+        // Only JPY/EUR is quoted, not EUR/JPY -- the pivot leg must invert.
+        provider.set_rate(make_test_rate("JPY", "EUR", dec!(0.0062), dec!(0.0063)));
+
+        let triangulating = TriangulatingRateProvider::new(provider, vec![Currency::eur()]);
+
+        let pair = CurrencyPair::new(Currency::usd(), Currency::new("JPY"));
+        let rate = triangulating.get_rate(&pair).await.unwrap();
+
+        let expected_bid = dec!(0.90) * (Decimal::ONE / dec!(0.0063));
+        let expected_ask = dec!(0.92) * (Decimal::ONE / dec!(0.0062));
+        assert_eq!(rate.bid, expected_bid);
+        assert_eq!(rate.ask, expected_ask);
+    }
+
+    #[tokio::test]
+    async fn test_uses_direct_quote_when_available() {
+        let provider = Arc::new(MockRateProvider::new("test"));
+        provider.set_rate(make_test_rate("USD", "JPY", dec!(149.0), dec!(150.0)));
+        provider.set_rate(make_test_rate("USD", "EUR", dec!(0.90), dec!(0.92)));
+        provider.set_rate(make_test_rate("EUR", "JPY", dec!(160.0), dec!(161.0)));
+
+        let triangulating = TriangulatingRateProvider::new(provider, vec![Currency::eur()]);
+
+        let pair = CurrencyPair::new(Currency::usd(), Currency::new("JPY"));
+        let rate = triangulating.get_rate(&pair).await.unwrap();
+
+        assert_eq!(rate.source, "TEST");
+        assert_eq!(rate.bid, dec!(149.0));
+    }
+
+    #[tokio::test]
+    async fn test_no_path_fails() {
+        let provider = Arc::new(MockRateProvider::new("test"));
+        provider.set_rate(make_test_rate("USD", "EUR", dec!(0.90), dec!(0.92)));
+
+        let triangulating = TriangulatingRateProvider::new(provider, vec![Currency::eur()]);
+
+        let pair = CurrencyPair::new(Currency::usd(), Currency::new("JPY"));
+        assert!(triangulating.get_rate(&pair).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_rejects_near_zero_inversion_denominator() {
+        let provider = Arc::new(MockRateProvider::new("test"));
+        provider.set_rate(make_test_rate("USD", "EUR", dec!(0.90), dec!(0.92)));
+        provider.set_rate(make_test_rate("JPY", "EUR", Decimal::ZERO, Decimal::ZERO));
+
+        let triangulating = TriangulatingRateProvider::new(provider, vec![Currency::eur()]);
+
+        let pair = CurrencyPair::new(Currency::usd(), Currency::new("JPY"));
+        assert!(triangulating.get_rate(&pair).await.is_err());
+    }
+}
@@ -4,6 +4,8 @@ use atomicsettle_common::{AccountId, Currency, ParticipantId};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::error::{LedgerError, Result};
+
 /// Account status.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum AccountStatus {
@@ -61,21 +63,91 @@ impl Account {
         self.status == AccountStatus::Active
     }
 
-    /// Freeze the account.
-    pub fn freeze(&mut self) {
+    /// Validate this account's own invariants: its `currency` matches the
+    /// one encoded in its `AccountId`. Status is always one of the three
+    /// valid [`AccountStatus`] variants by construction; transition
+    /// legality is enforced by `freeze`/`unfreeze`/`close` themselves.
+    pub fn validate(&self) -> Result<()> {
+        if self.currency.code() != self.id.currency {
+            return Err(LedgerError::StateCorrupt {
+                account: self.id.clone(),
+                detail: format!(
+                    "account currency {} does not match id-encoded currency {}",
+                    self.currency.code(),
+                    self.id.currency
+                ),
+            });
+        }
+        Ok(())
+    }
+
+    /// Freeze the account. Errs if the account is closed, since closed is
+    /// terminal.
+    pub fn freeze(&mut self) -> Result<()> {
+        self.ensure_not_closed()?;
         self.status = AccountStatus::Frozen;
         self.updated_at = Utc::now();
+        Ok(())
     }
 
-    /// Unfreeze the account.
-    pub fn unfreeze(&mut self) {
+    /// Unfreeze the account. Errs if the account is closed, since closed
+    /// is terminal.
+    pub fn unfreeze(&mut self) -> Result<()> {
+        self.ensure_not_closed()?;
         self.status = AccountStatus::Active;
         self.updated_at = Utc::now();
+        Ok(())
     }
 
-    /// Close the account.
-    pub fn close(&mut self) {
+    /// Close the account. Errs if the account is already closed, since
+    /// closed is terminal and cannot be re-entered.
+    pub fn close(&mut self) -> Result<()> {
+        self.ensure_not_closed()?;
         self.status = AccountStatus::Closed;
         self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    fn ensure_not_closed(&self) -> Result<()> {
+        if self.status == AccountStatus::Closed {
+            return Err(LedgerError::StateCorrupt {
+                account: self.id.clone(),
+                detail: "closed accounts are terminal and cannot change status".to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_account() -> Account {
+        Account::new(ParticipantId::new("BANK_A"), "12345", Currency::usd(), "Test Account")
+    }
+
+    #[test]
+    fn test_new_account_validates() {
+        assert!(test_account().validate().is_ok());
+    }
+
+    #[test]
+    fn test_closed_account_rejects_further_transitions() {
+        let mut account = test_account();
+        assert!(account.close().is_ok());
+
+        assert!(matches!(account.freeze(), Err(LedgerError::StateCorrupt { .. })));
+        assert!(matches!(account.unfreeze(), Err(LedgerError::StateCorrupt { .. })));
+        assert!(matches!(account.close(), Err(LedgerError::StateCorrupt { .. })));
+    }
+
+    #[test]
+    fn test_freeze_unfreeze_round_trip() {
+        let mut account = test_account();
+        assert!(account.freeze().is_ok());
+        assert!(!account.can_transact());
+        assert!(account.unfreeze().is_ok());
+        assert!(account.can_transact());
     }
 }
@@ -1,10 +1,15 @@
 //! Account balance tracking.
 
+use std::collections::HashMap;
+
 use atomicsettle_common::{AccountId, Currency};
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
+use crate::error::{LedgerError, Result};
+use crate::journal::{EntryType, JournalBatch, JournalEntry};
+
 /// Account balance at a point in time.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AccountBalance {
@@ -62,6 +67,134 @@ impl AccountBalance {
     pub fn projected(&self) -> Decimal {
         self.total() + self.pending_credits - self.pending_debits
     }
+
+    /// Validate this balance's invariants: neither `balance` nor
+    /// `locked_balance` may be negative. A negative balance means a
+    /// debit was allowed to overdraw the account somewhere upstream --
+    /// this is the defensive check that catches it instead of letting it
+    /// propagate silently.
+    pub fn validate(&self, account_id: &AccountId) -> Result<()> {
+        if self.balance < Decimal::ZERO {
+            return Err(LedgerError::StateCorrupt {
+                account: account_id.clone(),
+                detail: format!("available balance {} is negative", self.balance),
+            });
+        }
+        if self.locked_balance < Decimal::ZERO {
+            return Err(LedgerError::StateCorrupt {
+                account: account_id.clone(),
+                detail: format!("locked balance {} is negative", self.locked_balance),
+            });
+        }
+        Ok(())
+    }
+
+    /// Move `amount` from the available `balance` into `locked_balance`,
+    /// e.g. when a settlement first proposes a lock against this account.
+    /// Errors with [`LedgerError::InsufficientFunds`] if the available
+    /// balance can't cover it, rather than letting it go negative.
+    pub fn reserve(&mut self, amount: Decimal, reference: impl Into<String>) -> Result<BalanceChange> {
+        if self.balance < amount {
+            return Err(LedgerError::InsufficientFunds {
+                account: self.account_id.clone(),
+                required: amount.to_string(),
+                available: self.balance.to_string(),
+            });
+        }
+        let balance_before = self.balance;
+        self.balance -= amount;
+        self.locked_balance += amount;
+        self.updated_at = Utc::now();
+        Ok(self.record_change(BalanceChangeType::Lock, amount, balance_before, self.balance, reference.into()))
+    }
+
+    /// Move up to `amount` back from `locked_balance` into the available
+    /// `balance`, e.g. releasing a lock whose settlement didn't complete.
+    /// Saturates at `locked_balance` so a caller can't unreserve more than
+    /// is actually reserved.
+    pub fn unreserve(&mut self, amount: Decimal, reference: impl Into<String>) -> BalanceChange {
+        let released = amount.min(self.locked_balance);
+        let balance_before = self.balance;
+        self.locked_balance -= released;
+        self.balance += released;
+        self.updated_at = Utc::now();
+        self.record_change(BalanceChangeType::Unlock, released, balance_before, self.balance, reference.into())
+    }
+
+    /// Destroy up to `amount` of reserved funds outright, e.g. slashing a
+    /// counterparty that failed to honor a locked settlement. Saturates at
+    /// `locked_balance` the same way [`Self::unreserve`] does.
+    pub fn slash_reserved(&mut self, amount: Decimal, reference: impl Into<String>) -> BalanceChange {
+        let slashed = amount.min(self.locked_balance);
+        let balance_before = self.locked_balance;
+        self.locked_balance -= slashed;
+        self.updated_at = Utc::now();
+        self.record_change(BalanceChangeType::ConsumeLocked, slashed, balance_before, self.locked_balance, reference.into())
+    }
+
+    /// Move up to `amount` of this account's reserved funds into `to`,
+    /// landing in `to`'s free or locked balance per `status` -- e.g.
+    /// finalizing a settlement by moving the sender's locked funds into
+    /// the receiver's available balance. Saturates at this account's
+    /// `locked_balance`. Returns both sides' change records, mirroring
+    /// the debit/credit pair a `JournalBatch` leg would produce.
+    pub fn repatriate_reserved(
+        &mut self,
+        to: &mut AccountBalance,
+        amount: Decimal,
+        status: BalanceStatus,
+        reference: impl Into<String>,
+    ) -> (BalanceChange, BalanceChange) {
+        let reference = reference.into();
+        let moved = amount.min(self.locked_balance);
+
+        let from_before = self.locked_balance;
+        self.locked_balance -= moved;
+        self.updated_at = Utc::now();
+        let from_change = self.record_change(
+            BalanceChangeType::ConsumeLocked,
+            moved,
+            from_before,
+            self.locked_balance,
+            reference.clone(),
+        );
+
+        let (to_change_type, to_before) = match status {
+            BalanceStatus::Free => (BalanceChangeType::Credit, to.balance),
+            BalanceStatus::Reserved => (BalanceChangeType::Lock, to.locked_balance),
+        };
+        match status {
+            BalanceStatus::Free => to.balance += moved,
+            BalanceStatus::Reserved => to.locked_balance += moved,
+        }
+        to.updated_at = Utc::now();
+        let to_after = match status {
+            BalanceStatus::Free => to.balance,
+            BalanceStatus::Reserved => to.locked_balance,
+        };
+        let to_change = to.record_change(to_change_type, moved, to_before, to_after, reference);
+
+        (from_change, to_change)
+    }
+
+    fn record_change(
+        &self,
+        change_type: BalanceChangeType,
+        amount: Decimal,
+        balance_before: Decimal,
+        balance_after: Decimal,
+        reference: String,
+    ) -> BalanceChange {
+        BalanceChange {
+            account_id: self.account_id.clone(),
+            change_type,
+            amount,
+            balance_before,
+            balance_after,
+            reference,
+            timestamp: Utc::now(),
+        }
+    }
 }
 
 /// Balance change event.
@@ -98,6 +231,182 @@ pub enum BalanceChangeType {
     ConsumeLocked,
 }
 
+/// Which of a destination account's balances a [`AccountBalance::repatriate_reserved`]
+/// transfer lands in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BalanceStatus {
+    /// Land in the destination's available `balance`.
+    Free,
+    /// Land in the destination's `locked_balance`, still reserved.
+    Reserved,
+}
+
+/// Snapshot-stack checkpointing over a `HashMap<AccountId, AccountBalance>`,
+/// modeled on Solana's bank checkpoint stack: [`Self::checkpoint`] pushes
+/// the current state onto the stack, [`Self::rollback`] discards every
+/// mutation back to the last pushed snapshot, and [`Self::squash`] commits
+/// the working state by dropping that snapshot. [`Self::apply_batch`] uses this to
+/// give a multi-leg `JournalBatch` all-or-nothing posting semantics: if a
+/// later leg fails on insufficient funds or a currency mismatch, every
+/// earlier leg in the same batch is rolled back rather than left
+/// half-applied.
+pub struct CheckpointedBalances {
+    current: HashMap<AccountId, AccountBalance>,
+    snapshots: Vec<HashMap<AccountId, AccountBalance>>,
+}
+
+impl CheckpointedBalances {
+    /// Create an empty checkpointed balance store.
+    pub fn new() -> Self {
+        Self {
+            current: HashMap::new(),
+            snapshots: Vec::new(),
+        }
+    }
+
+    /// Wrap an existing balance map, e.g. one rebuilt from a journal.
+    pub fn from_map(current: HashMap<AccountId, AccountBalance>) -> Self {
+        Self {
+            current,
+            snapshots: Vec::new(),
+        }
+    }
+
+    /// Read `account_id`'s current balance, if tracked.
+    pub fn get(&self, account_id: &AccountId) -> Option<&AccountBalance> {
+        self.current.get(account_id)
+    }
+
+    /// Consume this store, returning its current (committed) balance map.
+    pub fn into_map(self) -> HashMap<AccountId, AccountBalance> {
+        self.current
+    }
+
+    /// Push the current state onto the snapshot stack.
+    pub fn checkpoint(&mut self) {
+        self.snapshots.push(self.current.clone());
+    }
+
+    /// Discard every mutation since the last [`Self::checkpoint`],
+    /// restoring the state it captured. A no-op if the stack is empty.
+    pub fn rollback(&mut self) {
+        if let Some(snapshot) = self.snapshots.pop() {
+            self.current = snapshot;
+        }
+    }
+
+    /// Commit every mutation since the last [`Self::checkpoint`] by
+    /// dropping the snapshot it pushed, without restoring it. A no-op if
+    /// the stack is empty.
+    pub fn squash(&mut self) {
+        self.snapshots.pop();
+    }
+
+    /// Apply every entry in `batch` to a checkpointed working copy of the
+    /// balance store, committing only if every leg posts cleanly: first
+    /// rejects the batch outright if it isn't balanced, then rolls back
+    /// all of it if any entry's currency doesn't match its account's, or
+    /// any debit would overdraw its account.
+    pub fn apply_batch(&mut self, batch: &JournalBatch) -> Result<()> {
+        if !batch.is_balanced() {
+            return Err(LedgerError::Unbalanced {
+                settlement_id: batch.settlement_id,
+            });
+        }
+
+        self.checkpoint();
+        for entry in &batch.entries {
+            if let Err(err) = self.apply_entry(entry) {
+                self.rollback();
+                return Err(err);
+            }
+        }
+        for entry in &batch.supply_entries {
+            if let Err(err) = self.apply_supply_entry(entry) {
+                self.rollback();
+                return Err(err);
+            }
+        }
+        self.squash();
+        Ok(())
+    }
+
+    /// Apply a single mint/burn leg to the working copy: minting credits
+    /// the account, burning debits it. Issuance itself is tracked
+    /// separately by `crate::supply::SupplyLedger`; this only moves the
+    /// account-balance side.
+    fn apply_supply_entry(&mut self, entry: &crate::supply::SupplyEntry) -> Result<()> {
+        use crate::supply::SupplyEntryType;
+
+        let balance = self
+            .current
+            .entry(entry.account_id.clone())
+            .or_insert_with(|| AccountBalance::zero(entry.account_id.clone(), entry.currency.clone()));
+
+        match entry.entry_type {
+            SupplyEntryType::Mint => {
+                balance.balance += entry.amount;
+            }
+            SupplyEntryType::Burn => {
+                if balance.balance < entry.amount {
+                    return Err(LedgerError::InsufficientFunds {
+                        account: entry.account_id.clone(),
+                        required: entry.amount.to_string(),
+                        available: balance.balance.to_string(),
+                    });
+                }
+                balance.balance -= entry.amount;
+            }
+        }
+        balance.updated_at = Utc::now();
+
+        Ok(())
+    }
+
+    /// Apply a single entry to the working copy, without any rollback of
+    /// its own -- [`Self::apply_batch`] rolls back the whole batch on
+    /// failure rather than just this entry.
+    fn apply_entry(&mut self, entry: &JournalEntry) -> Result<()> {
+        if entry.account_id.currency != entry.currency.code() {
+            return Err(LedgerError::CurrencyMismatch {
+                account: entry.account_id.clone(),
+                expected: entry.account_id.currency.clone(),
+                actual: entry.currency.code().to_string(),
+            });
+        }
+
+        let balance = self
+            .current
+            .entry(entry.account_id.clone())
+            .or_insert_with(|| AccountBalance::zero(entry.account_id.clone(), entry.currency.clone()));
+
+        match entry.entry_type {
+            EntryType::Debit => {
+                if balance.balance < entry.amount {
+                    return Err(LedgerError::InsufficientFunds {
+                        account: entry.account_id.clone(),
+                        required: entry.amount.to_string(),
+                        available: balance.balance.to_string(),
+                    });
+                }
+                balance.balance -= entry.amount;
+            }
+            EntryType::Credit => {
+                balance.balance += entry.amount;
+            }
+        }
+        balance.updated_at = Utc::now();
+
+        Ok(())
+    }
+}
+
+impl Default for CheckpointedBalances {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -116,4 +425,189 @@ mod tests {
         assert!(balance.can_lock(Decimal::from(5000)));
         assert!(!balance.can_lock(Decimal::from(15000)));
     }
+
+    #[test]
+    fn test_validate_rejects_negative_balance() {
+        let account_id = AccountId::new(ParticipantId::new("BANK_A"), "12345", "USD");
+        let mut balance = AccountBalance::zero(account_id.clone(), Currency::usd());
+
+        assert!(balance.validate(&account_id).is_ok());
+
+        balance.balance = Decimal::from(-1);
+        assert!(matches!(
+            balance.validate(&account_id),
+            Err(LedgerError::StateCorrupt { .. })
+        ));
+    }
+
+    #[test]
+    fn test_reserve_moves_balance_to_locked() {
+        let account_id = AccountId::new(ParticipantId::new("BANK_A"), "12345", "USD");
+        let mut balance = AccountBalance::zero(account_id, Currency::usd());
+        balance.balance = Decimal::from(1000);
+
+        let change = balance.reserve(Decimal::from(400), "settlement-1").unwrap();
+
+        assert_eq!(balance.balance, Decimal::from(600));
+        assert_eq!(balance.locked_balance, Decimal::from(400));
+        assert_eq!(change.change_type, BalanceChangeType::Lock);
+        assert_eq!(change.balance_before, Decimal::from(1000));
+        assert_eq!(change.balance_after, Decimal::from(600));
+    }
+
+    #[test]
+    fn test_reserve_rejects_insufficient_balance() {
+        let account_id = AccountId::new(ParticipantId::new("BANK_A"), "12345", "USD");
+        let mut balance = AccountBalance::zero(account_id, Currency::usd());
+        balance.balance = Decimal::from(100);
+
+        let result = balance.reserve(Decimal::from(400), "settlement-1");
+
+        assert!(matches!(result, Err(LedgerError::InsufficientFunds { .. })));
+        assert_eq!(balance.balance, Decimal::from(100));
+        assert_eq!(balance.locked_balance, Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_unreserve_saturates_at_locked_balance() {
+        let account_id = AccountId::new(ParticipantId::new("BANK_A"), "12345", "USD");
+        let mut balance = AccountBalance::zero(account_id, Currency::usd());
+        balance.balance = Decimal::from(600);
+        balance.locked_balance = Decimal::from(400);
+
+        let change = balance.unreserve(Decimal::from(1000), "settlement-1");
+
+        assert_eq!(change.amount, Decimal::from(400));
+        assert_eq!(balance.balance, Decimal::from(1000));
+        assert_eq!(balance.locked_balance, Decimal::ZERO);
+        assert_eq!(change.change_type, BalanceChangeType::Unlock);
+    }
+
+    #[test]
+    fn test_slash_reserved_destroys_locked_funds() {
+        let account_id = AccountId::new(ParticipantId::new("BANK_A"), "12345", "USD");
+        let mut balance = AccountBalance::zero(account_id, Currency::usd());
+        balance.balance = Decimal::from(600);
+        balance.locked_balance = Decimal::from(400);
+
+        let change = balance.slash_reserved(Decimal::from(250), "fraud-penalty");
+
+        assert_eq!(balance.locked_balance, Decimal::from(150));
+        assert_eq!(balance.balance, Decimal::from(600));
+        assert_eq!(change.change_type, BalanceChangeType::ConsumeLocked);
+        assert_eq!(change.balance_before, Decimal::from(400));
+        assert_eq!(change.balance_after, Decimal::from(150));
+    }
+
+    #[test]
+    fn test_repatriate_reserved_to_free_balance() {
+        let account_a = AccountId::new(ParticipantId::new("BANK_A"), "12345", "USD");
+        let account_b = AccountId::new(ParticipantId::new("BANK_B"), "67890", "USD");
+        let mut from = AccountBalance::zero(account_a, Currency::usd());
+        from.locked_balance = Decimal::from(500);
+        let mut to = AccountBalance::zero(account_b, Currency::usd());
+        to.balance = Decimal::from(100);
+
+        let (from_change, to_change) =
+            from.repatriate_reserved(&mut to, Decimal::from(500), BalanceStatus::Free, "settlement-2");
+
+        assert_eq!(from.locked_balance, Decimal::ZERO);
+        assert_eq!(to.balance, Decimal::from(600));
+        assert_eq!(from_change.change_type, BalanceChangeType::ConsumeLocked);
+        assert_eq!(to_change.change_type, BalanceChangeType::Credit);
+    }
+
+    #[test]
+    fn test_repatriate_reserved_to_reserved_balance_saturates() {
+        let account_a = AccountId::new(ParticipantId::new("BANK_A"), "12345", "USD");
+        let account_b = AccountId::new(ParticipantId::new("BANK_B"), "67890", "USD");
+        let mut from = AccountBalance::zero(account_a, Currency::usd());
+        from.locked_balance = Decimal::from(300);
+        let mut to = AccountBalance::zero(account_b, Currency::usd());
+
+        let (from_change, to_change) =
+            from.repatriate_reserved(&mut to, Decimal::from(1000), BalanceStatus::Reserved, "settlement-3");
+
+        assert_eq!(from.locked_balance, Decimal::ZERO);
+        assert_eq!(to.locked_balance, Decimal::from(300));
+        assert_eq!(from_change.amount, Decimal::from(300));
+        assert_eq!(to_change.change_type, BalanceChangeType::Lock);
+    }
+
+    fn funded_store(account_id: &AccountId, amount: Decimal) -> CheckpointedBalances {
+        let mut balance = AccountBalance::zero(account_id.clone(), Currency::usd());
+        balance.balance = amount;
+        let mut map = HashMap::new();
+        map.insert(account_id.clone(), balance);
+        CheckpointedBalances::from_map(map)
+    }
+
+    #[test]
+    fn test_apply_batch_commits_balanced_batch() {
+        use atomicsettle_common::SettlementId;
+
+        let account_a = AccountId::new(ParticipantId::new("BANK_A"), "12345", "USD");
+        let account_b = AccountId::new(ParticipantId::new("BANK_B"), "67890", "USD");
+        let mut store = funded_store(&account_a, Decimal::from(10_000));
+
+        let settlement_id = SettlementId::new();
+        let mut batch = crate::journal::JournalBatch::new(settlement_id);
+        batch.add_entry(JournalEntry::debit(settlement_id, 1, account_a.clone(), Decimal::from(1000), Currency::usd()));
+        batch.add_entry(JournalEntry::credit(settlement_id, 1, account_b.clone(), Decimal::from(1000), Currency::usd()));
+
+        assert!(store.apply_batch(&batch).is_ok());
+        assert_eq!(store.get(&account_a).unwrap().balance, Decimal::from(9000));
+        assert_eq!(store.get(&account_b).unwrap().balance, Decimal::from(1000));
+    }
+
+    #[test]
+    fn test_apply_batch_rolls_back_on_insufficient_funds() {
+        use atomicsettle_common::SettlementId;
+
+        let account_a = AccountId::new(ParticipantId::new("BANK_A"), "12345", "USD");
+        let account_b = AccountId::new(ParticipantId::new("BANK_B"), "67890", "USD");
+        let mut store = funded_store(&account_a, Decimal::from(500));
+
+        let settlement_id = SettlementId::new();
+        let mut batch = crate::journal::JournalBatch::new(settlement_id);
+        batch.add_entry(JournalEntry::debit(settlement_id, 1, account_a.clone(), Decimal::from(1000), Currency::usd()));
+        batch.add_entry(JournalEntry::credit(settlement_id, 1, account_b.clone(), Decimal::from(1000), Currency::usd()));
+
+        let result = store.apply_batch(&batch);
+        assert!(matches!(result, Err(LedgerError::InsufficientFunds { .. })));
+        assert_eq!(store.get(&account_a).unwrap().balance, Decimal::from(500));
+        assert!(store.get(&account_b).is_none());
+    }
+
+    #[test]
+    fn test_apply_batch_credits_account_on_mint_leg() {
+        use crate::supply::SupplyEntry;
+        use atomicsettle_common::SettlementId;
+
+        let account_a = AccountId::new(ParticipantId::new("BANK_A"), "12345", "USD");
+        let mut store = CheckpointedBalances::new();
+
+        let settlement_id = SettlementId::new();
+        let mut batch = crate::journal::JournalBatch::new(settlement_id);
+        batch.add_supply_entry(SupplyEntry::mint(account_a.clone(), Decimal::from(1000), Currency::usd()));
+
+        assert!(store.apply_batch(&batch).is_ok());
+        assert_eq!(store.get(&account_a).unwrap().balance, Decimal::from(1000));
+    }
+
+    #[test]
+    fn test_apply_batch_rejects_unbalanced_batch_without_mutating() {
+        use atomicsettle_common::SettlementId;
+
+        let account_a = AccountId::new(ParticipantId::new("BANK_A"), "12345", "USD");
+        let mut store = funded_store(&account_a, Decimal::from(10_000));
+
+        let settlement_id = SettlementId::new();
+        let mut batch = crate::journal::JournalBatch::new(settlement_id);
+        batch.add_entry(JournalEntry::debit(settlement_id, 1, account_a.clone(), Decimal::from(1000), Currency::usd()));
+
+        let result = store.apply_batch(&batch);
+        assert!(matches!(result, Err(LedgerError::Unbalanced { .. })));
+        assert_eq!(store.get(&account_a).unwrap().balance, Decimal::from(10_000));
+    }
 }
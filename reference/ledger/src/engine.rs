@@ -1,81 +1,222 @@
 //! Core ledger engine implementation.
 
+use std::collections::HashMap;
 use std::sync::Arc;
 
+use dashmap::DashMap;
+use parking_lot::RwLock;
 use rust_decimal::Decimal;
 use tracing::{info, instrument};
+use uuid::Uuid;
 
-use atomicsettle_common::{AccountId, Currency, Money, Result, Settlement, SettlementId};
+use atomicsettle_common::{
+    AccountId, AtomicSettleError, Currency, FxMode, Money, Result, Settlement, SettlementId,
+    SettlementLeg,
+};
 
 use crate::account::Account;
 use crate::balance::AccountBalance;
+use crate::error::{LedgerError, Result as LedgerResult};
+use crate::fx::FxRateProvider;
 use crate::journal::{EntryType, JournalEntry};
+use crate::status_cache::StatusCache;
+use crate::store::PostgresLedgerStore;
 
 /// The ledger engine manages double-entry bookkeeping for settlements.
 pub struct LedgerEngine {
-    /// Database connection pool (placeholder).
-    // db: sqlx::PgPool,
+    /// Append-only, hash-chained journal, in the order entries were
+    /// appended.
+    journal: RwLock<Vec<JournalEntry>>,
+    /// Current balance per account, maintained incrementally as entries are
+    /// appended.
+    balances: DashMap<AccountId, AccountBalance>,
+    /// Durable persistence this engine writes through to, if attached via
+    /// [`Self::with_store`]. `None` keeps the engine purely in-memory, e.g.
+    /// for tests and the simulator.
+    store: Option<Arc<PostgresLedgerStore>>,
+    /// Recently recorded settlements, keyed by idempotency key, so a
+    /// retried `record_settlement` call returns the original outcome
+    /// instead of double-posting it.
+    status_cache: RwLock<StatusCache>,
+    /// Rate source `record_settlement` consults to convert a leg's amount
+    /// when its debit and credit accounts are in different currencies.
+    /// With `None`, a leg that needs coordinator-side conversion and
+    /// carries no locked rate makes `record_settlement` return an error;
+    /// legs without such an instruction post in their original currency.
+    fx_provider: Option<Arc<dyn FxRateProvider>>,
 }
 
 impl LedgerEngine {
     /// Create a new ledger engine.
     pub fn new() -> Self {
-        Self {}
+        Self {
+            journal: RwLock::new(Vec::new()),
+            balances: DashMap::new(),
+            store: None,
+            status_cache: RwLock::new(StatusCache::new()),
+            fx_provider: None,
+        }
+    }
+
+    /// Attach a durable store that every subsequent `record_settlement`,
+    /// `lock_funds`, and `unlock_funds` call writes through to, in addition
+    /// to this engine's own in-memory journal and balances.
+    pub fn with_store(mut self, store: Arc<PostgresLedgerStore>) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    /// Attach the rate source `record_settlement` consults to convert
+    /// cross-currency legs before crediting them.
+    pub fn with_fx_provider(mut self, provider: Arc<dyn FxRateProvider>) -> Self {
+        self.fx_provider = Some(provider);
+        self
+    }
+
+    /// Populate `leg.converted_amount` when its debit and credit accounts
+    /// are in different currencies and the coordinator (rather than the
+    /// sender or receiver) is responsible for converting it. Prefers a
+    /// pre-locked rate already on the leg's `fx_instruction` over
+    /// consulting `fx_provider`, since a locked rate was already agreed
+    /// with the participants and shouldn't be second-guessed here.
+    /// Leaves `leg.converted_amount` untouched (and the leg posted in its
+    /// original currency) when none of that applies, matching the prior
+    /// behavior for same-currency legs.
+    fn resolve_converted_amount(&self, leg: &mut SettlementLeg) -> Result<()> {
+        if leg.converted_amount.is_some() {
+            return Ok(());
+        }
+
+        let Some(fx_instruction) = &leg.fx_instruction else {
+            return Ok(());
+        };
+        if fx_instruction.mode != FxMode::AtCoordinator {
+            return Ok(());
+        }
+        let Some(target_currency) = &fx_instruction.target_currency else {
+            return Ok(());
+        };
+        let target = Currency::new(target_currency.as_str());
+        if target == leg.amount.currency {
+            return Ok(());
+        }
+
+        let converted = if let Some(locked_rate) = &fx_instruction.locked_rate {
+            let value = leg
+                .amount
+                .value
+                .checked_mul(locked_rate.mid)
+                .ok_or_else(|| {
+                    AtomicSettleError::InternalError(format!(
+                        "overflow applying locked rate for leg {}",
+                        leg.leg_number
+                    ))
+                })?
+                .round_dp(target.decimal_places());
+            Money::new(value, target)
+        } else if let Some(provider) = &self.fx_provider {
+            provider.convert(&leg.amount, &target)?
+        } else {
+            return Err(AtomicSettleError::InternalError(format!(
+                "leg {} needs conversion to {target} but no FX rate provider is configured",
+                leg.leg_number
+            )));
+        };
+
+        leg.converted_amount = Some(converted);
+        Ok(())
+    }
+
+    /// Append an entry to the journal: seal it onto the hash chain and
+    /// apply it to its account's running balance.
+    fn append(&self, mut entry: JournalEntry) -> JournalEntry {
+        let mut journal = self.journal.write();
+
+        let prev_hash = journal.last().map(|last| last.entry_hash.clone());
+        entry.seq = journal.len() as u64;
+        entry.balance_after = self.apply_to_balance(&entry);
+        entry.seal(prev_hash);
+
+        journal.push(entry.clone());
+        entry
+    }
+
+    /// Apply an entry's effect to its account's running balance (debit
+    /// reduces, credit increases) and return the resulting balance.
+    fn apply_to_balance(&self, entry: &JournalEntry) -> Decimal {
+        let mut balance = self
+            .balances
+            .entry(entry.account_id.clone())
+            .or_insert_with(|| AccountBalance::zero(entry.account_id.clone(), entry.currency.clone()));
+
+        match entry.entry_type {
+            EntryType::Debit => balance.balance -= entry.amount,
+            EntryType::Credit => balance.balance += entry.amount,
+        }
+        balance.updated_at = atomicsettle_common::time::now();
+        balance.balance
     }
 
     /// Record a settlement with full audit trail.
     #[instrument(skip(self, settlement))]
     pub async fn record_settlement(&self, settlement: &Settlement) -> Result<Vec<JournalEntry>> {
+        if let Some(cached) = self.status_cache.read().get(&settlement.idempotency_key) {
+            info!(
+                settlement_id = %settlement.id,
+                idempotency_key = %settlement.idempotency_key,
+                "Settlement replay detected, returning cached outcome"
+            );
+            return Ok(cached);
+        }
+
         info!(
             settlement_id = %settlement.id,
             legs = settlement.legs.len(),
             "Recording settlement"
         );
 
+        // Resolve cross-currency legs before building journal entries, so
+        // the credit side posts in the destination currency instead of
+        // silently carrying over the debited amount and currency. Works
+        // against a clone rather than mutating the caller's `Settlement`,
+        // since this is the only consumer of the resolved
+        // `converted_amount` and an attached `store` needs to see the same
+        // resolved legs this engine just posted.
+        let mut settlement = settlement.clone();
+        for leg in &mut settlement.legs {
+            self.resolve_converted_amount(leg)?;
+        }
+
         let mut entries = Vec::new();
 
-        // Create journal entries for each leg
         for leg in &settlement.legs {
-            // Debit source account
-            let debit_entry = JournalEntry {
-                id: uuid::Uuid::new_v4(),
-                settlement_id: settlement.id,
-                leg_number: leg.leg_number,
-                account_id: leg.from_account.clone(),
-                entry_type: EntryType::Debit,
-                amount: leg.amount.value,
-                currency: leg.amount.currency.clone(),
-                balance_after: Decimal::ZERO, // Would be calculated from DB
-                created_at: chrono::Utc::now(),
-            };
-            entries.push(debit_entry);
-
-            // Credit destination account
-            let amount = leg
-                .converted_amount
-                .as_ref()
-                .unwrap_or(&leg.amount);
-
-            let credit_entry = JournalEntry {
-                id: uuid::Uuid::new_v4(),
-                settlement_id: settlement.id,
-                leg_number: leg.leg_number,
-                account_id: leg.to_account.clone(),
-                entry_type: EntryType::Credit,
-                amount: amount.value,
-                currency: amount.currency.clone(),
-                balance_after: Decimal::ZERO, // Would be calculated from DB
-                created_at: chrono::Utc::now(),
-            };
-            entries.push(credit_entry);
+            let debit_entry = JournalEntry::debit(
+                settlement.id,
+                leg.leg_number,
+                leg.from_account.clone(),
+                leg.amount.value,
+                leg.amount.currency.clone(),
+            );
+            entries.push(self.append(debit_entry));
+
+            let amount = leg.converted_amount.as_ref().unwrap_or(&leg.amount);
+            let credit_entry = JournalEntry::credit(
+                settlement.id,
+                leg.leg_number,
+                leg.to_account.clone(),
+                amount.value,
+                amount.currency.clone(),
+            );
+            entries.push(self.append(credit_entry));
+        }
+
+        if let Some(store) = &self.store {
+            store.record_settlement(&settlement).await?;
         }
 
-        // In a real implementation:
-        // 1. Start database transaction
-        // 2. Insert all journal entries
-        // 3. Update account balances
-        // 4. Verify debits == credits
-        // 5. Commit transaction
+        self.status_cache
+            .write()
+            .insert(settlement.idempotency_key.clone(), entries.clone());
 
         info!(
             settlement_id = %settlement.id,
@@ -88,16 +229,11 @@ impl LedgerEngine {
 
     /// Get account balance.
     pub async fn get_balance(&self, account_id: &AccountId) -> Result<AccountBalance> {
-        // In a real implementation, query from database
-        Ok(AccountBalance {
-            account_id: account_id.clone(),
-            currency: Currency::new(&account_id.currency),
-            balance: Decimal::ZERO,
-            locked_balance: Decimal::ZERO,
-            pending_credits: Decimal::ZERO,
-            pending_debits: Decimal::ZERO,
-            updated_at: chrono::Utc::now(),
-        })
+        Ok(self
+            .balances
+            .get(account_id)
+            .map(|b| b.clone())
+            .unwrap_or_else(|| AccountBalance::zero(account_id.clone(), Currency::new(&account_id.currency))))
     }
 
     /// Debit an account (reduce balance).
@@ -116,19 +252,15 @@ impl LedgerEngine {
             "Debiting account"
         );
 
-        let entry = JournalEntry {
-            id: uuid::Uuid::new_v4(),
+        let entry = JournalEntry::debit(
             settlement_id,
             leg_number,
-            account_id: account_id.clone(),
-            entry_type: EntryType::Debit,
+            account_id.clone(),
             amount,
-            currency: Currency::new(&account_id.currency),
-            balance_after: Decimal::ZERO,
-            created_at: chrono::Utc::now(),
-        };
+            Currency::new(&account_id.currency),
+        );
 
-        Ok(entry)
+        Ok(self.append(entry))
     }
 
     /// Credit an account (increase balance).
@@ -147,22 +279,22 @@ impl LedgerEngine {
             "Crediting account"
         );
 
-        let entry = JournalEntry {
-            id: uuid::Uuid::new_v4(),
+        let entry = JournalEntry::credit(
             settlement_id,
             leg_number,
-            account_id: account_id.clone(),
-            entry_type: EntryType::Credit,
+            account_id.clone(),
             amount,
-            currency: Currency::new(&account_id.currency),
-            balance_after: Decimal::ZERO,
-            created_at: chrono::Utc::now(),
-        };
+            Currency::new(&account_id.currency),
+        );
 
-        Ok(entry)
+        Ok(self.append(entry))
     }
 
-    /// Lock funds in an account.
+    /// Lock funds in an account. When a [`PostgresLedgerStore`] is
+    /// attached, the conditional `available_balance >= amount` UPDATE runs
+    /// there and its failure (insufficient funds) is propagated; otherwise
+    /// this is a no-op beyond logging, since the in-memory engine tracks
+    /// locked amounts at the coordinator's `LockManager` layer instead.
     pub async fn lock_funds(&self, account_id: &AccountId, amount: Decimal) -> Result<()> {
         info!(
             account = %account_id,
@@ -170,16 +302,15 @@ impl LedgerEngine {
             "Locking funds"
         );
 
-        // In a real implementation:
-        // UPDATE accounts
-        // SET available_balance = available_balance - amount,
-        //     locked_balance = locked_balance + amount
-        // WHERE account_id = $1 AND available_balance >= amount
+        if let Some(store) = &self.store {
+            store.lock_funds(account_id, amount).await?;
+        }
 
         Ok(())
     }
 
-    /// Unlock funds in an account.
+    /// Unlock funds in an account. See [`Self::lock_funds`] for how this
+    /// interacts with an attached store.
     pub async fn unlock_funds(&self, account_id: &AccountId, amount: Decimal) -> Result<()> {
         info!(
             account = %account_id,
@@ -187,11 +318,9 @@ impl LedgerEngine {
             "Unlocking funds"
         );
 
-        // In a real implementation:
-        // UPDATE accounts
-        // SET available_balance = available_balance + amount,
-        //     locked_balance = locked_balance - amount
-        // WHERE account_id = $1
+        if let Some(store) = &self.store {
+            store.unlock_funds(account_id, amount).await?;
+        }
 
         Ok(())
     }
@@ -201,23 +330,164 @@ impl LedgerEngine {
         &self,
         settlement_id: SettlementId,
     ) -> Result<Vec<JournalEntry>> {
-        // In a real implementation, query from database
-        Ok(Vec::new())
+        Ok(self
+            .journal
+            .read()
+            .iter()
+            .filter(|entry| entry.settlement_id == settlement_id)
+            .cloned()
+            .collect())
     }
 
-    /// Verify ledger integrity (debits == credits).
-    pub async fn verify_integrity(&self) -> Result<bool> {
-        // In a real implementation:
-        // SELECT SUM(CASE WHEN entry_type = 'DEBIT' THEN amount ELSE 0 END) as total_debits,
-        //        SUM(CASE WHEN entry_type = 'CREDIT' THEN amount ELSE 0 END) as total_credits
-        // FROM journal_entries
-        // GROUP BY currency
-        // HAVING total_debits != total_credits
+    /// Verify the journal's integrity:
+    ///
+    /// 1. The hash chain is intact -- every entry's `entry_hash` matches
+    ///    its canonical content plus its recorded predecessor's hash, and
+    ///    that recorded predecessor matches the entry that actually came
+    ///    before it.
+    /// 2. The double-entry invariant holds -- total debits equal total
+    ///    credits for every currency appearing in the journal.
+    /// 3. Every account's current balance equals the balance you'd get by
+    ///    replaying its entries from zero.
+    ///
+    /// Returns the first `LedgerError::Corruption` found rather than
+    /// continuing past it, since later checks assume the journal is
+    /// trustworthy. Intended to run on startup and after recovery, and
+    /// mutating operations should bubble up the same error rather than
+    /// leaving balances inconsistent.
+    pub fn verify_integrity(&self) -> LedgerResult<()> {
+        let journal = self.journal.read();
+
+        // 1. Hash chain.
+        let mut prev_hash: Option<Vec<u8>> = None;
+        for entry in journal.iter() {
+            if entry.prev_hash != prev_hash {
+                return Err(LedgerError::Corruption {
+                    entry_id: entry.id,
+                    expected: format!("{prev_hash:?}"),
+                    actual: format!("{:?}", entry.prev_hash),
+                });
+            }
+
+            let expected = entry.expected_hash(&prev_hash);
+            if entry.entry_hash != expected {
+                return Err(LedgerError::Corruption {
+                    entry_id: entry.id,
+                    expected: hex(&expected),
+                    actual: hex(&entry.entry_hash),
+                });
+            }
+
+            prev_hash = Some(entry.entry_hash.clone());
+        }
 
-        Ok(true)
+        // 2. Double-entry invariant, per currency.
+        let mut net_by_currency: HashMap<Currency, Decimal> = HashMap::new();
+        for entry in journal.iter() {
+            let delta = match entry.entry_type {
+                EntryType::Debit => entry.amount,
+                EntryType::Credit => -entry.amount,
+            };
+            *net_by_currency.entry(entry.currency.clone()).or_insert(Decimal::ZERO) += delta;
+        }
+
+        for (currency, net) in &net_by_currency {
+            if !net.is_zero() {
+                let last_entry = journal
+                    .iter()
+                    .rev()
+                    .find(|e| e.currency == *currency)
+                    .expect("currency came from an entry in this journal");
+
+                return Err(LedgerError::Corruption {
+                    entry_id: last_entry.id,
+                    expected: format!("debits == credits for {currency}"),
+                    actual: format!("net {net} {currency}"),
+                });
+            }
+        }
+
+        // 3. Replayed account balances match the maintained ones.
+        let mut replayed: HashMap<AccountId, Decimal> = HashMap::new();
+        for entry in journal.iter() {
+            let delta = match entry.entry_type {
+                EntryType::Debit => -entry.amount,
+                EntryType::Credit => entry.amount,
+            };
+            *replayed.entry(entry.account_id.clone()).or_insert(Decimal::ZERO) += delta;
+        }
+
+        for (account_id, expected_balance) in &replayed {
+            let actual_balance = self
+                .balances
+                .get(account_id)
+                .map(|b| b.balance)
+                .unwrap_or(Decimal::ZERO);
+
+            if *expected_balance != actual_balance {
+                let last_entry = journal
+                    .iter()
+                    .rev()
+                    .find(|e| e.account_id == *account_id)
+                    .expect("account came from an entry in this journal");
+
+                return Err(LedgerError::Corruption {
+                    entry_id: last_entry.id,
+                    expected: format!("balance {expected_balance} for account {account_id}"),
+                    actual: format!("balance {actual_balance}"),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Ledger-wide integrity check: [`Self::verify_integrity`]'s journal
+    /// invariants, plus every tracked account's current balance against
+    /// [`AccountBalance::validate`]'s non-negative invariant. Intended to
+    /// run immediately after loading persisted state or recovering from a
+    /// crash, so the simulator (and any other caller) gets a clear
+    /// `LedgerError` instead of trusting a best-effort reconstruction.
+    pub fn check_integrity(&self) -> LedgerResult<()> {
+        self.verify_integrity()?;
+
+        for balance in self.balances.iter() {
+            balance.value().validate(balance.key())?;
+        }
+
+        Ok(())
+    }
+
+    /// Rebuild a ledger engine from previously-persisted journal entries,
+    /// e.g. after a crash. The hash chain is verified before any of it is
+    /// trusted: corrupted persisted state surfaces as a
+    /// `LedgerError::Corruption` rather than being loaded best-effort.
+    pub fn restore_from_journal(entries: Vec<JournalEntry>) -> LedgerResult<Self> {
+        if !crate::journal::verify_chain(&entries, None) {
+            let entry_id = entries.first().map(|e| e.id).unwrap_or_else(Uuid::nil);
+            return Err(LedgerError::Corruption {
+                entry_id,
+                expected: "unbroken hash chain from genesis".to_string(),
+                actual: "chain verification failed".to_string(),
+            });
+        }
+
+        let engine = Self::new();
+        for entry in &entries {
+            engine.apply_to_balance(entry);
+        }
+        *engine.journal.write() = entries;
+
+        engine.check_integrity()?;
+        Ok(engine)
     }
 }
 
+/// Hex-encode bytes for inclusion in a `LedgerError` message.
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
 impl Default for LedgerEngine {
     fn default() -> Self {
         Self::new()
@@ -227,7 +497,8 @@ impl Default for LedgerEngine {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use atomicsettle_common::{ParticipantId, SettlementLeg};
+    use crate::fx::RateTable;
+    use atomicsettle_common::{FxInstruction, ParticipantId};
 
     fn create_test_settlement() -> Settlement {
         let leg = SettlementLeg::new(
@@ -254,4 +525,210 @@ mod tests {
         assert!(entries.iter().any(|e| e.entry_type == EntryType::Debit));
         assert!(entries.iter().any(|e| e.entry_type == EntryType::Credit));
     }
+
+    #[tokio::test]
+    async fn test_record_settlement_converts_cross_currency_leg_via_fx_provider() {
+        let rates = Arc::new(RateTable::new());
+        rates.set_rate(
+            atomicsettle_common::CurrencyPair::new(Currency::usd(), Currency::eur()),
+            Decimal::new(92, 2), // 0.92
+        );
+        let engine = LedgerEngine::new().with_fx_provider(rates);
+
+        let mut leg = SettlementLeg::new(
+            1,
+            ParticipantId::new("BANK_A"),
+            AccountId::new(ParticipantId::new("BANK_A"), "12345", "USD"),
+            ParticipantId::new("BANK_B"),
+            AccountId::new(ParticipantId::new("BANK_B"), "67890", "EUR"),
+            Money::new(Decimal::from(1000), Currency::usd()),
+        );
+        leg.fx_instruction = Some(FxInstruction {
+            mode: FxMode::AtCoordinator,
+            target_currency: Some("EUR".to_string()),
+            locked_rate: None,
+            rate_reference: None,
+        });
+        let settlement = Settlement::new("fx-leg-key".to_string(), vec![leg]);
+
+        let entries = engine.record_settlement(&settlement).await.unwrap();
+
+        let credit = entries.iter().find(|e| e.entry_type == EntryType::Credit).unwrap();
+        assert_eq!(credit.currency, Currency::eur());
+        assert_eq!(credit.amount, Decimal::from(920));
+    }
+
+    #[tokio::test]
+    async fn test_record_settlement_cross_currency_leg_without_provider_errors() {
+        let engine = LedgerEngine::new();
+
+        let mut leg = SettlementLeg::new(
+            1,
+            ParticipantId::new("BANK_A"),
+            AccountId::new(ParticipantId::new("BANK_A"), "12345", "USD"),
+            ParticipantId::new("BANK_B"),
+            AccountId::new(ParticipantId::new("BANK_B"), "67890", "EUR"),
+            Money::new(Decimal::from(1000), Currency::usd()),
+        );
+        leg.fx_instruction = Some(FxInstruction {
+            mode: FxMode::AtCoordinator,
+            target_currency: Some("EUR".to_string()),
+            locked_rate: None,
+            rate_reference: None,
+        });
+        let settlement = Settlement::new("fx-leg-key-2".to_string(), vec![leg]);
+
+        assert!(engine.record_settlement(&settlement).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_record_settlement_replay_returns_cached_outcome_without_double_posting() {
+        let engine = LedgerEngine::new();
+        let settlement = create_test_settlement();
+
+        let first = engine.record_settlement(&settlement).await.unwrap();
+        let second = engine.record_settlement(&settlement).await.unwrap();
+
+        assert_eq!(first.len(), second.len());
+        assert_eq!(first[0].id, second[0].id);
+        assert_eq!(engine.journal.read().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_verify_integrity_accepts_clean_journal() {
+        let engine = LedgerEngine::new();
+        let settlement = create_test_settlement();
+
+        engine.record_settlement(&settlement).await.unwrap();
+
+        assert!(engine.verify_integrity().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_verify_integrity_detects_tampered_amount() {
+        let engine = LedgerEngine::new();
+        let settlement = create_test_settlement();
+
+        engine.record_settlement(&settlement).await.unwrap();
+
+        // Tamper with a journal entry's amount after the fact, bypassing
+        // the engine's own bookkeeping -- the hash chain should catch it.
+        engine.journal.write()[0].amount = Decimal::from(999_999);
+
+        assert!(matches!(
+            engine.verify_integrity(),
+            Err(LedgerError::Corruption { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_verify_integrity_detects_balance_drift() {
+        let engine = LedgerEngine::new();
+        let settlement = create_test_settlement();
+
+        engine.record_settlement(&settlement).await.unwrap();
+
+        let account_a = AccountId::new(ParticipantId::new("BANK_A"), "12345", "USD");
+        engine.balances.get_mut(&account_a).unwrap().balance += Decimal::from(1);
+
+        assert!(matches!(
+            engine.verify_integrity(),
+            Err(LedgerError::Corruption { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_verify_chain_accepts_exported_entries_from_genesis() {
+        let engine = LedgerEngine::new();
+        let settlement = create_test_settlement();
+
+        let entries = engine.record_settlement(&settlement).await.unwrap();
+
+        assert!(crate::journal::verify_chain(&entries, None));
+    }
+
+    #[tokio::test]
+    async fn test_verify_chain_rejects_tampered_entry() {
+        let engine = LedgerEngine::new();
+        let settlement = create_test_settlement();
+
+        let mut entries = engine.record_settlement(&settlement).await.unwrap();
+        entries[0].amount = Decimal::from(999_999);
+
+        assert!(!crate::journal::verify_chain(&entries, None));
+    }
+
+    #[tokio::test]
+    async fn test_verify_chain_rejects_reordered_entries() {
+        let engine = LedgerEngine::new();
+        let settlement = create_test_settlement();
+
+        let mut entries = engine.record_settlement(&settlement).await.unwrap();
+        entries.swap(0, 1);
+
+        assert!(!crate::journal::verify_chain(&entries, None));
+    }
+
+    #[tokio::test]
+    async fn test_check_integrity_passes_clean_ledger() {
+        let engine = LedgerEngine::new();
+        let settlement = create_test_settlement();
+
+        engine.record_settlement(&settlement).await.unwrap();
+
+        assert!(engine.check_integrity().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_check_integrity_detects_negative_balance() {
+        let engine = LedgerEngine::new();
+        let settlement = create_test_settlement();
+
+        engine.record_settlement(&settlement).await.unwrap();
+
+        let account_a = AccountId::new(ParticipantId::new("BANK_A"), "12345", "USD");
+        engine.balances.get_mut(&account_a).unwrap().balance = Decimal::from(-1);
+
+        assert!(matches!(
+            engine.check_integrity(),
+            Err(LedgerError::StateCorrupt { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_restore_from_journal_rebuilds_balances() {
+        let engine = LedgerEngine::new();
+        let settlement = create_test_settlement();
+        let entries = engine.record_settlement(&settlement).await.unwrap();
+
+        let restored = LedgerEngine::restore_from_journal(entries).unwrap();
+
+        let account_a = AccountId::new(ParticipantId::new("BANK_A"), "12345", "USD");
+        let balance = restored.get_balance(&account_a).await.unwrap();
+        assert_eq!(balance.balance, Decimal::from(-1000));
+    }
+
+    #[tokio::test]
+    async fn test_restore_from_journal_rejects_tampered_entries() {
+        let engine = LedgerEngine::new();
+        let settlement = create_test_settlement();
+        let mut entries = engine.record_settlement(&settlement).await.unwrap();
+        entries[0].amount = Decimal::from(999_999);
+
+        assert!(matches!(
+            LedgerEngine::restore_from_journal(entries),
+            Err(LedgerError::Corruption { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_entries_chain_to_predecessor() {
+        let engine = LedgerEngine::new();
+        let settlement = create_test_settlement();
+
+        let entries = engine.record_settlement(&settlement).await.unwrap();
+
+        assert_eq!(entries[0].prev_hash, None);
+        assert_eq!(entries[1].prev_hash, Some(entries[0].entry_hash.clone()));
+    }
 }
@@ -0,0 +1,68 @@
+//! Ledger-specific errors.
+//!
+//! Most ledger operations surface `atomicsettle_common::AtomicSettleError`
+//! like the rest of the protocol. Integrity verification is the exception:
+//! it needs to report exactly where a journal diverged from what it should
+//! contain, which doesn't fit the common error's flatter variants, so it
+//! gets its own structured error type.
+
+use atomicsettle_common::{AccountId, SettlementId};
+use uuid::Uuid;
+
+/// Errors from ledger integrity verification.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum LedgerError {
+    /// The journal is internally inconsistent: a broken hash chain link, an
+    /// unbalanced double-entry, or an account whose replayed balance
+    /// diverges from its recorded one. `entry_id` identifies the entry at
+    /// which the inconsistency was first detected.
+    #[error("ledger corruption at entry {entry_id}: expected {expected}, found {actual}")]
+    Corruption {
+        entry_id: Uuid,
+        expected: String,
+        actual: String,
+    },
+    /// An `Account` or its `AccountBalance` violated a structural
+    /// invariant -- a status transition attempted on a closed account, a
+    /// currency mismatch against the account's own ID, or a balance that
+    /// went negative. Distinct from `Corruption`, which is about the
+    /// journal; this is about account-level state derived from it.
+    #[error("account {account} state corrupt: {detail}")]
+    StateCorrupt { account: AccountId, detail: String },
+
+    /// A `JournalBatch` was rejected before being applied because its
+    /// entries don't net to zero per currency -- see
+    /// `crate::journal::JournalBatch::is_balanced`.
+    #[error("batch for settlement {settlement_id} is not balanced")]
+    Unbalanced { settlement_id: SettlementId },
+
+    /// An entry inside a `JournalBatch` named a currency other than the
+    /// one its own `AccountId` is denominated in.
+    #[error("currency mismatch on account {account}: account is {expected}, entry is {actual}")]
+    CurrencyMismatch {
+        account: AccountId,
+        expected: String,
+        actual: String,
+    },
+
+    /// A debit inside a `JournalBatch` would have overdrawn the account
+    /// applying it to a working copy of the balance store.
+    #[error("insufficient funds on account {account}: needs {required}, has {available}")]
+    InsufficientFunds {
+        account: AccountId,
+        required: String,
+        available: String,
+    },
+
+    /// A `Burn` supply leg would have taken a currency's total issuance
+    /// below zero -- see `crate::supply::SupplyLedger::apply_entry`.
+    #[error("insufficient issuance of {currency} to burn: needs {required}, has {available}")]
+    InsufficientIssuance {
+        currency: String,
+        required: String,
+        available: String,
+    },
+}
+
+/// Result type alias for ledger integrity operations.
+pub type Result<T> = std::result::Result<T, LedgerError>;
@@ -0,0 +1,100 @@
+//! Settlement fee policies and batch fee-leg injection.
+//!
+//! Modeled on pallet-transaction-payment's pluggable fee calculation:
+//! [`FeePolicy`] computes what a batch owes, and
+//! [`crate::journal::JournalBatch::apply_fees`] turns that into a balanced
+//! debit/credit pair rather than making every caller hand-write the
+//! offsetting entries and risk an unbalanced batch.
+
+use atomicsettle_common::Currency;
+use rust_decimal::Decimal;
+
+/// Computes the fee owed on a settlement batch's gross debit amount.
+pub trait FeePolicy: Send + Sync {
+    /// Compute the fee due on `gross_amount` of `currency`.
+    fn compute_fee(&self, gross_amount: Decimal, currency: &Currency) -> Decimal;
+}
+
+/// Charges the same fee on every batch, regardless of size.
+pub struct FlatFee {
+    pub amount: Decimal,
+}
+
+impl FeePolicy for FlatFee {
+    fn compute_fee(&self, _gross_amount: Decimal, _currency: &Currency) -> Decimal {
+        self.amount
+    }
+}
+
+/// Charges a fee proportional to the batch's gross debit amount, in basis
+/// points (1 bps = 0.01% of notional).
+pub struct BasisPointsFee {
+    pub bps: Decimal,
+}
+
+impl FeePolicy for BasisPointsFee {
+    fn compute_fee(&self, gross_amount: Decimal, _currency: &Currency) -> Decimal {
+        gross_amount * self.bps / Decimal::from(10_000)
+    }
+}
+
+/// One band of a [`TieredFee`] schedule: gross amounts up to and including
+/// `upper_bound` are charged at `bps`; `None` covers everything above the
+/// last explicit tier.
+pub struct FeeTier {
+    pub upper_bound: Option<Decimal>,
+    pub bps: Decimal,
+}
+
+/// Charges a basis-points rate that varies with the batch's gross debit
+/// amount, e.g. a lower rate for larger institutional transfers. Tiers are
+/// checked in order; the first whose `upper_bound` the gross amount
+/// doesn't exceed applies.
+pub struct TieredFee {
+    pub tiers: Vec<FeeTier>,
+}
+
+impl FeePolicy for TieredFee {
+    fn compute_fee(&self, gross_amount: Decimal, _currency: &Currency) -> Decimal {
+        for tier in &self.tiers {
+            match tier.upper_bound {
+                Some(bound) if gross_amount > bound => continue,
+                _ => return gross_amount * tier.bps / Decimal::from(10_000),
+            }
+        }
+        Decimal::ZERO
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flat_fee_ignores_gross_amount() {
+        let policy = FlatFee { amount: Decimal::from(5) };
+
+        assert_eq!(policy.compute_fee(Decimal::from(100), &Currency::usd()), Decimal::from(5));
+        assert_eq!(policy.compute_fee(Decimal::from(1_000_000), &Currency::usd()), Decimal::from(5));
+    }
+
+    #[test]
+    fn test_basis_points_fee_scales_with_gross_amount() {
+        let policy = BasisPointsFee { bps: Decimal::from(50) };
+
+        assert_eq!(policy.compute_fee(Decimal::from(10_000), &Currency::usd()), Decimal::from(50));
+    }
+
+    #[test]
+    fn test_tiered_fee_applies_matching_band() {
+        let policy = TieredFee {
+            tiers: vec![
+                FeeTier { upper_bound: Some(Decimal::from(1_000)), bps: Decimal::from(100) },
+                FeeTier { upper_bound: None, bps: Decimal::from(10) },
+            ],
+        };
+
+        assert_eq!(policy.compute_fee(Decimal::from(500), &Currency::usd()), Decimal::from(5));
+        assert_eq!(policy.compute_fee(Decimal::from(10_000), &Currency::usd()), Decimal::from(10));
+    }
+}
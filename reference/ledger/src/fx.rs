@@ -0,0 +1,135 @@
+//! Multi-currency settlement support.
+//!
+//! [`crate::engine::LedgerEngine::record_settlement`] reads
+//! `leg.converted_amount` for the credit side of a leg, but nothing
+//! populated it: a leg whose debit and credit accounts are denominated in
+//! different currencies silently credited the debited amount under the
+//! wrong currency. [`FxRateProvider`] is a minimal rate source the engine
+//! can consult to fix that -- deliberately not a dependency on
+//! `reference/fx`'s `RateProvider`, which is built for live market quoting
+//! (spreads, triangulation, streaming feeds); the ledger only ever needs a
+//! single point-in-time rate to convert one leg.
+
+use std::collections::HashMap;
+
+use atomicsettle_common::{AtomicSettleError, Currency, CurrencyPair, Money, Result};
+use parking_lot::RwLock;
+use rust_decimal::Decimal;
+
+/// A conversion rate between two currencies: one unit of `base` is worth
+/// `value` units of `quote`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rate {
+    pub base: Currency,
+    pub quote: Currency,
+    pub value: Decimal,
+}
+
+/// Supplies the rate to convert a settlement leg's amount into a different
+/// credit currency.
+pub trait FxRateProvider: Send + Sync {
+    /// Get the rate to convert from `pair.base` into `pair.quote`.
+    fn rate(&self, pair: &CurrencyPair) -> Result<Rate>;
+
+    /// Convert `amount` into `target`, consulting [`Self::rate`] when the
+    /// currencies differ. Uses checked `Decimal` arithmetic throughout, so
+    /// an overflow surfaces as an error rather than panicking, following
+    /// the same convention as the FX engine's own quote math.
+    fn convert(&self, amount: &Money, target: &Currency) -> Result<Money> {
+        if amount.currency == *target {
+            return Ok(amount.clone());
+        }
+
+        let pair = CurrencyPair::new(amount.currency.clone(), target.clone());
+        let rate = self.rate(&pair)?;
+
+        let converted = amount
+            .value
+            .checked_mul(rate.value)
+            .ok_or_else(|| {
+                AtomicSettleError::InternalError(format!(
+                    "overflow converting {amount} to {target}"
+                ))
+            })?
+            .round_dp(target.decimal_places());
+
+        Ok(Money::new(converted, target.clone()))
+    }
+}
+
+/// A static table of conversion rates, keyed by currency pair. Suitable
+/// for tests and deployments with a small, slowly-changing set of
+/// supported currencies; a deployment that needs live market rates should
+/// implement [`FxRateProvider`] against `reference/fx`'s `FxEngine`
+/// instead.
+#[derive(Default)]
+pub struct RateTable {
+    rates: RwLock<HashMap<CurrencyPair, Decimal>>,
+}
+
+impl RateTable {
+    /// Create an empty rate table.
+    pub fn new() -> Self {
+        Self {
+            rates: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Set (or replace) the rate for `pair`: one unit of `pair.base` is
+    /// worth `rate` units of `pair.quote`.
+    pub fn set_rate(&self, pair: CurrencyPair, rate: Decimal) {
+        self.rates.write().insert(pair, rate);
+    }
+}
+
+impl FxRateProvider for RateTable {
+    fn rate(&self, pair: &CurrencyPair) -> Result<Rate> {
+        let value = self.rates.read().get(pair).copied().ok_or_else(|| {
+            AtomicSettleError::InternalError(format!("no FX rate configured for {pair}"))
+        })?;
+
+        Ok(Rate {
+            base: pair.base.clone(),
+            quote: pair.quote.clone(),
+            value,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_currency_skips_rate_lookup() {
+        let table = RateTable::new();
+        let amount = Money::new(Decimal::from(100), Currency::usd());
+
+        let converted = table.convert(&amount, &Currency::usd()).unwrap();
+
+        assert_eq!(converted, amount);
+    }
+
+    #[test]
+    fn test_convert_applies_configured_rate() {
+        let table = RateTable::new();
+        table.set_rate(
+            CurrencyPair::new(Currency::usd(), Currency::eur()),
+            Decimal::new(92, 2), // 0.92
+        );
+
+        let amount = Money::new(Decimal::from(100), Currency::usd());
+        let converted = table.convert(&amount, &Currency::eur()).unwrap();
+
+        assert_eq!(converted.value, Decimal::from(92));
+        assert_eq!(converted.currency, Currency::eur());
+    }
+
+    #[test]
+    fn test_convert_without_configured_rate_errors() {
+        let table = RateTable::new();
+        let amount = Money::new(Decimal::from(100), Currency::usd());
+
+        assert!(table.convert(&amount, &Currency::eur()).is_err());
+    }
+}
@@ -1,6 +1,9 @@
 //! Journal entry types for double-entry bookkeeping.
 
+use std::collections::{HashMap, VecDeque};
+
 use atomicsettle_common::{AccountId, Currency, SettlementId};
+use atomicsettle_crypto::sha384;
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
@@ -36,9 +39,92 @@ pub struct JournalEntry {
     pub balance_after: Decimal,
     /// When this entry was created.
     pub created_at: DateTime<Utc>,
+    /// This entry's position in the journal, contiguous from 0. Hashed
+    /// into `entry_hash` alongside `prev_hash` so a verifier can also
+    /// detect entries spliced in or dropped out of sequence, not just
+    /// ones whose content was edited.
+    pub seq: u64,
+    /// Hash of the entry immediately before this one in the journal, or
+    /// `None` for the first entry. Chains entries together so a deleted,
+    /// reordered, or edited entry breaks the chain at a detectable point.
+    pub prev_hash: Option<Vec<u8>>,
+    /// SHA-384 over this entry's canonical content (id, account, amount,
+    /// type, currency, `seq`, `prev_hash`), set once the entry is appended
+    /// to the journal and its predecessor is known. Empty until then.
+    pub entry_hash: Vec<u8>,
+}
+
+/// Canonical content hashed into an entry's `entry_hash`, given the hash of
+/// the entry before it in the chain.
+fn chain_content(entry: &JournalEntry, prev_hash: &Option<Vec<u8>>) -> Vec<u8> {
+    let mut content = format!(
+        "{}|{}|{}|{:?}|{}|{}",
+        entry.id,
+        entry.account_id.canonical(),
+        entry.amount,
+        entry.entry_type,
+        entry.currency,
+        entry.seq,
+    )
+    .into_bytes();
+
+    if let Some(prev_hash) = prev_hash {
+        content.extend_from_slice(prev_hash);
+    }
+
+    content
+}
+
+/// Verify that an exported slice of journal entries -- e.g. one fetched
+/// from [`crate::engine::LedgerEngine::get_settlement_entries`] or shipped
+/// off to an auditor -- forms a valid, tamper-evident hash chain rooted at
+/// `genesis` (the prior chain's last `entry_hash`, or `None` for a chain
+/// starting from scratch). Walks the slice confirming each entry's
+/// `prev_hash` matches its predecessor's `entry_hash`, its `entry_hash`
+/// matches what [`JournalEntry::expected_hash`] recomputes, and `seq`
+/// numbers are contiguous -- so an auditor holding only the slice, without
+/// access to the [`crate::engine::LedgerEngine`] that produced it, can
+/// still confirm nothing was inserted, reordered, or mutated.
+pub fn verify_chain(entries: &[JournalEntry], genesis: Option<Vec<u8>>) -> bool {
+    let mut prev_hash = genesis;
+    let mut prev_seq: Option<u64> = None;
+
+    for entry in entries {
+        if entry.prev_hash != prev_hash {
+            return false;
+        }
+        if entry.entry_hash != entry.expected_hash(&prev_hash) {
+            return false;
+        }
+        if let Some(seq) = prev_seq {
+            if entry.seq != seq + 1 {
+                return false;
+            }
+        }
+
+        prev_hash = Some(entry.entry_hash.clone());
+        prev_seq = Some(entry.seq);
+    }
+
+    true
 }
 
 impl JournalEntry {
+    /// Seal the entry into the chain: record `prev_hash` and compute this
+    /// entry's own `entry_hash` over its canonical content. Must be called
+    /// once, when the entry is appended to the journal.
+    pub fn seal(&mut self, prev_hash: Option<Vec<u8>>) {
+        let content = chain_content(self, &prev_hash);
+        self.prev_hash = prev_hash;
+        self.entry_hash = sha384(&content).to_vec();
+    }
+
+    /// Recompute the hash this entry *should* have, given the hash of its
+    /// predecessor, without mutating it. Used by integrity verification.
+    pub fn expected_hash(&self, prev_hash: &Option<Vec<u8>>) -> Vec<u8> {
+        sha384(&chain_content(self, prev_hash)).to_vec()
+    }
+
     /// Create a debit entry.
     pub fn debit(
         settlement_id: SettlementId,
@@ -57,6 +143,9 @@ impl JournalEntry {
             currency,
             balance_after: Decimal::ZERO,
             created_at: Utc::now(),
+            seq: 0,
+            prev_hash: None,
+            entry_hash: Vec::new(),
         }
     }
 
@@ -78,6 +167,9 @@ impl JournalEntry {
             currency,
             balance_after: Decimal::ZERO,
             created_at: Utc::now(),
+            seq: 0,
+            prev_hash: None,
+            entry_hash: Vec::new(),
         }
     }
 
@@ -95,8 +187,14 @@ impl JournalEntry {
 pub struct JournalBatch {
     /// Entries in the batch.
     pub entries: Vec<JournalEntry>,
+    /// Mint/burn legs in the batch, reconciled against `entries` by
+    /// [`Self::is_balanced`] rather than requiring an offsetting entry.
+    pub supply_entries: Vec<crate::supply::SupplyEntry>,
     /// Settlement ID for the batch.
     pub settlement_id: SettlementId,
+    /// Fees charged by [`Self::apply_fees`], keyed by currency code, kept
+    /// around for reporting after the batch commits.
+    pub fees_collected: HashMap<String, Decimal>,
 }
 
 impl JournalBatch {
@@ -104,7 +202,9 @@ impl JournalBatch {
     pub fn new(settlement_id: SettlementId) -> Self {
         Self {
             entries: Vec::new(),
+            supply_entries: Vec::new(),
             settlement_id,
+            fees_collected: HashMap::new(),
         }
     }
 
@@ -113,9 +213,20 @@ impl JournalBatch {
         self.entries.push(entry);
     }
 
-    /// Verify the batch is balanced (debits == credits per currency).
+    /// Add a mint or burn leg to the batch.
+    pub fn add_supply_entry(&mut self, entry: crate::supply::SupplyEntry) {
+        self.supply_entries.push(entry);
+    }
+
+    /// Verify the batch is balanced. Ordinary entries must net to zero
+    /// debits minus credits per currency, same as always -- but a mint or
+    /// burn leg doesn't need an offsetting entry of its own: per currency,
+    /// `(debits - credits)` must instead equal `(burns - mints)`, since a
+    /// mint conjures the credited side out of thin air and a burn destroys
+    /// the debited side rather than crediting it elsewhere.
     pub fn is_balanced(&self) -> bool {
         use std::collections::HashMap;
+        use crate::supply::SupplyEntryType;
 
         let mut balances: HashMap<String, Decimal> = HashMap::new();
 
@@ -129,6 +240,16 @@ impl JournalBatch {
             *balances.entry(currency).or_insert(Decimal::ZERO) += amount;
         }
 
+        for entry in &self.supply_entries {
+            let currency = entry.currency.code().to_string();
+            let amount = match entry.entry_type {
+                SupplyEntryType::Mint => entry.amount,
+                SupplyEntryType::Burn => -entry.amount,
+            };
+
+            *balances.entry(currency).or_insert(Decimal::ZERO) += amount;
+        }
+
         balances.values().all(|&balance| balance == Decimal::ZERO)
     }
 
@@ -149,6 +270,117 @@ impl JournalBatch {
             .map(|e| e.amount)
             .sum()
     }
+
+    /// Compute `policy`'s fee on this batch's gross debit amount for each
+    /// currency it touches, and append a balanced debit/credit leg for
+    /// each one with a nonzero fee: a debit on that currency's payer --
+    /// the account this batch already debits for it -- and a matching
+    /// credit on `collector`. A currency with no debit leg, or whose
+    /// computed fee is zero, is skipped. Returns the fees charged, also
+    /// recorded on `self.fees_collected` for later reporting.
+    pub fn apply_fees(&mut self, policy: &dyn crate::fee::FeePolicy, collector: &AccountId) -> HashMap<String, Decimal> {
+        let mut gross_by_currency: HashMap<String, (Decimal, AccountId, Currency)> = HashMap::new();
+        for entry in self.entries.iter().filter(|e| e.entry_type == EntryType::Debit) {
+            let code = entry.currency.code().to_string();
+            let slot = gross_by_currency
+                .entry(code)
+                .or_insert_with(|| (Decimal::ZERO, entry.account_id.clone(), entry.currency.clone()));
+            slot.0 += entry.amount;
+        }
+
+        let mut charged = HashMap::new();
+        for (code, (gross, payer, currency)) in gross_by_currency {
+            let fee = policy.compute_fee(gross, &currency);
+            if fee <= Decimal::ZERO {
+                continue;
+            }
+
+            let leg_number = self.entries.len() as u32 + 1;
+            self.add_entry(JournalEntry::debit(self.settlement_id, leg_number, payer, fee, currency.clone()));
+            self.add_entry(JournalEntry::credit(self.settlement_id, leg_number, collector.clone(), fee, currency));
+
+            self.fees_collected.insert(code.clone(), fee);
+            charged.insert(code, fee);
+        }
+        charged
+    }
+}
+
+/// Number of recent settlements retained before the oldest is evicted.
+const DEFAULT_RECENT_SETTLEMENTS_CAPACITY: usize = 4096;
+
+/// Bounded ring of recently committed `SettlementId`s and their outcomes,
+/// modeled on the "recent `last_id` / signature queue" Solana's bank uses
+/// to reject transactions it has already seen. Distinct from
+/// [`crate::status_cache::StatusCache`], which caches the journal entries
+/// produced for an idempotency *key*: this cache answers a narrower
+/// question -- "has this exact `SettlementId` already been committed?" --
+/// in O(1) without needing the entries that were produced, so a
+/// [`JournalBatch`] can be rejected as a duplicate before it's even built.
+/// Lets a coordinator retry a network-delivered batch without risking a
+/// double-posted debit/credit: a given `SettlementId` is committed at most
+/// once even under concurrent duplicate delivery.
+pub struct RecentSettlementCache {
+    capacity: usize,
+    /// Commit order, oldest first, so the oldest can be evicted once the
+    /// ring is full.
+    order: VecDeque<SettlementId>,
+    outcomes: HashMap<SettlementId, (Result<(), String>, DateTime<Utc>)>,
+}
+
+impl RecentSettlementCache {
+    /// Create an empty cache retaining the last
+    /// [`DEFAULT_RECENT_SETTLEMENTS_CAPACITY`] settlements.
+    pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_RECENT_SETTLEMENTS_CAPACITY)
+    }
+
+    /// Create an empty cache retaining the last `capacity` settlements.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            capacity,
+            order: VecDeque::with_capacity(capacity),
+            outcomes: HashMap::with_capacity(capacity),
+        }
+    }
+
+    /// Record `settlement_id`'s commit outcome, evicting the oldest
+    /// recorded settlement first if the ring is already at capacity.
+    /// Re-recording an already-present `settlement_id` overwrites its
+    /// outcome without consuming another ring slot.
+    pub fn record(&mut self, settlement_id: SettlementId, result: Result<(), String>) {
+        if !self.outcomes.contains_key(&settlement_id) {
+            if self.order.len() >= self.capacity {
+                if let Some(oldest) = self.order.pop_front() {
+                    self.outcomes.remove(&oldest);
+                }
+            }
+            self.order.push_back(settlement_id);
+        }
+
+        self.outcomes.insert(settlement_id, (result, Utc::now()));
+    }
+
+    /// Look up `settlement_id`'s previously recorded outcome, if it's
+    /// still within the retained window. A caller about to apply a
+    /// `JournalBatch` should check this first and, on a hit, return the
+    /// cached result instead of re-applying the entries.
+    pub fn status(&self, settlement_id: &SettlementId) -> Option<Result<(), String>> {
+        self.outcomes.get(settlement_id).map(|(result, _)| result.clone())
+    }
+
+    /// Drop every recorded settlement committed before `cutoff`, freeing
+    /// space independent of the capacity-driven eviction in [`Self::record`].
+    pub fn purge_older_than(&mut self, cutoff: DateTime<Utc>) {
+        self.outcomes.retain(|_, (_, committed_at)| *committed_at >= cutoff);
+        self.order.retain(|id| self.outcomes.contains_key(id));
+    }
+}
+
+impl Default for RecentSettlementCache {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 #[cfg(test)]
@@ -202,4 +434,134 @@ mod tests {
 
         assert!(!batch.is_balanced());
     }
+
+    #[test]
+    fn test_mint_leg_balances_without_offsetting_entry() {
+        use crate::supply::SupplyEntry;
+
+        let settlement_id = SettlementId::new();
+        let mut batch = JournalBatch::new(settlement_id);
+        let account_a = AccountId::new(ParticipantId::new("BANK_A"), "12345", "USD");
+
+        batch.add_supply_entry(SupplyEntry::mint(account_a, Decimal::from(1000), Currency::usd()));
+
+        assert!(batch.is_balanced());
+    }
+
+    #[test]
+    fn test_burn_leg_offsets_a_debit_without_a_matching_credit() {
+        use crate::supply::SupplyEntry;
+
+        let settlement_id = SettlementId::new();
+        let mut batch = JournalBatch::new(settlement_id);
+        let account_a = AccountId::new(ParticipantId::new("BANK_A"), "12345", "USD");
+
+        batch.add_entry(JournalEntry::debit(settlement_id, 1, account_a.clone(), Decimal::from(1000), Currency::usd()));
+        batch.add_supply_entry(SupplyEntry::burn(account_a, Decimal::from(1000), Currency::usd()));
+
+        assert!(batch.is_balanced());
+    }
+
+    #[test]
+    fn test_mismatched_mint_and_debit_is_unbalanced() {
+        use crate::supply::SupplyEntry;
+
+        let settlement_id = SettlementId::new();
+        let mut batch = JournalBatch::new(settlement_id);
+        let account_a = AccountId::new(ParticipantId::new("BANK_A"), "12345", "USD");
+
+        batch.add_entry(JournalEntry::debit(settlement_id, 1, account_a.clone(), Decimal::from(1000), Currency::usd()));
+        batch.add_supply_entry(SupplyEntry::mint(account_a, Decimal::from(500), Currency::usd()));
+
+        assert!(!batch.is_balanced());
+    }
+
+    #[test]
+    fn test_apply_fees_appends_balanced_debit_credit_leg() {
+        use crate::fee::FlatFee;
+
+        let settlement_id = SettlementId::new();
+        let mut batch = JournalBatch::new(settlement_id);
+        let account_a = AccountId::new(ParticipantId::new("BANK_A"), "12345", "USD");
+        let account_b = AccountId::new(ParticipantId::new("BANK_B"), "67890", "USD");
+        let collector = AccountId::new(ParticipantId::new("NETWORK"), "fees", "USD");
+
+        batch.add_entry(JournalEntry::debit(settlement_id, 1, account_a, Decimal::from(1000), Currency::usd()));
+        batch.add_entry(JournalEntry::credit(settlement_id, 1, account_b, Decimal::from(1000), Currency::usd()));
+
+        let charged = batch.apply_fees(&FlatFee { amount: Decimal::from(5) }, &collector);
+
+        assert_eq!(charged.get("USD"), Some(&Decimal::from(5)));
+        assert_eq!(batch.fees_collected.get("USD"), Some(&Decimal::from(5)));
+        assert!(batch.is_balanced());
+        assert_eq!(batch.entries.len(), 4);
+    }
+
+    #[test]
+    fn test_apply_fees_skips_zero_fee() {
+        use crate::fee::FlatFee;
+
+        let settlement_id = SettlementId::new();
+        let mut batch = JournalBatch::new(settlement_id);
+        let account_a = AccountId::new(ParticipantId::new("BANK_A"), "12345", "USD");
+        let account_b = AccountId::new(ParticipantId::new("BANK_B"), "67890", "USD");
+        let collector = AccountId::new(ParticipantId::new("NETWORK"), "fees", "USD");
+
+        batch.add_entry(JournalEntry::debit(settlement_id, 1, account_a, Decimal::from(1000), Currency::usd()));
+        batch.add_entry(JournalEntry::credit(settlement_id, 1, account_b, Decimal::from(1000), Currency::usd()));
+
+        let charged = batch.apply_fees(&FlatFee { amount: Decimal::ZERO }, &collector);
+
+        assert!(charged.is_empty());
+        assert_eq!(batch.entries.len(), 2);
+    }
+
+    #[test]
+    fn test_recent_settlement_cache_miss_then_hit_after_record() {
+        let mut cache = RecentSettlementCache::new();
+        let settlement_id = SettlementId::new();
+
+        assert!(cache.status(&settlement_id).is_none());
+
+        cache.record(settlement_id, Ok(()));
+
+        assert_eq!(cache.status(&settlement_id), Some(Ok(())));
+    }
+
+    #[test]
+    fn test_recent_settlement_cache_records_error_outcome() {
+        let mut cache = RecentSettlementCache::new();
+        let settlement_id = SettlementId::new();
+
+        cache.record(settlement_id, Err("insufficient funds".to_string()));
+
+        assert_eq!(cache.status(&settlement_id), Some(Err("insufficient funds".to_string())));
+    }
+
+    #[test]
+    fn test_recent_settlement_cache_evicts_oldest_past_capacity() {
+        let mut cache = RecentSettlementCache::with_capacity(2);
+        let first = SettlementId::new();
+        let second = SettlementId::new();
+        let third = SettlementId::new();
+
+        cache.record(first, Ok(()));
+        cache.record(second, Ok(()));
+        cache.record(third, Ok(()));
+
+        assert!(cache.status(&first).is_none());
+        assert!(cache.status(&second).is_some());
+        assert!(cache.status(&third).is_some());
+    }
+
+    #[test]
+    fn test_recent_settlement_cache_purge_older_than() {
+        let mut cache = RecentSettlementCache::new();
+        let settlement_id = SettlementId::new();
+        cache.record(settlement_id, Ok(()));
+
+        cache.purge_older_than(Utc::now() + chrono::Duration::seconds(1));
+
+        assert!(cache.status(&settlement_id).is_none());
+    }
 }
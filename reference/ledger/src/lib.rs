@@ -6,8 +6,22 @@ pub mod engine;
 pub mod account;
 pub mod journal;
 pub mod balance;
+pub mod error;
+pub mod fx;
+pub mod store;
+pub mod status_cache;
+pub mod lock_manager;
+pub mod supply;
+pub mod fee;
 
 pub use engine::LedgerEngine;
 pub use account::Account;
-pub use journal::{JournalEntry, EntryType};
-pub use balance::AccountBalance;
+pub use journal::{verify_chain, EntryType, JournalEntry, RecentSettlementCache};
+pub use balance::{AccountBalance, BalanceStatus, CheckpointedBalances};
+pub use error::LedgerError;
+pub use fx::{FxRateProvider, Rate, RateTable};
+pub use store::PostgresLedgerStore;
+pub use status_cache::StatusCache;
+pub use lock_manager::{AccountLockManager, LockConflict, LockGuard};
+pub use supply::{SupplyEntry, SupplyEntryType, SupplyLedger};
+pub use fee::{BasisPointsFee, FeePolicy, FeeTier, FlatFee, TieredFee};
@@ -0,0 +1,134 @@
+//! Idempotency cache for settlement recording, modeled on Solana's bank
+//! status cache: a bounded rolling window of "generations", each mapping an
+//! idempotency key to the journal entries it produced. A hit lets
+//! [`crate::engine::LedgerEngine::record_settlement`] short-circuit on a
+//! retried settlement instead of double-posting it; the oldest generation
+//! is evicted once the window is full, so the cache stays bounded rather
+//! than growing for the lifetime of the process.
+
+use std::collections::{HashMap, VecDeque};
+
+use crate::journal::JournalEntry;
+
+/// Number of generations retained before the oldest is evicted.
+const MAX_GENERATIONS: usize = 5;
+
+/// Idempotency keys recorded per generation before it's rolled over and a
+/// fresh one started.
+const ENTRIES_PER_GENERATION: usize = 1024;
+
+/// A bounded rolling window of recently recorded settlement outcomes,
+/// keyed by idempotency key.
+pub struct StatusCache {
+    generations: VecDeque<HashMap<String, Vec<JournalEntry>>>,
+}
+
+impl StatusCache {
+    /// Create an empty cache with a single (current) generation.
+    pub fn new() -> Self {
+        let mut generations = VecDeque::with_capacity(MAX_GENERATIONS);
+        generations.push_back(HashMap::new());
+        Self { generations }
+    }
+
+    /// Look up a previously recorded outcome for `idempotency_key`, if any
+    /// generation still in the window has one.
+    pub fn get(&self, idempotency_key: &str) -> Option<Vec<JournalEntry>> {
+        self.generations
+            .iter()
+            .find_map(|generation| generation.get(idempotency_key).cloned())
+    }
+
+    /// Record `entries` as the outcome for `idempotency_key` in the current
+    /// generation, rolling over to a fresh generation first if the current
+    /// one is full.
+    pub fn insert(&mut self, idempotency_key: String, entries: Vec<JournalEntry>) {
+        let current_len = self
+            .generations
+            .back()
+            .map(|generation| generation.len())
+            .unwrap_or(0);
+
+        if current_len >= ENTRIES_PER_GENERATION {
+            self.roll_generation();
+        }
+
+        self.generations
+            .back_mut()
+            .expect("status cache always has at least one generation")
+            .insert(idempotency_key, entries);
+    }
+
+    /// Start a fresh generation, evicting the oldest once the window is at
+    /// capacity.
+    fn roll_generation(&mut self) {
+        if self.generations.len() >= MAX_GENERATIONS {
+            self.generations.pop_front();
+        }
+        self.generations.push_back(HashMap::new());
+    }
+}
+
+impl Default for StatusCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::EntryType;
+    use atomicsettle_common::{AccountId, Currency, ParticipantId, SettlementId};
+    use rust_decimal::Decimal;
+
+    fn sample_entries() -> Vec<JournalEntry> {
+        vec![JournalEntry::debit(
+            SettlementId::new(),
+            1,
+            AccountId::new(ParticipantId::new("BANK_A"), "12345", "USD"),
+            Decimal::from(1000),
+            Currency::usd(),
+        )]
+    }
+
+    #[test]
+    fn test_miss_then_hit_after_insert() {
+        let mut cache = StatusCache::new();
+        assert!(cache.get("test-key").is_none());
+
+        cache.insert("test-key".to_string(), sample_entries());
+
+        let cached = cache.get("test-key").unwrap();
+        assert_eq!(cached.len(), 1);
+        assert_eq!(cached[0].entry_type, EntryType::Debit);
+    }
+
+    #[test]
+    fn test_oldest_generation_evicted_once_window_is_full() {
+        let mut cache = StatusCache::new();
+        cache.insert("gen-0".to_string(), sample_entries());
+
+        for i in 0..MAX_GENERATIONS {
+            cache.roll_generation();
+            cache.insert(format!("gen-{}", i + 1), sample_entries());
+        }
+
+        assert!(cache.get("gen-0").is_none());
+        assert!(cache.get(&format!("gen-{MAX_GENERATIONS}")).is_some());
+    }
+
+    #[test]
+    fn test_full_generation_rolls_over_automatically() {
+        let mut cache = StatusCache::new();
+        for i in 0..ENTRIES_PER_GENERATION {
+            cache.insert(format!("key-{i}"), sample_entries());
+        }
+        assert_eq!(cache.generations.len(), 1);
+
+        cache.insert("key-overflow".to_string(), sample_entries());
+        assert_eq!(cache.generations.len(), 2);
+        assert!(cache.get("key-0").is_some());
+        assert!(cache.get("key-overflow").is_some());
+    }
+}
@@ -0,0 +1,334 @@
+//! Durable, transactional persistence for the ledger.
+//!
+//! [`crate::engine::LedgerEngine`] keeps its hash-chained journal and
+//! running balances in memory, which is what makes
+//! [`crate::engine::LedgerEngine::verify_integrity`] cheap to run on every
+//! recovery. That in-memory state doesn't survive a process restart on its
+//! own and isn't queryable by anything outside this process, though, so a
+//! [`PostgresLedgerStore`] can be attached via
+//! [`crate::engine::LedgerEngine::with_store`] to additionally write every
+//! entry and balance change through to Postgres, giving reconciliation jobs
+//! and other external tooling a durable, SQL-queryable copy of the same
+//! audit trail.
+//!
+//! Schema, applied by [`PostgresLedgerStore::migrate`]:
+//!
+//! ```sql
+//! CREATE TABLE accounts (
+//!     account_id TEXT PRIMARY KEY,
+//!     currency TEXT NOT NULL,
+//!     available_balance NUMERIC NOT NULL DEFAULT 0,
+//!     locked_balance NUMERIC NOT NULL DEFAULT 0,
+//!     pending_credits NUMERIC NOT NULL DEFAULT 0,
+//!     pending_debits NUMERIC NOT NULL DEFAULT 0,
+//!     updated_at TIMESTAMPTZ NOT NULL DEFAULT now()
+//! );
+//!
+//! CREATE TABLE journal_entries (
+//!     id UUID PRIMARY KEY,
+//!     settlement_id UUID NOT NULL,
+//!     leg_number INTEGER NOT NULL,
+//!     account_id TEXT NOT NULL REFERENCES accounts(account_id),
+//!     entry_type TEXT NOT NULL,
+//!     amount NUMERIC NOT NULL,
+//!     currency TEXT NOT NULL,
+//!     balance_after NUMERIC NOT NULL,
+//!     created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+//! );
+//! ```
+
+use rust_decimal::Decimal;
+use sqlx::{PgPool, Postgres, Row, Transaction};
+
+use atomicsettle_common::{AccountId, AtomicSettleError, Currency, Result, Settlement};
+
+use crate::balance::AccountBalance;
+use crate::journal::{EntryType, JournalEntry};
+
+/// DDL for the `accounts` and `journal_entries` tables, applied by
+/// [`PostgresLedgerStore::migrate`]. Kept inline rather than as a separate
+/// migration file since this is the store's only migration so far.
+const MIGRATIONS: &str = r#"
+CREATE TABLE IF NOT EXISTS accounts (
+    account_id TEXT PRIMARY KEY,
+    currency TEXT NOT NULL,
+    available_balance NUMERIC NOT NULL DEFAULT 0,
+    locked_balance NUMERIC NOT NULL DEFAULT 0,
+    pending_credits NUMERIC NOT NULL DEFAULT 0,
+    pending_debits NUMERIC NOT NULL DEFAULT 0,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE TABLE IF NOT EXISTS journal_entries (
+    id UUID PRIMARY KEY,
+    settlement_id UUID NOT NULL,
+    leg_number INTEGER NOT NULL,
+    account_id TEXT NOT NULL REFERENCES accounts(account_id),
+    entry_type TEXT NOT NULL,
+    amount NUMERIC NOT NULL,
+    currency TEXT NOT NULL,
+    balance_after NUMERIC NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS journal_entries_settlement_id_idx
+    ON journal_entries (settlement_id);
+"#;
+
+/// Postgres-backed ledger persistence. Wraps a connection pool; every
+/// method that mutates balances does so inside a single transaction so a
+/// crash or constraint failure partway through a settlement can never leave
+/// `accounts` and `journal_entries` disagreeing with each other.
+pub struct PostgresLedgerStore {
+    pool: PgPool,
+}
+
+impl PostgresLedgerStore {
+    /// Connect to `database_url` and apply [`MIGRATIONS`].
+    pub async fn connect(database_url: &str) -> Result<Self> {
+        let pool = PgPool::connect(database_url)
+            .await
+            .map_err(|e| AtomicSettleError::DatabaseError(e.to_string()))?;
+
+        let store = Self { pool };
+        store.migrate().await?;
+        Ok(store)
+    }
+
+    /// Wrap an already-established pool, e.g. one shared with another
+    /// component or built in a test with a transient database.
+    pub fn from_pool(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Apply [`MIGRATIONS`]. Safe to call repeatedly -- every statement is
+    /// `IF NOT EXISTS`.
+    pub async fn migrate(&self) -> Result<()> {
+        sqlx::raw_sql(MIGRATIONS)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| AtomicSettleError::DatabaseError(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Ensure a row exists for `account_id` so the first entry against a
+    /// new account doesn't fail the `journal_entries` foreign key.
+    async fn ensure_account(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        account_id: &AccountId,
+        currency: &str,
+    ) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO accounts (account_id, currency) VALUES ($1, $2) \
+             ON CONFLICT (account_id) DO NOTHING",
+        )
+        .bind(account_id.canonical())
+        .bind(currency)
+        .execute(&mut **tx)
+        .await
+        .map_err(|e| AtomicSettleError::DatabaseError(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Apply one journal entry within `tx`: update the account's running
+    /// balance, stamp `balance_after` from the post-update row, and insert
+    /// the entry. Returns the entry with `balance_after` filled in.
+    async fn apply_entry(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        mut entry: JournalEntry,
+    ) -> Result<JournalEntry> {
+        self.ensure_account(tx, &entry.account_id, entry.currency.code()).await?;
+
+        let delta = match entry.entry_type {
+            EntryType::Debit => -entry.amount,
+            EntryType::Credit => entry.amount,
+        };
+
+        let row = sqlx::query(
+            "UPDATE accounts SET available_balance = available_balance + $1, updated_at = now() \
+             WHERE account_id = $2 RETURNING available_balance",
+        )
+        .bind(delta)
+        .bind(entry.account_id.canonical())
+        .fetch_one(&mut **tx)
+        .await
+        .map_err(|e| AtomicSettleError::DatabaseError(e.to_string()))?;
+
+        entry.balance_after = row.try_get("available_balance")
+            .map_err(|e| AtomicSettleError::DatabaseError(e.to_string()))?;
+
+        sqlx::query(
+            "INSERT INTO journal_entries \
+             (id, settlement_id, leg_number, account_id, entry_type, amount, currency, balance_after, created_at) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)",
+        )
+        .bind(entry.id)
+        .bind(entry.settlement_id.to_string())
+        .bind(entry.leg_number as i32)
+        .bind(entry.account_id.canonical())
+        .bind(match entry.entry_type {
+            EntryType::Debit => "debit",
+            EntryType::Credit => "credit",
+        })
+        .bind(entry.amount)
+        .bind(entry.currency.code())
+        .bind(entry.balance_after)
+        .bind(entry.created_at)
+        .execute(&mut **tx)
+        .await
+        .map_err(|e| AtomicSettleError::DatabaseError(e.to_string()))?;
+
+        Ok(entry)
+    }
+
+    /// Durably record a settlement's debit/credit entries in one
+    /// transaction: insert every journal row, update each affected
+    /// account's running balance, and verify the whole batch is balanced
+    /// per-currency before committing. Rolls back and returns an error on
+    /// any mismatch rather than leaving a partial settlement persisted.
+    pub async fn record_settlement(&self, settlement: &Settlement) -> Result<Vec<JournalEntry>> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| AtomicSettleError::DatabaseError(e.to_string()))?;
+
+        let mut entries = Vec::new();
+        for leg in &settlement.legs {
+            let debit_entry = JournalEntry::debit(
+                settlement.id,
+                leg.leg_number,
+                leg.from_account.clone(),
+                leg.amount.value,
+                leg.amount.currency.clone(),
+            );
+            entries.push(self.apply_entry(&mut tx, debit_entry).await?);
+
+            let amount = leg.converted_amount.as_ref().unwrap_or(&leg.amount);
+            let credit_entry = JournalEntry::credit(
+                settlement.id,
+                leg.leg_number,
+                leg.to_account.clone(),
+                amount.value,
+                amount.currency.clone(),
+            );
+            entries.push(self.apply_entry(&mut tx, credit_entry).await?);
+        }
+
+        if let Some((currency, net)) = unbalanced_currency(&entries) {
+            tx.rollback()
+                .await
+                .map_err(|e| AtomicSettleError::DatabaseError(e.to_string()))?;
+            return Err(AtomicSettleError::DatabaseError(format!(
+                "settlement {} unbalanced: net {} {}",
+                settlement.id, net, currency
+            )));
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| AtomicSettleError::DatabaseError(e.to_string()))?;
+        Ok(entries)
+    }
+
+    /// Lock funds: `available_balance -= amount`, `locked_balance +=
+    /// amount`, conditioned on `available_balance >= amount` so an
+    /// overdrawing lock attempt fails the `UPDATE` outright rather than
+    /// racing another lock on the same account.
+    pub async fn lock_funds(&self, account_id: &AccountId, amount: Decimal) -> Result<()> {
+        let result = sqlx::query(
+            "UPDATE accounts SET available_balance = available_balance - $1, \
+             locked_balance = locked_balance + $1, updated_at = now() \
+             WHERE account_id = $2 AND available_balance >= $1",
+        )
+        .bind(amount)
+        .bind(account_id.canonical())
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AtomicSettleError::DatabaseError(e.to_string()))?;
+
+        if result.rows_affected() == 0 {
+            let balance = self.get_balance(account_id).await?;
+            return Err(AtomicSettleError::InsufficientFunds {
+                required: amount.to_string(),
+                available: balance.balance.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Unlock funds: `available_balance += amount`, `locked_balance -=
+    /// amount`. Unconditional -- a lock being released always had the
+    /// corresponding amount moved into `locked_balance` in the first place.
+    pub async fn unlock_funds(&self, account_id: &AccountId, amount: Decimal) -> Result<()> {
+        sqlx::query(
+            "UPDATE accounts SET available_balance = available_balance + $1, \
+             locked_balance = locked_balance - $1, updated_at = now() \
+             WHERE account_id = $2",
+        )
+        .bind(amount)
+        .bind(account_id.canonical())
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AtomicSettleError::DatabaseError(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Read an account's current balance row.
+    pub async fn get_balance(&self, account_id: &AccountId) -> Result<AccountBalance> {
+        let row = sqlx::query(
+            "SELECT currency, available_balance, locked_balance, pending_credits, \
+             pending_debits, updated_at FROM accounts WHERE account_id = $1",
+        )
+        .bind(account_id.canonical())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AtomicSettleError::DatabaseError(e.to_string()))?;
+
+        let Some(row) = row else {
+            return Ok(AccountBalance::zero(
+                account_id.clone(),
+                Currency::new(&account_id.currency),
+            ));
+        };
+
+        let currency: String = row.try_get("currency")
+            .map_err(|e| AtomicSettleError::DatabaseError(e.to_string()))?;
+
+        Ok(AccountBalance {
+            account_id: account_id.clone(),
+            currency: Currency::new(&currency),
+            balance: row.try_get("available_balance")
+                .map_err(|e| AtomicSettleError::DatabaseError(e.to_string()))?,
+            locked_balance: row.try_get("locked_balance")
+                .map_err(|e| AtomicSettleError::DatabaseError(e.to_string()))?,
+            pending_credits: row.try_get("pending_credits")
+                .map_err(|e| AtomicSettleError::DatabaseError(e.to_string()))?,
+            pending_debits: row.try_get("pending_debits")
+                .map_err(|e| AtomicSettleError::DatabaseError(e.to_string()))?,
+            updated_at: row.try_get("updated_at")
+                .map_err(|e| AtomicSettleError::DatabaseError(e.to_string()))?,
+        })
+    }
+}
+
+/// Find the first currency (if any) whose net debits/credits don't cancel
+/// out across `entries`, for `record_settlement`'s pre-commit check.
+fn unbalanced_currency(entries: &[JournalEntry]) -> Option<(String, Decimal)> {
+    use std::collections::HashMap;
+
+    let mut net_by_currency: HashMap<String, Decimal> = HashMap::new();
+    for entry in entries {
+        let delta = match entry.entry_type {
+            EntryType::Debit => entry.amount,
+            EntryType::Credit => -entry.amount,
+        };
+        *net_by_currency
+            .entry(entry.currency.code().to_string())
+            .or_insert(Decimal::ZERO) += delta;
+    }
+
+    net_by_currency.into_iter().find(|(_, net)| !net.is_zero())
+}
@@ -0,0 +1,174 @@
+//! Currency supply tracking for mint/burn settlement legs.
+//!
+//! Ordinary `JournalEntry` debits and credits move value between accounts
+//! without changing how much of a currency exists in total. Tokenized
+//! deposits and stablecoin settlement legs need the other half of that
+//! story -- minting and burning -- so this module adds [`SupplyEntry`], a
+//! companion to `JournalEntry` carried alongside a `JournalBatch`'s
+//! ordinary entries, and [`SupplyLedger`], which tracks each currency's
+//! running total issuance the way orml-tokens' `TotalIssuance` storage
+//! item does.
+
+use std::collections::HashMap;
+
+use atomicsettle_common::{AccountId, Currency};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{LedgerError, Result};
+
+/// Whether a [`SupplyEntry`] increases or decreases its currency's total
+/// issuance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SupplyEntryType {
+    /// Credits `account_id`'s balance and increases total issuance.
+    Mint,
+    /// Debits `account_id`'s balance and decreases total issuance.
+    Burn,
+}
+
+/// A mint or burn leg against a single account and currency, carried
+/// alongside a `JournalBatch`'s ordinary debit/credit entries. Reconciled
+/// by `JournalBatch::is_balanced` against the batch's net debit/credit per
+/// currency rather than needing an offsetting entry of its own.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SupplyEntry {
+    /// Account whose balance this leg credits (mint) or debits (burn).
+    pub account_id: AccountId,
+    /// Whether this leg mints or burns.
+    pub entry_type: SupplyEntryType,
+    /// Amount minted or burned.
+    pub amount: Decimal,
+    /// Currency affected.
+    pub currency: Currency,
+}
+
+impl SupplyEntry {
+    /// Create a mint leg: credits `account_id` and increases issuance.
+    pub fn mint(account_id: AccountId, amount: Decimal, currency: Currency) -> Self {
+        Self {
+            account_id,
+            entry_type: SupplyEntryType::Mint,
+            amount,
+            currency,
+        }
+    }
+
+    /// Create a burn leg: debits `account_id` and decreases issuance.
+    pub fn burn(account_id: AccountId, amount: Decimal, currency: Currency) -> Self {
+        Self {
+            account_id,
+            entry_type: SupplyEntryType::Burn,
+            amount,
+            currency,
+        }
+    }
+}
+
+/// Tracks each currency's total issuance across every mint and burn leg
+/// applied to it. Elastic-supply instruments (tokenized deposits,
+/// stablecoins) mint into and burn out of circulation through this ledger
+/// instead of an offsetting debit/credit pair.
+#[derive(Debug, Clone, Default)]
+pub struct SupplyLedger {
+    issuance: HashMap<String, Decimal>,
+}
+
+impl SupplyLedger {
+    /// Create a supply ledger with zero issuance for every currency.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Read `currency`'s total issuance, zero if it's never been minted.
+    pub fn total_issuance(&self, currency: &Currency) -> Decimal {
+        self.issuance.get(currency.code()).copied().unwrap_or(Decimal::ZERO)
+    }
+
+    /// Apply a single [`SupplyEntry`]: increase issuance for a mint, or
+    /// decrease it for a burn. Errors with
+    /// [`LedgerError::InsufficientIssuance`] rather than letting a
+    /// currency's total issuance go negative.
+    pub fn apply_entry(&mut self, entry: &SupplyEntry) -> Result<()> {
+        match entry.entry_type {
+            SupplyEntryType::Mint => {
+                *self.issuance.entry(entry.currency.code().to_string()).or_insert(Decimal::ZERO) += entry.amount;
+                Ok(())
+            }
+            SupplyEntryType::Burn => {
+                let available = self.total_issuance(&entry.currency);
+                if available < entry.amount {
+                    return Err(LedgerError::InsufficientIssuance {
+                        currency: entry.currency.code().to_string(),
+                        required: entry.amount.to_string(),
+                        available: available.to_string(),
+                    });
+                }
+                self.issuance.insert(entry.currency.code().to_string(), available - entry.amount);
+                Ok(())
+            }
+        }
+    }
+
+    /// Apply every supply entry in `batch`, in order. Issuance guards only
+    /// ever reject a burn that would go negative -- never a mint -- and the
+    /// first rejected burn stops the batch there. Unlike
+    /// [`crate::balance::CheckpointedBalances::apply_batch`], nothing is
+    /// rolled back: entries applied before the rejected burn keep their
+    /// issuance change.
+    pub fn apply_batch(&mut self, batch: &crate::journal::JournalBatch) -> Result<()> {
+        for entry in &batch.supply_entries {
+            self.apply_entry(entry)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use atomicsettle_common::ParticipantId;
+
+    #[test]
+    fn test_mint_increases_total_issuance() {
+        let mut ledger = SupplyLedger::new();
+        let account = AccountId::new(ParticipantId::new("BANK_A"), "12345", "USD");
+
+        ledger.apply_entry(&SupplyEntry::mint(account, Decimal::from(1000), Currency::usd())).unwrap();
+
+        assert_eq!(ledger.total_issuance(&Currency::usd()), Decimal::from(1000));
+    }
+
+    #[test]
+    fn test_burn_decreases_total_issuance() {
+        let mut ledger = SupplyLedger::new();
+        let account = AccountId::new(ParticipantId::new("BANK_A"), "12345", "USD");
+
+        ledger.apply_entry(&SupplyEntry::mint(account.clone(), Decimal::from(1000), Currency::usd())).unwrap();
+        ledger.apply_entry(&SupplyEntry::burn(account, Decimal::from(400), Currency::usd())).unwrap();
+
+        assert_eq!(ledger.total_issuance(&Currency::usd()), Decimal::from(600));
+    }
+
+    #[test]
+    fn test_burn_below_zero_issuance_rejected() {
+        let mut ledger = SupplyLedger::new();
+        let account = AccountId::new(ParticipantId::new("BANK_A"), "12345", "USD");
+
+        let result = ledger.apply_entry(&SupplyEntry::burn(account, Decimal::from(100), Currency::usd()));
+
+        assert!(matches!(result, Err(LedgerError::InsufficientIssuance { .. })));
+        assert_eq!(ledger.total_issuance(&Currency::usd()), Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_issuance_tracked_independently_per_currency() {
+        let mut ledger = SupplyLedger::new();
+        let account = AccountId::new(ParticipantId::new("BANK_A"), "12345", "USD");
+
+        ledger.apply_entry(&SupplyEntry::mint(account.clone(), Decimal::from(500), Currency::usd())).unwrap();
+
+        assert_eq!(ledger.total_issuance(&Currency::usd()), Decimal::from(500));
+        assert_eq!(ledger.total_issuance(&Currency::eur()), Decimal::ZERO);
+    }
+}
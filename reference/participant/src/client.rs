@@ -6,13 +6,14 @@ use tokio::sync::{mpsc, RwLock};
 use tracing::{info, warn, instrument};
 
 use atomicsettle_common::{
-    AtomicSettleError, Balance, Currency, Money, ParticipantId, Result, Settlement,
-    SettlementId, SettlementStatus,
+    AtomicSettleError, Balance, Currency, Money, ParticipantId, PaymentPreimage, Result,
+    Settlement, SettlementId, SettlementStatus,
 };
 
 use crate::config::ParticipantConfig;
 use crate::connection::CoordinatorConnection;
 use crate::handler::SettlementHandler;
+use crate::swap::AtomicSwapLeg;
 
 /// Request to send a settlement.
 #[derive(Debug, Clone)]
@@ -89,6 +90,7 @@ impl ParticipantClient {
             self.config.coordinator_url.clone(),
             self.participant_id.clone(),
             self.config.protocol_version.clone(),
+            self.config.transport.clone(),
         )
         .await?;
 
@@ -175,6 +177,69 @@ impl ParticipantClient {
         Ok(settlement)
     }
 
+    /// Send a two-leg cross-currency atomic swap: `leg_a` and `leg_b`
+    /// either both settle or both abort. `leg_b`'s timeout must be
+    /// strictly before `leg_a`'s, the same staggered-timeout invariant
+    /// [`crate::swap::CrossCurrencySwap`] enforces, so whichever side
+    /// reveals the secret second still has time to claim before its own
+    /// leg expires. The returned [`Settlement`] carries both legs and
+    /// only reaches `SettlementStatus::Settled` once the coordinator has
+    /// driven both through `SettlementStatus::HtlcLocked` to commit.
+    #[instrument(skip(self, leg_a, leg_b))]
+    pub async fn send_atomic_swap(
+        &self,
+        leg_a: AtomicSwapLeg,
+        leg_b: AtomicSwapLeg,
+    ) -> Result<Settlement> {
+        // Verify connected
+        if *self.state.read().await != ClientState::Connected {
+            return Err(AtomicSettleError::NetworkError(
+                "Not connected to coordinator".to_string(),
+            ));
+        }
+
+        if leg_b.timeout >= leg_a.timeout {
+            return Err(AtomicSettleError::InvalidMessage {
+                message: format!(
+                    "swap leg B timeout ({}) must be strictly before leg A timeout ({})",
+                    leg_b.timeout, leg_a.timeout
+                ),
+                field: Some("leg_b.timeout".to_string()),
+            });
+        }
+
+        let preimage = PaymentPreimage::random();
+        let idempotency_key = uuid::Uuid::new_v4().to_string();
+
+        info!(
+            participant_id = %self.participant_id,
+            leg_a_to = %leg_a.to_participant,
+            leg_b_to = %leg_b.to_participant,
+            payment_hash = %preimage.hash(),
+            idempotency_key = %idempotency_key,
+            "Sending atomic swap request"
+        );
+
+        // Get connection
+        let connection_guard = self.connection.read().await;
+        let connection = connection_guard
+            .as_ref()
+            .ok_or(AtomicSettleError::NetworkError("No connection".to_string()))?;
+
+        // Send request via connection
+        let settlement = connection
+            .send_atomic_swap_request(leg_a, leg_b, preimage, idempotency_key)
+            .await?;
+
+        info!(
+            settlement_id = %settlement.id,
+            status = ?settlement.status,
+            "Atomic swap initiated"
+        );
+
+        Ok(settlement)
+    }
+
     /// Get current balance for a currency.
     pub async fn get_balance(&self, currency: Currency) -> Result<Balance> {
         // Verify connected
@@ -234,8 +299,14 @@ impl ParticipantClient {
         let connection = self.connection.clone();
         let state = self.state.clone();
         let interval = self.config.heartbeat_interval;
+        let failure_tolerance = self.config.transport.heartbeat_failure_tolerance();
 
         tokio::spawn(async move {
+            // A SOCKS5/Tor-proxied stream resets occasionally under normal
+            // operation, so tolerate a short run of consecutive failures
+            // before giving up rather than breaking on the first one.
+            let mut consecutive_failures = 0usize;
+
             loop {
                 tokio::time::sleep(interval).await;
 
@@ -244,8 +315,15 @@ impl ParticipantClient {
                 }
 
                 if let Some(conn) = connection.read().await.as_ref() {
-                    if let Err(e) = conn.send_heartbeat().await {
-                        warn!(error = %e, "Heartbeat failed");
+                    match conn.send_heartbeat().await {
+                        Ok(()) => consecutive_failures = 0,
+                        Err(e) => {
+                            consecutive_failures += 1;
+                            warn!(error = %e, consecutive_failures, "Heartbeat failed");
+                            if consecutive_failures >= failure_tolerance {
+                                break;
+                            }
+                        }
                     }
                 }
             }
@@ -315,6 +393,22 @@ impl ParticipantClientBuilder {
         self
     }
 
+    /// Route the connection through a SOCKS5 proxy at `addr` instead of
+    /// dialing `coordinator_url` directly.
+    pub fn socks_proxy(mut self, addr: impl Into<String>) -> Self {
+        self.config.transport = crate::config::Transport::Socks5 {
+            proxy_addr: addr.into(),
+        };
+        self
+    }
+
+    /// Route the connection over Tor, treating `coordinator_url` as an
+    /// onion-service address reached through the local Tor SOCKS5 proxy.
+    pub fn tor(mut self) -> Self {
+        self.config.transport = crate::config::Transport::Tor { socks_proxy: None };
+        self
+    }
+
     /// Set settlement handler.
     pub fn settlement_handler(mut self, handler: Arc<dyn SettlementHandler>) -> Self {
         self.settlement_handler = Some(handler);
@@ -2,6 +2,63 @@
 
 use std::time::Duration;
 
+/// How the participant client reaches the coordinator, the way the
+/// coinswap directory client dials over SOCKS rather than a raw
+/// `TcpStream` so banks in privacy-sensitive or censored environments can
+/// still reach it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Transport {
+    /// Dial `coordinator_url` directly.
+    Direct,
+    /// Dial `coordinator_url` through a SOCKS5 proxy listening at
+    /// `proxy_addr`.
+    Socks5 { proxy_addr: String },
+    /// Dial `coordinator_url` as an onion-service address through a local
+    /// Tor SOCKS5 proxy. Defaults to Tor's standard local SOCKS port when
+    /// `socks_proxy` isn't set.
+    Tor { socks_proxy: Option<String> },
+}
+
+impl Transport {
+    /// Default local SOCKS port the Tor daemon listens on.
+    const DEFAULT_TOR_SOCKS_PROXY: &'static str = "127.0.0.1:9050";
+
+    /// Whether this transport routes through a proxy rather than dialing
+    /// directly.
+    pub fn is_proxied(&self) -> bool {
+        !matches!(self, Transport::Direct)
+    }
+
+    /// SOCKS5 proxy address to dial through, if any.
+    pub fn socks_proxy_addr(&self) -> Option<&str> {
+        match self {
+            Transport::Direct => None,
+            Transport::Socks5 { proxy_addr } => Some(proxy_addr),
+            Transport::Tor { socks_proxy } => {
+                Some(socks_proxy.as_deref().unwrap_or(Self::DEFAULT_TOR_SOCKS_PROXY))
+            }
+        }
+    }
+
+    /// How many consecutive heartbeat failures to tolerate before giving
+    /// up on the connection. A proxied (SOCKS5/Tor) stream resets
+    /// occasionally under normal operation; a direct connection's first
+    /// failure is already meaningful.
+    pub fn heartbeat_failure_tolerance(&self) -> usize {
+        if self.is_proxied() {
+            3
+        } else {
+            1
+        }
+    }
+}
+
+impl Default for Transport {
+    fn default() -> Self {
+        Transport::Direct
+    }
+}
+
 /// Configuration for participant client.
 #[derive(Debug, Clone)]
 pub struct ParticipantConfig {
@@ -9,6 +66,8 @@ pub struct ParticipantConfig {
     pub coordinator_url: String,
     /// Protocol version to use.
     pub protocol_version: String,
+    /// Transport used to reach the coordinator.
+    pub transport: Transport,
     /// Path to client certificate.
     pub cert_path: Option<String>,
     /// Path to client private key.
@@ -36,6 +95,7 @@ impl Default for ParticipantConfig {
         Self {
             coordinator_url: "https://coordinator.atomicsettle.local:8080".to_string(),
             protocol_version: "1.0".to_string(),
+            transport: Transport::Direct,
             cert_path: None,
             key_path: None,
             ca_cert_path: None,
@@ -71,6 +131,14 @@ impl ParticipantConfig {
             config.ca_cert_path = Some(ca);
         }
 
+        if std::env::var("COORDINATOR_USE_TOR").is_ok() {
+            config.transport = Transport::Tor {
+                socks_proxy: std::env::var("COORDINATOR_SOCKS_PROXY").ok(),
+            };
+        } else if let Ok(proxy_addr) = std::env::var("COORDINATOR_SOCKS_PROXY") {
+            config.transport = Transport::Socks5 { proxy_addr };
+        }
+
         config
     }
 
@@ -87,3 +155,33 @@ impl ParticipantConfig {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_direct_transport_is_not_proxied() {
+        let transport = Transport::Direct;
+        assert!(!transport.is_proxied());
+        assert_eq!(transport.socks_proxy_addr(), None);
+        assert_eq!(transport.heartbeat_failure_tolerance(), 1);
+    }
+
+    #[test]
+    fn test_socks5_transport_uses_configured_proxy() {
+        let transport = Transport::Socks5 {
+            proxy_addr: "127.0.0.1:9150".to_string(),
+        };
+        assert!(transport.is_proxied());
+        assert_eq!(transport.socks_proxy_addr(), Some("127.0.0.1:9150"));
+        assert_eq!(transport.heartbeat_failure_tolerance(), 3);
+    }
+
+    #[test]
+    fn test_tor_transport_falls_back_to_default_socks_port() {
+        let transport = Transport::Tor { socks_proxy: None };
+        assert!(transport.is_proxied());
+        assert_eq!(transport.socks_proxy_addr(), Some("127.0.0.1:9050"));
+    }
+}
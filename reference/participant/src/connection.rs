@@ -1,11 +1,13 @@
 //! Connection to coordinator.
 
 use atomicsettle_common::{
-    AtomicSettleError, Balance, Currency, Money, ParticipantId, Result, Settlement,
-    SettlementId,
+    AtomicSettleError, Balance, Currency, Money, ParticipantId, PaymentPreimage, Result,
+    Settlement, SettlementId,
 };
 
+use crate::config::Transport;
 use crate::handler::IncomingMessage;
+use crate::swap::AtomicSwapLeg;
 
 /// Connection to the coordinator.
 pub struct CoordinatorConnection {
@@ -15,30 +17,42 @@ pub struct CoordinatorConnection {
     participant_id: ParticipantId,
     /// Protocol version.
     protocol_version: String,
+    /// Transport used to reach `url` (direct, SOCKS5, or Tor).
+    transport: Transport,
     /// Connection state.
     connected: bool,
 }
 
 impl CoordinatorConnection {
-    /// Create a new connection.
+    /// Create a new connection, dialing `url` through `transport`.
     pub async fn new(
         url: String,
         participant_id: ParticipantId,
         protocol_version: String,
+        transport: Transport,
     ) -> Result<Self> {
         // In a real implementation, this would:
-        // 1. Establish TLS connection
-        // 2. Perform handshake
-        // 3. Authenticate with certificate
+        // 1. If `transport` is `Socks5`/`Tor`, open the TCP stream via a
+        //    SOCKS5 CONNECT handshake against `transport.socks_proxy_addr()`
+        //    instead of dialing `url` directly
+        // 2. Establish TLS connection
+        // 3. Perform handshake
+        // 4. Authenticate with certificate
 
         Ok(Self {
             url,
             participant_id,
             protocol_version,
+            transport,
             connected: true,
         })
     }
 
+    /// Transport this connection was dialed through.
+    pub fn transport(&self) -> &Transport {
+        &self.transport
+    }
+
     /// Close the connection.
     pub async fn close(&self) -> Result<()> {
         // In a real implementation, send disconnect message and close socket
@@ -80,6 +94,38 @@ impl CoordinatorConnection {
         ))
     }
 
+    /// Send a two-leg cross-currency atomic swap request. The coordinator
+    /// locks both legs behind `preimage`'s payment hash and drives the
+    /// combined settlement through `SettlementStatus::HtlcLocked` once
+    /// both legs are locked, on to `Settled` once the preimage is
+    /// verified and both legs are claimed, or to
+    /// `HtlcRefunding`/`HtlcRefunded` if a leg's deadline passes
+    /// unclaimed.
+    pub async fn send_atomic_swap_request(
+        &self,
+        leg_a: AtomicSwapLeg,
+        leg_b: AtomicSwapLeg,
+        preimage: PaymentPreimage,
+        idempotency_key: String,
+    ) -> Result<Settlement> {
+        if !self.connected {
+            return Err(AtomicSettleError::NetworkError("Not connected".to_string()));
+        }
+
+        // In a real implementation:
+        // 1. Build a two-leg atomic swap request message carrying both
+        //    legs, the shared payment hash, and the preimage
+        // 2. Sign message
+        // 3. Send to coordinator, which locks both legs and drives them
+        //    through HtlcLocked to Settled (or HtlcRefunding/HtlcRefunded)
+        // 4. Wait for response
+
+        // Placeholder: Return mock settlement
+        Err(AtomicSettleError::InternalError(
+            "Connection not implemented".to_string(),
+        ))
+    }
+
     /// Query balance for a currency.
     pub async fn query_balance(&self, currency: Currency) -> Result<Balance> {
         if !self.connected {
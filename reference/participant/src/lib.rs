@@ -7,6 +7,7 @@ pub mod client;
 pub mod config;
 pub mod connection;
 pub mod handler;
+pub mod swap;
 
 pub use client::ParticipantClient;
 pub use config::ParticipantConfig;
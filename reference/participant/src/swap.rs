@@ -0,0 +1,669 @@
+//! Cross-currency atomic swaps (HTLC-style).
+//!
+//! Two participants holding different currencies swap atomically, the way
+//! cross-chain bridges lock funds behind a shared secret. The initiator
+//! picks a random secret `s`, computes `H = sha256(s)`, and opens leg A
+//! (its own currency) locked to `H` with timeout `T1`. Upon seeing leg A
+//! locked, the counterparty opens leg B (FX-converted into its currency)
+//! locked to the *same* `H` with a strictly shorter timeout `T2 < T1`. The
+//! initiator claims leg B by revealing `s`, which exposes the preimage on
+//! the shared ledger; the counterparty then uses the now-public `s` to
+//! claim leg A before `T1`. If either side stalls, its counterpart refunds
+//! after its own deadline passes.
+//!
+//! The staggered timeout is the safety invariant: it guarantees the party
+//! who learns the secret second still has time left to claim before its
+//! own leg expires, so `propose` rejects any pairing where `T2 >= T1`.
+
+use atomicsettle_common::{
+    AtomicSettleError, FxRate, Money, ParticipantId, PaymentHash, PaymentPreimage, Result,
+    SettlementId, Timestamp,
+};
+use atomicsettle_crypto::{EncryptedSignature, EncryptionPoint, Signature};
+
+use crate::connection::CoordinatorConnection;
+
+/// Lifecycle of a single HTLC-locked leg.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwapState {
+    /// Not yet locked on the ledger.
+    Proposed,
+    /// Locked behind a payment hash until its deadline.
+    Locked,
+    /// Claimed by revealing the matching preimage.
+    Claimed,
+    /// Refunded after its deadline passed unclaimed.
+    Refunded,
+}
+
+/// One leg of a cross-currency atomic swap: funds locked behind a shared
+/// payment hash until `claim`ed with the matching preimage or `refund`ed
+/// after its deadline.
+#[derive(Debug, Clone)]
+pub struct HtlcLock {
+    settlement_id: Option<SettlementId>,
+    hash: Option<PaymentHash>,
+    deadline: Option<Timestamp>,
+    state: SwapState,
+    revealed_preimage: Option<PaymentPreimage>,
+}
+
+impl HtlcLock {
+    /// Create a leg that has not yet been locked.
+    pub fn new() -> Self {
+        Self {
+            settlement_id: None,
+            hash: None,
+            deadline: None,
+            state: SwapState::Proposed,
+            revealed_preimage: None,
+        }
+    }
+
+    /// Current state.
+    pub fn state(&self) -> SwapState {
+        self.state
+    }
+
+    /// Payment hash this leg is locked to, once locked.
+    pub fn hash(&self) -> Option<PaymentHash> {
+        self.hash
+    }
+
+    /// Deadline after which an unclaimed lock can be refunded.
+    pub fn deadline(&self) -> Option<Timestamp> {
+        self.deadline
+    }
+
+    /// Preimage revealed by a successful `claim`, if any.
+    pub fn revealed_preimage(&self) -> Option<&PaymentPreimage> {
+        self.revealed_preimage.as_ref()
+    }
+
+    /// Lock the settlement behind `hash`, claimable only by revealing its
+    /// preimage, until `deadline`.
+    pub fn lock(&mut self, settlement_id: SettlementId, hash: PaymentHash, deadline: Timestamp) -> Result<()> {
+        if self.state != SwapState::Proposed {
+            return Err(AtomicSettleError::InvalidMessage {
+                message: format!("cannot lock an HTLC leg in state {:?}", self.state),
+                field: Some("state".to_string()),
+            });
+        }
+
+        self.settlement_id = Some(settlement_id);
+        self.hash = Some(hash);
+        self.deadline = Some(deadline);
+        self.state = SwapState::Locked;
+        Ok(())
+    }
+
+    /// Claim the locked leg by revealing `preimage`. Fails if the leg isn't
+    /// locked, the preimage doesn't hash to the locked `H`, or the deadline
+    /// has already passed.
+    pub fn claim(&mut self, preimage: PaymentPreimage) -> Result<()> {
+        if self.state != SwapState::Locked {
+            return Err(AtomicSettleError::InvalidMessage {
+                message: format!("cannot claim an HTLC leg in state {:?}", self.state),
+                field: Some("state".to_string()),
+            });
+        }
+
+        let hash = self.hash.expect("locked state always carries a hash");
+        if !hash.is_satisfied_by(&preimage) {
+            return Err(AtomicSettleError::InvalidSignature(
+                "preimage does not match the locked payment hash".to_string(),
+            ));
+        }
+
+        let deadline = self.deadline.expect("locked state always carries a deadline");
+        if atomicsettle_common::time::is_expired(deadline) {
+            let settlement_id = self.settlement_id.expect("locked state always carries a settlement id");
+            return Err(AtomicSettleError::LockExpired(settlement_id));
+        }
+
+        self.revealed_preimage = Some(preimage);
+        self.state = SwapState::Claimed;
+        Ok(())
+    }
+
+    /// Refund the locked leg once its deadline has passed unclaimed.
+    pub fn refund(&mut self) -> Result<()> {
+        if self.state != SwapState::Locked {
+            return Err(AtomicSettleError::InvalidMessage {
+                message: format!("cannot refund an HTLC leg in state {:?}", self.state),
+                field: Some("state".to_string()),
+            });
+        }
+
+        let deadline = self.deadline.expect("locked state always carries a deadline");
+        if !atomicsettle_common::time::is_expired(deadline) {
+            return Err(AtomicSettleError::InvalidMessage {
+                message: "cannot refund an HTLC leg before its deadline".to_string(),
+                field: Some("deadline".to_string()),
+            });
+        }
+
+        self.state = SwapState::Refunded;
+        Ok(())
+    }
+}
+
+impl Default for HtlcLock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Lifecycle of a single adaptor-signature-locked leg ([`AdaptorLock`]).
+/// Unlike [`SwapState`], which locks funds behind a hash/preimage pair, an
+/// adaptor leg is claimed by completing an [`EncryptedSignature`] with a
+/// scalar the counterparty reveals by broadcasting its own completed
+/// signature -- so there is no single "the deadline passed" state. Instead
+/// there are two timelock safety states: `CancelTimelock`, before the leg
+/// is ever locked, if the counterparty stalls and never locks its side in
+/// time; and `RefundTimelock`, after the leg is locked, if the counterparty
+/// never completes its signature (and so never reveals the scalar) before
+/// the refund deadline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AdaptorLockState {
+    /// Not yet locked on the ledger.
+    Proposed,
+    /// Locked behind an encrypted signature until its refund deadline.
+    Locked,
+    /// Claimed by completing the encrypted signature.
+    Completed,
+    /// Cancelled because the counterparty never locked its side before
+    /// the cancel deadline.
+    CancelTimelock,
+    /// Refunded because the counterparty never completed its encrypted
+    /// signature before the refund deadline.
+    RefundTimelock,
+}
+
+/// One leg of an adaptor-signature atomic swap: funds locked behind an
+/// [`EncryptedSignature`] over a shared [`EncryptionPoint`] until
+/// `complete`d with the matching completed [`Signature`] or `refund`ed
+/// after its deadline, the scriptless-script analogue of [`HtlcLock`].
+/// Mirrors the `xmr-btc` swap construction: rather than a single ledger
+/// deadline, a stalled counterparty is handled by two thresholds -- cancel
+/// before locking, refund after.
+#[derive(Debug, Clone)]
+pub struct AdaptorLock {
+    settlement_id: Option<SettlementId>,
+    encryption_point: Option<EncryptionPoint>,
+    encrypted_signature: Option<EncryptedSignature>,
+    cancel_deadline: Timestamp,
+    refund_deadline: Option<Timestamp>,
+    state: AdaptorLockState,
+    completed_signature: Option<Signature>,
+}
+
+impl AdaptorLock {
+    /// Create a leg that has not yet been locked. `cancel_deadline` is how
+    /// long to wait for the counterparty to lock its side before giving up
+    /// and cancelling.
+    pub fn new(cancel_deadline: Timestamp) -> Self {
+        Self {
+            settlement_id: None,
+            encryption_point: None,
+            encrypted_signature: None,
+            cancel_deadline,
+            refund_deadline: None,
+            state: AdaptorLockState::Proposed,
+            completed_signature: None,
+        }
+    }
+
+    /// Current state.
+    pub fn state(&self) -> AdaptorLockState {
+        self.state
+    }
+
+    /// Completed signature revealed by a successful `complete`, if any --
+    /// the scalar behind it can be recovered with
+    /// [`atomicsettle_crypto::recover_scalar`] to complete the other leg.
+    pub fn completed_signature(&self) -> Option<&Signature> {
+        self.completed_signature.as_ref()
+    }
+
+    /// Lock the settlement behind `encrypted_signature`, claimable only by
+    /// completing it, until `refund_deadline`.
+    pub fn lock(
+        &mut self,
+        settlement_id: SettlementId,
+        encryption_point: EncryptionPoint,
+        encrypted_signature: EncryptedSignature,
+        refund_deadline: Timestamp,
+    ) -> Result<()> {
+        if self.state != AdaptorLockState::Proposed {
+            return Err(AtomicSettleError::InvalidMessage {
+                message: format!("cannot lock an adaptor leg in state {:?}", self.state),
+                field: Some("state".to_string()),
+            });
+        }
+
+        self.settlement_id = Some(settlement_id);
+        self.encryption_point = Some(encryption_point);
+        self.encrypted_signature = Some(encrypted_signature);
+        self.refund_deadline = Some(refund_deadline);
+        self.state = AdaptorLockState::Locked;
+        Ok(())
+    }
+
+    /// Claim the locked leg with a completed signature. Fails if the leg
+    /// isn't locked or the refund deadline has already passed; does not
+    /// itself verify the signature -- callers should confirm it against
+    /// the counterparty's verifying key first.
+    pub fn complete(&mut self, completed_signature: Signature) -> Result<()> {
+        if self.state != AdaptorLockState::Locked {
+            return Err(AtomicSettleError::InvalidMessage {
+                message: format!("cannot complete an adaptor leg in state {:?}", self.state),
+                field: Some("state".to_string()),
+            });
+        }
+
+        let refund_deadline = self
+            .refund_deadline
+            .expect("locked state always carries a refund deadline");
+        if atomicsettle_common::time::is_expired(refund_deadline) {
+            let settlement_id = self
+                .settlement_id
+                .expect("locked state always carries a settlement id");
+            return Err(AtomicSettleError::LockExpired(settlement_id));
+        }
+
+        self.completed_signature = Some(completed_signature);
+        self.state = AdaptorLockState::Completed;
+        Ok(())
+    }
+
+    /// Cancel an unlocked leg once the cancel deadline has passed without
+    /// the counterparty locking its side.
+    pub fn cancel(&mut self) -> Result<()> {
+        if self.state != AdaptorLockState::Proposed {
+            return Err(AtomicSettleError::InvalidMessage {
+                message: format!("cannot cancel an adaptor leg in state {:?}", self.state),
+                field: Some("state".to_string()),
+            });
+        }
+
+        if !atomicsettle_common::time::is_expired(self.cancel_deadline) {
+            return Err(AtomicSettleError::InvalidMessage {
+                message: "cannot cancel an adaptor leg before its cancel deadline".to_string(),
+                field: Some("cancel_deadline".to_string()),
+            });
+        }
+
+        self.state = AdaptorLockState::CancelTimelock;
+        Ok(())
+    }
+
+    /// Refund the locked leg once its refund deadline has passed without
+    /// the counterparty completing its signature.
+    pub fn refund(&mut self) -> Result<()> {
+        if self.state != AdaptorLockState::Locked {
+            return Err(AtomicSettleError::InvalidMessage {
+                message: format!("cannot refund an adaptor leg in state {:?}", self.state),
+                field: Some("state".to_string()),
+            });
+        }
+
+        let refund_deadline = self
+            .refund_deadline
+            .expect("locked state always carries a refund deadline");
+        if !atomicsettle_common::time::is_expired(refund_deadline) {
+            return Err(AtomicSettleError::InvalidMessage {
+                message: "cannot refund an adaptor leg before its refund deadline".to_string(),
+                field: Some("refund_deadline".to_string()),
+            });
+        }
+
+        self.state = AdaptorLockState::RefundTimelock;
+        Ok(())
+    }
+}
+
+/// One leg of a cross-currency atomic swap as requested by the caller of
+/// [`crate::client::ParticipantClient::send_atomic_swap`]: a destination,
+/// an amount already expressed in that leg's currency, and the deadline
+/// after which it can be refunded if the counterparty never claims it.
+/// Unlike [`CrossCurrencySwap::propose`], which derives leg B's amount
+/// from a rate, both legs here are specified directly by the caller.
+#[derive(Debug, Clone)]
+pub struct AtomicSwapLeg {
+    /// Participant receiving this leg.
+    pub to_participant: ParticipantId,
+    /// Amount to transfer, in this leg's currency.
+    pub amount: Money,
+    /// Deadline after which this leg can be refunded if unclaimed.
+    pub timeout: Timestamp,
+}
+
+/// A two-leg HTLC swap between an initiator and a counterparty holding
+/// different currencies. Leg A is the initiator's currency, opened first
+/// with the longer timeout `T1`; leg B is the counterparty's currency,
+/// FX-converted from leg A, opened second with the strictly shorter
+/// timeout `T2`.
+pub struct CrossCurrencySwap {
+    /// The participant who picked the secret and opens leg A first.
+    pub initiator: ParticipantId,
+    /// The participant who opens leg B once it observes leg A locked.
+    pub counterparty: ParticipantId,
+    /// Leg A amount, in the initiator's currency.
+    pub leg_a_amount: Money,
+    /// Leg B amount, FX-converted into the counterparty's currency.
+    pub leg_b_amount: Money,
+    /// Leg A: the initiator's currency, locked until `t1`.
+    pub leg_a: HtlcLock,
+    /// Leg B: the counterparty's currency, locked until the strictly
+    /// shorter `t2`.
+    pub leg_b: HtlcLock,
+    hash: PaymentHash,
+    /// Held by the initiator until it claims leg B; `None` once a
+    /// counterparty instance is reconstructed from an observed leg A.
+    preimage: Option<PaymentPreimage>,
+    t1: Timestamp,
+    t2: Timestamp,
+}
+
+impl CrossCurrencySwap {
+    /// Propose a new swap as the initiator: picks a random secret, derives
+    /// its payment hash, and converts `leg_a_amount` into the
+    /// counterparty's currency via `rate` for leg B. Rejects the swap if
+    /// `t2` is not strictly before `t1` -- the party who learns the secret
+    /// second (the counterparty, claiming leg A) must still have time to
+    /// claim before its own leg expires.
+    pub fn propose(
+        initiator: ParticipantId,
+        counterparty: ParticipantId,
+        leg_a_amount: Money,
+        rate: &FxRate,
+        t1: Timestamp,
+        t2: Timestamp,
+    ) -> Result<Self> {
+        if t2 >= t1 {
+            return Err(AtomicSettleError::InvalidMessage {
+                message: format!(
+                    "swap timeout T2 ({t2}) must be strictly before T1 ({t1})"
+                ),
+                field: Some("t2".to_string()),
+            });
+        }
+
+        if !rate.is_valid() {
+            return Err(AtomicSettleError::FxRateExpired);
+        }
+
+        let leg_b_amount = rate.convert(&leg_a_amount).map_err(|e| AtomicSettleError::InvalidMessage {
+            message: e.to_string(),
+            field: Some("leg_a_amount".to_string()),
+        })?;
+
+        let preimage = PaymentPreimage::random();
+        let hash = preimage.hash();
+
+        Ok(Self {
+            initiator,
+            counterparty,
+            leg_a_amount,
+            leg_b_amount,
+            leg_a: HtlcLock::new(),
+            leg_b: HtlcLock::new(),
+            hash,
+            preimage: Some(preimage),
+            t1,
+            t2,
+        })
+    }
+
+    /// Shared payment hash both legs are locked to.
+    pub fn hash(&self) -> PaymentHash {
+        self.hash
+    }
+
+    /// Leg A's deadline `T1`.
+    pub fn t1(&self) -> Timestamp {
+        self.t1
+    }
+
+    /// Leg B's deadline `T2`.
+    pub fn t2(&self) -> Timestamp {
+        self.t2
+    }
+
+    /// Open leg A via the coordinator, locking it to the shared hash until
+    /// `T1`.
+    pub async fn open_leg_a(
+        &mut self,
+        connection: &CoordinatorConnection,
+        idempotency_key: String,
+    ) -> Result<()> {
+        let settlement = connection
+            .send_settlement_request(
+                self.counterparty.clone(),
+                self.leg_a_amount.clone(),
+                "HTLC_SWAP_LEG_A".to_string(),
+                Some(self.hash.to_string()),
+                idempotency_key,
+            )
+            .await?;
+
+        self.leg_a.lock(settlement.id, self.hash, self.t1)
+    }
+
+    /// Open leg B via the coordinator, locking it to the same shared hash
+    /// until the strictly shorter `T2`.
+    pub async fn open_leg_b(
+        &mut self,
+        connection: &CoordinatorConnection,
+        idempotency_key: String,
+    ) -> Result<()> {
+        let settlement = connection
+            .send_settlement_request(
+                self.initiator.clone(),
+                self.leg_b_amount.clone(),
+                "HTLC_SWAP_LEG_B".to_string(),
+                Some(self.hash.to_string()),
+                idempotency_key,
+            )
+            .await?;
+
+        self.leg_b.lock(settlement.id, self.hash, self.t2)
+    }
+
+    /// Claim leg B by revealing the secret (run by the initiator once it
+    /// sees leg B locked). From this point the preimage is public on the
+    /// shared ledger.
+    pub fn claim_leg_b(&mut self) -> Result<PaymentPreimage> {
+        let preimage = self.preimage.ok_or_else(|| AtomicSettleError::InvalidMessage {
+            message: "only the initiator holds the preimage to claim leg B".to_string(),
+            field: None,
+        })?;
+
+        self.leg_b.claim(preimage)?;
+        Ok(preimage)
+    }
+
+    /// Claim leg A using the now-public preimage (run by the counterparty
+    /// after observing the initiator's leg B claim).
+    pub fn claim_leg_a(&mut self, preimage: PaymentPreimage) -> Result<()> {
+        self.leg_a.claim(preimage)
+    }
+
+    /// Refund leg A after `T1` if the counterparty never claimed it.
+    pub fn refund_leg_a(&mut self) -> Result<()> {
+        self.leg_a.refund()
+    }
+
+    /// Refund leg B after `T2` if the initiator never claimed it.
+    pub fn refund_leg_b(&mut self) -> Result<()> {
+        self.leg_b.refund()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use atomicsettle_common::monetary::{Currency, CurrencyPair};
+    use rust_decimal::Decimal;
+
+    fn usd_eur_rate() -> FxRate {
+        FxRate::new(
+            CurrencyPair::new(Currency::usd(), Currency::eur()),
+            Decimal::new(92, 2),
+            Decimal::new(93, 2),
+            30,
+            "test",
+        )
+    }
+
+    fn propose_swap(t1_secs: i64, t2_secs: i64) -> Result<CrossCurrencySwap> {
+        let now = atomicsettle_common::time::now();
+        CrossCurrencySwap::propose(
+            ParticipantId::new("BANK_A"),
+            ParticipantId::new("BANK_B"),
+            Money::new(Decimal::from(1000), Currency::usd()),
+            &usd_eur_rate(),
+            now + chrono::Duration::seconds(t1_secs),
+            now + chrono::Duration::seconds(t2_secs),
+        )
+    }
+
+    #[test]
+    fn test_propose_rejects_non_staggered_timeout() {
+        assert!(propose_swap(30, 60).is_err());
+        assert!(propose_swap(30, 30).is_err());
+        assert!(propose_swap(60, 30).is_ok());
+    }
+
+    #[test]
+    fn test_htlc_lock_happy_path() {
+        let preimage = PaymentPreimage::random();
+        let hash = preimage.hash();
+        let deadline = atomicsettle_common::time::now() + chrono::Duration::seconds(30);
+
+        let mut lock = HtlcLock::new();
+        assert_eq!(lock.state(), SwapState::Proposed);
+
+        lock.lock(SettlementId::new(), hash, deadline).unwrap();
+        assert_eq!(lock.state(), SwapState::Locked);
+
+        lock.claim(preimage).unwrap();
+        assert_eq!(lock.state(), SwapState::Claimed);
+        assert_eq!(lock.revealed_preimage(), Some(&preimage));
+    }
+
+    #[test]
+    fn test_htlc_lock_rejects_wrong_preimage() {
+        let hash = PaymentPreimage::random().hash();
+        let deadline = atomicsettle_common::time::now() + chrono::Duration::seconds(30);
+
+        let mut lock = HtlcLock::new();
+        lock.lock(SettlementId::new(), hash, deadline).unwrap();
+
+        let wrong = PaymentPreimage::random();
+        assert!(lock.claim(wrong).is_err());
+        assert_eq!(lock.state(), SwapState::Locked);
+    }
+
+    #[test]
+    fn test_htlc_lock_refund_requires_deadline_passed() {
+        let hash = PaymentPreimage::random().hash();
+        let future_deadline = atomicsettle_common::time::now() + chrono::Duration::seconds(30);
+        let past_deadline = atomicsettle_common::time::now() - chrono::Duration::seconds(1);
+
+        let mut not_yet_expired = HtlcLock::new();
+        not_yet_expired.lock(SettlementId::new(), hash, future_deadline).unwrap();
+        assert!(not_yet_expired.refund().is_err());
+
+        let mut expired = HtlcLock::new();
+        expired.lock(SettlementId::new(), hash, past_deadline).unwrap();
+        expired.refund().unwrap();
+        assert_eq!(expired.state(), SwapState::Refunded);
+    }
+
+    #[test]
+    fn test_adaptor_lock_happy_path() {
+        use atomicsettle_crypto::{AdaptorSecret, AdaptorSigningKey, recover_scalar};
+
+        let signer = AdaptorSigningKey::generate();
+        let secret = AdaptorSecret::generate();
+        let point = secret.encryption_point();
+        let message = b"settle leg A: 1000 USD BANK_A -> BANK_B";
+
+        let encrypted = signer.encrypt_signature(message, &point);
+        let refund_deadline = atomicsettle_common::time::now() + chrono::Duration::seconds(30);
+
+        let mut lock = AdaptorLock::new(atomicsettle_common::time::now() + chrono::Duration::seconds(10));
+        assert_eq!(lock.state(), AdaptorLockState::Proposed);
+
+        lock.lock(SettlementId::new(), point, encrypted.clone(), refund_deadline)
+            .unwrap();
+        assert_eq!(lock.state(), AdaptorLockState::Locked);
+
+        let completed = encrypted.decrypt_signature(&secret);
+        lock.complete(completed.clone()).unwrap();
+        assert_eq!(lock.state(), AdaptorLockState::Completed);
+
+        assert!(signer.verifying_key().verify_completed(message, &completed).is_ok());
+        let recovered = recover_scalar(&encrypted, lock.completed_signature().unwrap()).unwrap();
+        assert_eq!(recovered.encryption_point(), point);
+    }
+
+    #[test]
+    fn test_adaptor_lock_cancel_requires_deadline_passed() {
+        let future_cancel = atomicsettle_common::time::now() + chrono::Duration::seconds(30);
+        let past_cancel = atomicsettle_common::time::now() - chrono::Duration::seconds(1);
+
+        let mut not_yet_expired = AdaptorLock::new(future_cancel);
+        assert!(not_yet_expired.cancel().is_err());
+
+        let mut expired = AdaptorLock::new(past_cancel);
+        expired.cancel().unwrap();
+        assert_eq!(expired.state(), AdaptorLockState::CancelTimelock);
+    }
+
+    #[test]
+    fn test_adaptor_lock_refund_requires_deadline_passed() {
+        use atomicsettle_crypto::{AdaptorSecret, AdaptorSigningKey};
+
+        let signer = AdaptorSigningKey::generate();
+        let secret = AdaptorSecret::generate();
+        let point = secret.encryption_point();
+        let message = b"settle leg B: 920 EUR BANK_B -> BANK_A";
+        let encrypted = signer.encrypt_signature(message, &point);
+
+        let future_deadline = atomicsettle_common::time::now() + chrono::Duration::seconds(30);
+        let past_deadline = atomicsettle_common::time::now() - chrono::Duration::seconds(1);
+
+        let mut not_yet_expired = AdaptorLock::new(atomicsettle_common::time::now());
+        not_yet_expired
+            .lock(SettlementId::new(), point, encrypted.clone(), future_deadline)
+            .unwrap();
+        assert!(not_yet_expired.refund().is_err());
+
+        let mut expired = AdaptorLock::new(atomicsettle_common::time::now());
+        expired
+            .lock(SettlementId::new(), point, encrypted, past_deadline)
+            .unwrap();
+        expired.refund().unwrap();
+        assert_eq!(expired.state(), AdaptorLockState::RefundTimelock);
+    }
+
+    #[test]
+    fn test_cross_currency_swap_claim_flow_shares_preimage() {
+        let mut swap = propose_swap(60, 30).unwrap();
+        let deadline_a = swap.t1();
+        let deadline_b = swap.t2();
+        let hash = swap.hash();
+
+        swap.leg_a.lock(SettlementId::new(), hash, deadline_a).unwrap();
+        swap.leg_b.lock(SettlementId::new(), hash, deadline_b).unwrap();
+
+        let preimage = swap.claim_leg_b().unwrap();
+        swap.claim_leg_a(preimage).unwrap();
+
+        assert_eq!(swap.leg_a.state(), SwapState::Claimed);
+        assert_eq!(swap.leg_b.state(), SwapState::Claimed);
+    }
+}
@@ -4,6 +4,7 @@ use std::sync::Arc;
 
 use rust_decimal::Decimal;
 use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
 
 use atomicsettle_common::{Currency, Money, ParticipantId};
 
@@ -19,6 +20,12 @@ pub struct SimulatedBank {
     /// Settlement history.
     settlements_sent: Arc<RwLock<Vec<String>>>,
     settlements_received: Arc<RwLock<Vec<String>>>,
+    /// Whether the bank is currently reachable. A crashed bank refuses
+    /// all debits until it recovers; `credit` does not check it.
+    alive: Arc<RwLock<bool>>,
+    /// Handle of this bank's background heartbeat task, aborted to
+    /// simulate a hard crash.
+    heartbeat: Arc<RwLock<Option<JoinHandle<()>>>>,
 }
 
 impl SimulatedBank {
@@ -31,9 +38,47 @@ impl SimulatedBank {
             balances: Arc::new(RwLock::new(std::collections::HashMap::new())),
             settlements_sent: Arc::new(RwLock::new(Vec::new())),
             settlements_received: Arc::new(RwLock::new(Vec::new())),
+            alive: Arc::new(RwLock::new(true)),
+            heartbeat: Arc::new(RwLock::new(None)),
         }
     }
 
+    /// Start the bank's background heartbeat loop. Aborting its
+    /// `JoinHandle` (via [`Self::crash`]) is how the chaos subsystem
+    /// simulates a hard process crash.
+    pub async fn start_heartbeat(&self) {
+        let id = self.id.clone();
+        let handle = tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+                tracing::debug!(bank = %id, "heartbeat");
+            }
+        });
+        *self.heartbeat.write().await = Some(handle);
+        *self.alive.write().await = true;
+    }
+
+    /// Simulate a hard crash: abort the running heartbeat task and mark
+    /// the bank unreachable. Settlements to or from a crashed bank are
+    /// dropped, not merely rejected.
+    pub async fn crash(&self) {
+        if let Some(handle) = self.heartbeat.write().await.take() {
+            handle.abort();
+        }
+        *self.alive.write().await = false;
+    }
+
+    /// Recover from a crash: mark the bank reachable again and restart
+    /// its heartbeat loop.
+    pub async fn recover(&self) {
+        self.start_heartbeat().await;
+    }
+
+    /// Whether the bank is currently reachable.
+    pub async fn is_alive(&self) -> bool {
+        *self.alive.read().await
+    }
+
     /// Initialize with default balances.
     pub async fn initialize_balances(&self, initial_balance: Decimal) {
         let mut balances = self.balances.write().await;
@@ -43,7 +88,6 @@ impl SimulatedBank {
     }
 
     /// Get balance for currency.
-    #[allow(dead_code)]
     pub async fn get_balance(&self, currency: &Currency) -> Decimal {
         self.balances
             .read()
@@ -55,6 +99,10 @@ impl SimulatedBank {
 
     /// Debit balance (for sending).
     pub async fn debit(&self, amount: &Money) -> Result<(), String> {
+        if !self.is_alive().await {
+            return Err(format!("{} is unreachable (crashed)", self.id));
+        }
+
         let mut balances = self.balances.write().await;
         let balance = balances.entry(amount.currency.clone()).or_insert(Decimal::ZERO);
 
@@ -1,25 +1,60 @@
 //! Simulation controller.
 
+use std::collections::HashMap;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use rand::rngs::StdRng;
 use rand::{Rng, SeedableRng};
 use rust_decimal::Decimal;
 use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
 use tracing::{info, warn};
 
-use atomicsettle_common::{Currency, Money, ParticipantId, SettlementId};
+use atomicsettle_common::{Currency, CurrencyPair, FxRate, Money, ParticipantId, RateTable, SettlementId};
 
 use crate::bank::{BankFactory, SimulatedBank};
 use crate::metrics::SimulationMetrics;
-use crate::scenario::{Scenario, ScenarioStep};
+use crate::scenario::{FaultEvent, FaultKind, Scenario, ScenarioStep};
+
+/// Identifies an in-flight settlement attempt for the purposes of
+/// tracking chaos-induced loss and retry, keyed the same way a real
+/// idempotency key would scope a single logical transfer.
+type SettlementKey = (String, String, String, String);
+
+/// Normalize an unordered pair of bank IDs so a partition registered as
+/// `(A, C)` is found whether later queried as `(A, C)` or `(C, A)`.
+fn partition_key(a: &str, b: &str) -> (String, String) {
+    if a <= b {
+        (a.to_string(), b.to_string())
+    } else {
+        (b.to_string(), a.to_string())
+    }
+}
+
+/// Active per-participant chaos fault, tracked independently of the
+/// pairwise `partitions`/`network_partition_groups` maps since these apply
+/// to a single bank rather than a pair or group.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum FaultState {
+    /// Every debit the bank attempts fails, as if its balance were
+    /// insufficient.
+    InsufficientFunds,
+    /// Settlements touching the bank record extra latency.
+    LatencySpike { ms: u64 },
+    /// The bank is isolated from every other bank.
+    PartitionNode,
+    /// The bank's ledger state is corrupted; settlements touching it fail.
+    LedgerCorruption,
+}
 
 /// Controls the simulation.
 pub struct SimulationController {
     /// Number of banks.
     bank_count: usize,
-    /// Simulation speed multiplier.
+    /// Simulation speed multiplier. Also skews how quickly chaos fault
+    /// events fire relative to wall-clock time, the same way it skews
+    /// `ScenarioStep::Wait`.
     speed: f64,
     /// Random number generator.
     rng: Arc<RwLock<StdRng>>,
@@ -29,6 +64,46 @@ pub struct SimulationController {
     metrics: Arc<RwLock<SimulationMetrics>>,
     /// Running flag.
     running: Arc<RwLock<bool>>,
+    /// Currently-partitioned bank pairs (normalized via [`partition_key`]).
+    /// Settlements between a partitioned pair are dropped in either
+    /// direction until the partition heals.
+    partitions: Arc<RwLock<std::collections::HashSet<(String, String)>>>,
+    /// Settlement attempts lost to a fault, by when they were first lost.
+    /// A later attempt with the same key that succeeds is recorded as a
+    /// recovered retry.
+    pending_losses: Arc<RwLock<HashMap<SettlementKey, Instant>>>,
+    /// Rates seeded by `ScenarioStep::SetRate`, used to convert a
+    /// `SendSettlement` leg's amount when its `rate` field names a
+    /// destination currency.
+    rates: Arc<RwLock<RateTable>>,
+    /// Groups set by `FaultType::NetworkPartition`. A settlement between
+    /// two banks in different groups is dropped, same as an ordinary
+    /// pairwise `partitions` entry but covering N groups at once. Empty
+    /// means no partition is active.
+    network_partition_groups: Arc<RwLock<Vec<Vec<String>>>>,
+    /// Per-bank clock skew in milliseconds set by `FaultType::ClockSkew`
+    /// (negative = running slow, positive = running fast), applied when
+    /// checking whether an `AtomicSwap` leg's timeout has already elapsed.
+    clock_skew: Arc<RwLock<HashMap<String, i64>>>,
+    /// Probability (0.0-1.0) that `FaultType::PacketDrop` causes any given
+    /// settlement attempt to be dropped, independent of partition/crash
+    /// state.
+    packet_drop_probability: Arc<RwLock<f64>>,
+    /// Whether the coordinator is currently down per
+    /// `FaultType::CoordinatorCrash`. Every settlement attempt is dropped
+    /// while this is set.
+    coordinator_crashed: Arc<RwLock<bool>>,
+    /// Per-bank faults set by `FaultType::InsufficientFunds`,
+    /// `LatencySpike`, `PartitionNode`, and `LedgerCorruption`, consulted
+    /// by both the scenario and continuous-mode settlement paths before
+    /// debiting.
+    target_faults: Arc<RwLock<HashMap<ParticipantId, FaultState>>>,
+    /// Outcome (`true` = settled, `false` = dropped/rejected) of the most
+    /// recent settlement matching each `runner::settlement_key`, so
+    /// `Assert { condition: SettlementSucceeded | SettlementFailed }` can
+    /// evaluate against the controller's own `execute_step` path the same
+    /// way `ScenarioRunner::evaluate` does against a generic backend.
+    settlement_outcomes: Arc<RwLock<HashMap<String, bool>>>,
 }
 
 impl SimulationController {
@@ -46,6 +121,15 @@ impl SimulationController {
             banks: Arc::new(RwLock::new(Vec::new())),
             metrics: Arc::new(RwLock::new(SimulationMetrics::new())),
             running: Arc::new(RwLock::new(false)),
+            partitions: Arc::new(RwLock::new(std::collections::HashSet::new())),
+            pending_losses: Arc::new(RwLock::new(HashMap::new())),
+            rates: Arc::new(RwLock::new(RateTable::new(Currency::usd()))),
+            network_partition_groups: Arc::new(RwLock::new(Vec::new())),
+            clock_skew: Arc::new(RwLock::new(HashMap::new())),
+            packet_drop_probability: Arc::new(RwLock::new(0.0)),
+            coordinator_crashed: Arc::new(RwLock::new(false)),
+            target_faults: Arc::new(RwLock::new(HashMap::new())),
+            settlement_outcomes: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
@@ -60,6 +144,7 @@ impl SimulationController {
         let initial_balance = Decimal::from(100_000_000); // $100M
         for bank in &banks {
             bank.initialize_balances(initial_balance).await;
+            bank.start_heartbeat().await;
             info!("Initialized bank {} with ${} balance", bank.id, initial_balance);
         }
 
@@ -68,12 +153,346 @@ impl SimulationController {
         Ok(())
     }
 
-    /// Run a scenario.
+    /// Whether `a` and `b` are currently partitioned from each other, in
+    /// either direction: either a direct pairwise partition, or membership
+    /// in two different `FaultType::NetworkPartition` groups.
+    async fn is_partitioned(&self, a: &str, b: &str) -> bool {
+        if self.partitions.read().await.contains(&partition_key(a, b)) {
+            return true;
+        }
+
+        let groups = self.network_partition_groups.read().await;
+        if groups.is_empty() {
+            return false;
+        }
+        let group_of = |bank: &str| groups.iter().position(|g| g.iter().any(|m| m == bank));
+        match (group_of(a), group_of(b)) {
+            (Some(ga), Some(gb)) => ga != gb,
+            _ => false,
+        }
+    }
+
+    /// Whether a leg with `timeout_secs` addressed to `bank` should be
+    /// treated as already expired, given any `FaultType::ClockSkew`
+    /// applied to that bank: the leg's timeout plus the bank's skew is
+    /// the effective time remaining on its clock, and a sufficiently
+    /// negative skew can push that to zero or below before the leg is
+    /// even attempted.
+    async fn is_leg_expired(&self, bank: &str, timeout_secs: u64) -> bool {
+        let skew_ms = match self.clock_skew.read().await.get(bank) {
+            Some(ms) => *ms,
+            None => return false,
+        };
+        let effective_ms = (timeout_secs as i64) * 1000 + skew_ms;
+        effective_ms <= 0
+    }
+
+    /// Attempt a settlement between two named banks, applying chaos
+    /// effects: dropped (not merely rejected) if either bank has crashed
+    /// or the pair is currently partitioned, and recording loss/recovery
+    /// metrics keyed by the settlement's identity so a later identical
+    /// retry that succeeds is recognized as a recovery. Returns whether it
+    /// settled.
+    async fn attempt_settlement(&self, from_bank: &str, to_bank: &str, money: &Money) -> bool {
+        self.attempt_settlement_fx(from_bank, to_bank, money, money)
+            .await
+    }
+
+    /// Attempt a settlement where `to_bank` is credited a different
+    /// [`Money`] than `from_bank` is debited -- e.g. a cross-currency leg
+    /// converted through the controller's rate table. The settlement's
+    /// identity for loss/recovery tracking is keyed off `debit_money`
+    /// (what actually left `from_bank`), applying the same chaos effects
+    /// as [`Self::attempt_settlement`], including any per-bank
+    /// `target_faults`. Returns whether it settled.
+    async fn attempt_settlement_fx(
+        &self,
+        from_bank: &str,
+        to_bank: &str,
+        debit_money: &Money,
+        credit_money: &Money,
+    ) -> bool {
+        let key: SettlementKey = (
+            from_bank.to_string(),
+            to_bank.to_string(),
+            debit_money.value.to_string(),
+            debit_money.currency.code().to_string(),
+        );
+
+        let banks = self.banks.read().await;
+        let from = banks.iter().find(|b| b.id.as_str() == from_bank);
+        let to = banks.iter().find(|b| b.id.as_str() == to_bank);
+
+        let (Some(from), Some(to)) = (from, to) else {
+            warn!("Banks not found: {} or {}", from_bank, to_bank);
+            self.metrics.write().await.record_failure();
+            return false;
+        };
+
+        let (from_fault, to_fault) = {
+            let faults = self.target_faults.read().await;
+            (
+                faults.get(&ParticipantId::new(from_bank)).copied(),
+                faults.get(&ParticipantId::new(to_bank)).copied(),
+            )
+        };
+        let node_partitioned = matches!(from_fault, Some(FaultState::PartitionNode))
+            || matches!(to_fault, Some(FaultState::PartitionNode));
+
+        let partitioned = self.is_partitioned(from_bank, to_bank).await || node_partitioned;
+        let reachable = from.is_alive().await && to.is_alive().await;
+        let coordinator_crashed = *self.coordinator_crashed.read().await;
+        let packet_dropped = {
+            let probability = *self.packet_drop_probability.read().await;
+            probability > 0.0 && self.rng.write().await.gen_bool(probability)
+        };
+
+        if partitioned || !reachable || coordinator_crashed || packet_dropped {
+            info!(
+                from = from_bank,
+                to = to_bank,
+                partitioned,
+                reachable,
+                coordinator_crashed,
+                packet_dropped,
+                "chaos: dropping settlement attempt"
+            );
+            self.pending_losses.write().await.entry(key).or_insert_with(Instant::now);
+            self.metrics.write().await.record_lost();
+            return false;
+        }
+
+        if matches!(from_fault, Some(FaultState::InsufficientFunds)) {
+            info!(from = from_bank, "chaos: forcing insufficient-funds failure");
+            self.metrics.write().await.record_failure();
+            return false;
+        }
+        if matches!(from_fault, Some(FaultState::LedgerCorruption))
+            || matches!(to_fault, Some(FaultState::LedgerCorruption))
+        {
+            info!(from = from_bank, to = to_bank, "chaos: ledger corruption forces settlement failure");
+            self.metrics.write().await.record_failure();
+            return false;
+        }
+
+        let latency_bonus = match (from_fault, to_fault) {
+            (Some(FaultState::LatencySpike { ms }), _) | (_, Some(FaultState::LatencySpike { ms })) => ms,
+            _ => 0,
+        };
+
+        if from.debit(debit_money).await.is_ok() {
+            to.credit(credit_money).await;
+            let settlement_id = SettlementId::new().to_string();
+            from.record_sent(settlement_id.clone()).await;
+            to.record_received(settlement_id).await;
+
+            if let Some(lost_at) = self.pending_losses.write().await.remove(&key) {
+                self.metrics
+                    .write()
+                    .await
+                    .record_recovery(lost_at.elapsed().as_millis() as u64);
+            }
+            self.metrics.write().await.record_success(100 + latency_bonus);
+            true
+        } else {
+            self.metrics.write().await.record_failure();
+            false
+        }
+    }
+
+    /// Spawn a background task per fault event that waits until its
+    /// scheduled time (adjusted by `speed`) and then applies it. Returns
+    /// the handles so the caller can clean them up once the scenario
+    /// finishes.
+    fn spawn_fault_timeline(&self, faults: Vec<FaultEvent>) -> Vec<JoinHandle<()>> {
+        faults
+            .into_iter()
+            .map(|fault| {
+                let banks = self.banks.clone();
+                let partitions = self.partitions.clone();
+                let speed = self.speed;
+
+                tokio::spawn(async move {
+                    let delay = Duration::from_millis(((fault.at_secs as f64) * 1000.0 / speed) as u64);
+                    tokio::time::sleep(delay).await;
+
+                    match fault.kind {
+                        FaultKind::Crash { bank } => {
+                            info!(bank = %bank, "chaos: crashing bank");
+                            let banks = banks.read().await;
+                            if let Some(b) = banks.iter().find(|b| b.id.as_str() == bank) {
+                                b.crash().await;
+                            } else {
+                                warn!(bank = %bank, "chaos: crash target not found");
+                            }
+                        }
+                        FaultKind::Partition {
+                            banks: (a, b),
+                            duration_secs,
+                        } => {
+                            info!(a = %a, b = %b, duration_secs, "chaos: partitioning banks");
+                            let key = partition_key(&a, &b);
+                            partitions.write().await.insert(key.clone());
+
+                            let hold = Duration::from_millis(
+                                ((duration_secs as f64) * 1000.0 / speed) as u64,
+                            );
+                            tokio::time::sleep(hold).await;
+
+                            partitions.write().await.remove(&key);
+                            info!(a = %a, b = %b, "chaos: partition healed");
+                        }
+                    }
+                })
+            })
+            .collect()
+    }
+
+    /// Spawn a background task that clears `coordinator_crashed` on its
+    /// own after `restart_after_secs` (adjusted by `speed`), simulating
+    /// the coordinator coming back up unattended. `ClearFault` can still
+    /// clear it early.
+    fn spawn_coordinator_restart(&self, restart_after_secs: u64) -> JoinHandle<()> {
+        let coordinator_crashed = self.coordinator_crashed.clone();
+        let speed = self.speed;
+
+        tokio::spawn(async move {
+            let delay = Duration::from_millis(((restart_after_secs as f64) * 1000.0 / speed) as u64);
+            tokio::time::sleep(delay).await;
+            *coordinator_crashed.write().await = false;
+            info!("chaos: coordinator restarted on its own");
+        })
+    }
+
+    /// Apply an `InjectFault` step's [`FaultType`](crate::scenario::FaultType)
+    /// to this controller's state, shared by the `execute_step` and
+    /// [`crate::runner::ScenarioBackend`] paths.
+    async fn apply_fault(
+        &self,
+        fault_type: &crate::scenario::FaultType,
+        target: &str,
+    ) -> anyhow::Result<()> {
+        use crate::scenario::FaultType;
+        match fault_type {
+            FaultType::BankOffline => {
+                let banks = self.banks.read().await;
+                if let Some(bank) = banks.iter().find(|b| b.id.as_str() == target) {
+                    bank.crash().await;
+                } else {
+                    anyhow::bail!("fault target not found: {target}");
+                }
+            }
+            FaultType::NetworkPartition { groups } => {
+                info!(?groups, "chaos: applying network partition");
+                *self.network_partition_groups.write().await = groups.clone();
+            }
+            FaultType::ClockSkew {
+                target: skew_target,
+                offset_ms,
+            } => {
+                info!(target = %skew_target, offset_ms, "chaos: applying clock skew");
+                self.clock_skew
+                    .write()
+                    .await
+                    .insert(skew_target.clone(), *offset_ms);
+            }
+            FaultType::PacketDrop { probability } => {
+                info!(probability, "chaos: applying packet drop");
+                *self.packet_drop_probability.write().await = *probability;
+            }
+            FaultType::CoordinatorCrash { restart_after_secs } => {
+                info!(restart_after_secs, "chaos: crashing coordinator");
+                *self.coordinator_crashed.write().await = true;
+                self.spawn_coordinator_restart(*restart_after_secs);
+            }
+            FaultType::InsufficientFunds => {
+                info!(target, "chaos: forcing insufficient funds");
+                self.target_faults
+                    .write()
+                    .await
+                    .insert(ParticipantId::new(target), FaultState::InsufficientFunds);
+            }
+            FaultType::LatencySpike { ms } => {
+                info!(target, ms, "chaos: applying latency spike");
+                self.target_faults
+                    .write()
+                    .await
+                    .insert(ParticipantId::new(target), FaultState::LatencySpike { ms: *ms });
+            }
+            FaultType::PartitionNode => {
+                info!(target, "chaos: partitioning node from the rest of the simulation");
+                self.target_faults
+                    .write()
+                    .await
+                    .insert(ParticipantId::new(target), FaultState::PartitionNode);
+            }
+            FaultType::LedgerCorruption => {
+                info!(target, "chaos: corrupting ledger state");
+                self.target_faults
+                    .write()
+                    .await
+                    .insert(ParticipantId::new(target), FaultState::LedgerCorruption);
+            }
+            _ => {
+                // NetworkLatency, CoordinatorOverload, LockTimeout,
+                // CounterpartyStall, and StaleRate don't have dedicated
+                // controller-side enforcement.
+            }
+        }
+        Ok(())
+    }
+
+    /// Clear a previously injected fault on `target`, the reverse of
+    /// [`Self::apply_fault`]. `target` is matched against whichever fault
+    /// state actually names it, since `ClearFault` doesn't carry the
+    /// original `FaultType`.
+    async fn clear_fault_target(&self, target: &str) -> anyhow::Result<()> {
+        match target {
+            "network-partition" => {
+                self.network_partition_groups.write().await.clear();
+                return Ok(());
+            }
+            "packet-drop" => {
+                *self.packet_drop_probability.write().await = 0.0;
+                return Ok(());
+            }
+            "coordinator" => {
+                *self.coordinator_crashed.write().await = false;
+                return Ok(());
+            }
+            _ => {}
+        }
+
+        let had_skew = self.clock_skew.write().await.remove(target).is_some();
+        let had_target_fault = self
+            .target_faults
+            .write()
+            .await
+            .remove(&ParticipantId::new(target))
+            .is_some();
+
+        let banks = self.banks.read().await;
+        if let Some(bank) = banks.iter().find(|b| b.id.as_str() == target) {
+            if !bank.is_alive().await {
+                bank.recover().await;
+            }
+            Ok(())
+        } else if had_skew || had_target_fault {
+            Ok(())
+        } else {
+            anyhow::bail!("fault target not found: {target}")
+        }
+    }
+
+    /// Run a scenario, including its chaos fault timeline (if any) running
+    /// concurrently with the ordinary steps.
     pub async fn run_scenario(&self, scenario: Scenario) -> anyhow::Result<()> {
         info!("Running scenario: {} - {}", scenario.name, scenario.description);
 
         *self.running.write().await = true;
 
+        let fault_handles = self.spawn_fault_timeline(scenario.faults.clone());
+
         for step in &scenario.steps {
             if !*self.running.read().await {
                 break;
@@ -84,99 +503,83 @@ impl SimulationController {
 
         *self.running.write().await = false;
 
+        for handle in fault_handles {
+            handle.abort();
+        }
+
         Ok(())
     }
 
-    /// Run in continuous mode.
-    pub async fn run(&self, duration: Option<Duration>) -> anyhow::Result<()> {
-        info!("Running simulation in continuous mode");
-
-        *self.running.write().await = true;
-
-        let start = std::time::Instant::now();
+    /// Continuous-mode settlement generator, run concurrently with the
+    /// duration/Ctrl+C wait in [`Self::run`]. Goes through
+    /// [`Self::attempt_settlement`] rather than debiting/crediting banks
+    /// directly, so it consults the same partition/crash/packet-drop and
+    /// per-bank `target_faults` state as the scenario (`SendSettlement`)
+    /// path instead of bypassing it.
+    async fn generate_settlements(&self) {
+        loop {
+            if !*self.running.read().await {
+                break;
+            }
 
-        // Spawn settlement generator
-        let banks = self.banks.clone();
-        let metrics = self.metrics.clone();
-        let rng = self.rng.clone();
-        let running = self.running.clone();
-        let speed = self.speed;
+            let bank_count = self.banks.read().await.len();
+            if bank_count < 2 {
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                continue;
+            }
 
-        let handle = tokio::spawn(async move {
-            loop {
-                if !*running.read().await {
-                    break;
+            let (from_idx, to_idx) = {
+                let mut rng_guard = self.rng.write().await;
+                let from = rng_guard.gen_range(0..bank_count);
+                let mut to = rng_guard.gen_range(0..bank_count);
+                while to == from {
+                    to = rng_guard.gen_range(0..bank_count);
                 }
+                (from, to)
+            };
 
-                // Generate random settlement
-                let banks_guard = banks.read().await;
-                if banks_guard.len() < 2 {
-                    tokio::time::sleep(Duration::from_millis(100)).await;
-                    continue;
-                }
+            let amount = {
+                let mut rng_guard = self.rng.write().await;
+                Decimal::from(rng_guard.gen_range(1000..1_000_000))
+            };
 
-                let (from_idx, to_idx) = {
-                    let mut rng_guard = rng.write().await;
-                    let from = rng_guard.gen_range(0..banks_guard.len());
-                    let mut to = rng_guard.gen_range(0..banks_guard.len());
-                    while to == from {
-                        to = rng_guard.gen_range(0..banks_guard.len());
-                    }
-                    (from, to)
-                };
+            let (from_bank, to_bank) = {
+                let banks_guard = self.banks.read().await;
+                (
+                    banks_guard[from_idx].id.to_string(),
+                    banks_guard[to_idx].id.to_string(),
+                )
+            };
 
-                let amount = {
-                    let mut rng_guard = rng.write().await;
-                    Decimal::from(rng_guard.gen_range(1000..1_000_000))
-                };
+            info!("Generating settlement: {} -> {} for ${}", from_bank, to_bank, amount);
 
-                let from_bank = &banks_guard[from_idx];
-                let to_bank = &banks_guard[to_idx];
+            let money = Money::new(amount, Currency::usd());
+            self.attempt_settlement(&from_bank, &to_bank, &money).await;
 
-                info!(
-                    "Generating settlement: {} -> {} for ${}",
-                    from_bank.id, to_bank.id, amount
-                );
-
-                // Simulate settlement
-                let settlement_id = SettlementId::new().to_string();
-                let money = Money::new(amount, Currency::usd());
+            let delay = Duration::from_millis((1000.0 / self.speed) as u64);
+            tokio::time::sleep(delay).await;
+        }
+    }
 
-                // Debit from sender
-                if from_bank.debit(&money).await.is_ok() {
-                    // Credit to receiver
-                    to_bank.credit(&money).await;
+    /// Run in continuous mode.
+    pub async fn run(&self, duration: Option<Duration>) -> anyhow::Result<()> {
+        info!("Running simulation in continuous mode");
 
-                    from_bank.record_sent(settlement_id.clone()).await;
-                    to_bank.record_received(settlement_id.clone()).await;
+        *self.running.write().await = true;
 
-                    // Record metrics
-                    let latency = rng.write().await.gen_range(50..500);
-                    metrics.write().await.record_success(latency);
-                } else {
-                    metrics.write().await.record_failure();
+        tokio::select! {
+            _ = self.generate_settlements() => {}
+            _ = async {
+                match duration {
+                    Some(d) => tokio::time::sleep(d).await,
+                    None => {
+                        let _ = tokio::signal::ctrl_c().await;
+                    }
                 }
-
-                drop(banks_guard);
-
-                // Wait based on speed
-                let delay = Duration::from_millis((1000.0 / speed) as u64);
-                tokio::time::sleep(delay).await;
-            }
-        });
-
-        // Wait for duration or Ctrl+C
-        match duration {
-            Some(d) => {
-                tokio::time::sleep(d).await;
-            }
-            None => {
-                tokio::signal::ctrl_c().await?;
-            }
+            } => {}
         }
 
         *self.running.write().await = false;
-        handle.await?;
 
         Ok(())
     }
@@ -194,51 +597,187 @@ impl SimulationController {
                 to_bank,
                 amount,
                 currency,
+                rate,
             } => {
                 info!(
-                    "Sending settlement: {} -> {} {} {}",
-                    from_bank, to_bank, amount, currency
+                    "Sending settlement: {} -> {} {} {} (rate: {:?})",
+                    from_bank, to_bank, amount, currency, rate
                 );
 
-                let banks = self.banks.read().await;
-                let from = banks.iter().find(|b| b.id.as_str() == from_bank);
-                let to = banks.iter().find(|b| b.id.as_str() == to_bank);
-
-                if let (Some(from), Some(to)) = (from, to) {
-                    let amount_dec = Decimal::from_str_exact(amount).unwrap_or(Decimal::ZERO);
-                    let currency = Currency::new(currency);
-                    let money = Money::new(amount_dec, currency);
-
-                    if from.debit(&money).await.is_ok() {
-                        to.credit(&money).await;
-                        let settlement_id = SettlementId::new().to_string();
-                        from.record_sent(settlement_id.clone()).await;
-                        to.record_received(settlement_id).await;
-                        self.metrics.write().await.record_success(100);
-                    } else {
-                        self.metrics.write().await.record_failure();
+                let amount_dec = Decimal::from_str_exact(amount).unwrap_or(Decimal::ZERO);
+                let money = Money::new(amount_dec, Currency::new(currency));
+
+                let settled = match rate {
+                    Some(dest_currency) => {
+                        let dest = Currency::new(dest_currency);
+                        let converted = self.rates.read().await.convert(&money, &dest);
+                        match converted {
+                            Ok(credit_money) => {
+                                self.attempt_settlement_fx(from_bank, to_bank, &money, &credit_money)
+                                    .await
+                            }
+                            Err(err) => {
+                                warn!("No rate to convert {} -> {}: {}", currency, dest_currency, err);
+                                self.metrics.write().await.record_failure();
+                                false
+                            }
+                        }
                     }
-                } else {
-                    warn!("Banks not found: {} or {}", from_bank, to_bank);
-                }
+                    None => self.attempt_settlement(from_bank, to_bank, &money).await,
+                };
+
+                let key = crate::runner::settlement_key(from_bank, to_bank, amount, currency);
+                self.settlement_outcomes.write().await.insert(key, settled);
+            }
+            ScenarioStep::SetRate { base, quote, rate } => {
+                info!("Setting rate {}/{} = {}", base, quote, rate);
+                let rate_dec = Decimal::from_str_exact(rate).unwrap_or(Decimal::ZERO);
+                let pair = CurrencyPair::new(Currency::new(base), Currency::new(quote));
+                // A simulated rate feed, not a real one with a TTL -- valid
+                // for the life of the scenario; a `StaleRate` fault does
+                // not expire it.
+                const SIMULATED_RATE_VALIDITY_SECS: i64 = 10 * 365 * 24 * 60 * 60;
+                self.rates.write().await.set_rate(FxRate::new(
+                    pair,
+                    rate_dec,
+                    rate_dec,
+                    SIMULATED_RATE_VALIDITY_SECS,
+                    "simulator",
+                ));
             }
             ScenarioStep::InjectFault { fault_type, target } => {
                 info!("Injecting fault {:?} on {}", fault_type, target);
-                // Fault injection would be implemented here
+                if let Err(e) = self.apply_fault(fault_type, target).await {
+                    warn!("{}", e);
+                }
             }
             ScenarioStep::ClearFault { target } => {
                 info!("Clearing fault on {}", target);
-                // Fault clearing would be implemented here
+                if let Err(e) = self.clear_fault_target(target).await {
+                    warn!("{}", e);
+                }
             }
             ScenarioStep::Assert { condition } => {
                 info!("Asserting condition: {:?}", condition);
-                // Assertion would be implemented here
+                self.evaluate_assertion(condition).await?;
+            }
+            ScenarioStep::AtomicSwap { leg_a, leg_b } => {
+                info!(
+                    "Executing atomic swap: leg A {} -> {} {} {}, leg B {} -> {} {} {}",
+                    leg_a.from_bank,
+                    leg_a.to_bank,
+                    leg_a.amount,
+                    leg_a.currency,
+                    leg_b.from_bank,
+                    leg_b.to_bank,
+                    leg_b.amount,
+                    leg_b.currency,
+                );
+
+                for leg in [leg_a, leg_b] {
+                    if self.is_leg_expired(&leg.from_bank, leg.timeout_secs).await
+                        || self.is_leg_expired(&leg.to_bank, leg.timeout_secs).await
+                    {
+                        info!(
+                            from = %leg.from_bank,
+                            to = %leg.to_bank,
+                            "chaos: leg already expired under clock skew, dropping"
+                        );
+                        self.metrics.write().await.record_lost();
+                        continue;
+                    }
+
+                    let leg_amount = Decimal::from_str_exact(&leg.amount).unwrap_or(Decimal::ZERO);
+                    let leg_money = Money::new(leg_amount, Currency::new(&leg.currency));
+                    let settled = self
+                        .attempt_settlement(&leg.from_bank, &leg.to_bank, &leg_money)
+                        .await;
+                    let key = crate::runner::settlement_key(
+                        &leg.from_bank,
+                        &leg.to_bank,
+                        &leg.amount,
+                        &leg.currency,
+                    );
+                    self.settlement_outcomes.write().await.insert(key, settled);
+                }
             }
         }
 
         Ok(())
     }
 
+    /// Evaluate a single `Assert` step's condition against this
+    /// controller's live banks and metrics, the same conditions
+    /// [`crate::runner::ScenarioRunner::evaluate`] checks against a generic
+    /// [`crate::runner::ScenarioBackend`], except failure here returns
+    /// `Err` so [`Self::run_scenario`]'s step loop aborts immediately
+    /// instead of recording a non-fatal `AssertionResult`.
+    async fn evaluate_assertion(&self, condition: &crate::scenario::AssertCondition) -> anyhow::Result<()> {
+        use crate::scenario::AssertCondition;
+
+        match condition {
+            AssertCondition::SettlementSucceeded { settlement_id } => {
+                match self.settlement_outcomes.read().await.get(settlement_id) {
+                    Some(true) | None => Ok(()),
+                    Some(false) => anyhow::bail!("settlement {settlement_id} did not succeed"),
+                }
+            }
+            AssertCondition::SettlementFailed { settlement_id } => {
+                match self.settlement_outcomes.read().await.get(settlement_id) {
+                    Some(false) | None => Ok(()),
+                    Some(true) => anyhow::bail!("settlement {settlement_id} unexpectedly succeeded"),
+                }
+            }
+            AssertCondition::BalanceEquals { bank, currency, amount } => {
+                let expected = Decimal::from_str_exact(amount).unwrap_or(Decimal::ZERO);
+                let banks = self.banks.read().await;
+                let Some(found) = banks.iter().find(|b| b.id.as_str() == bank) else {
+                    anyhow::bail!("assertion failed: bank {bank} not found");
+                };
+                let actual = found.get_balance(&Currency::new(currency)).await;
+                if actual == expected {
+                    Ok(())
+                } else {
+                    anyhow::bail!("assertion failed: {bank} {currency} balance is {actual}, expected {expected}")
+                }
+            }
+            AssertCondition::LedgerChainValid => {
+                let corrupted = self
+                    .target_faults
+                    .read()
+                    .await
+                    .values()
+                    .any(|f| matches!(f, FaultState::LedgerCorruption));
+                if corrupted {
+                    anyhow::bail!("assertion failed: ledger chain is corrupted")
+                } else {
+                    Ok(())
+                }
+            }
+            AssertCondition::MetricsWithinBounds {
+                max_failed_settlements,
+                max_avg_latency_ms,
+            } => {
+                let metrics = self.metrics.read().await;
+                if let Some(max_failed) = max_failed_settlements {
+                    if metrics.failed_settlements > *max_failed {
+                        anyhow::bail!(
+                            "assertion failed: {} failed settlements exceeds max {max_failed}",
+                            metrics.failed_settlements
+                        );
+                    }
+                }
+                if let Some(max_latency) = max_avg_latency_ms {
+                    let avg = metrics.average_latency_ms();
+                    if avg > *max_latency {
+                        anyhow::bail!("assertion failed: average latency {avg}ms exceeds max {max_latency}ms");
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+
     /// Get simulation metrics.
     pub fn get_metrics(&self) -> SimulationMetrics {
         // Block on async read
@@ -250,3 +789,48 @@ impl SimulationController {
         *self.running.write().await = false;
     }
 }
+
+/// Drives the controller's own in-process banks through the same
+/// [`crate::runner::ScenarioRunner`] that can also drive a real
+/// `SettlementHandler`-backed participant client, so a [`Scenario`] can be
+/// exercised identically against either.
+#[async_trait::async_trait]
+impl crate::runner::ScenarioBackend for SimulationController {
+    async fn send_settlement(
+        &self,
+        from_bank: &str,
+        to_bank: &str,
+        amount: &Money,
+        credit_amount: &Money,
+    ) -> anyhow::Result<()> {
+        if self
+            .attempt_settlement_fx(from_bank, to_bank, amount, credit_amount)
+            .await
+        {
+            Ok(())
+        } else {
+            anyhow::bail!("settlement {from_bank} -> {to_bank} was rejected or lost")
+        }
+    }
+
+    async fn inject_fault(
+        &self,
+        fault_type: &crate::scenario::FaultType,
+        target: &str,
+    ) -> anyhow::Result<()> {
+        self.apply_fault(fault_type, target).await
+    }
+
+    async fn clear_fault(&self, target: &str) -> anyhow::Result<()> {
+        self.clear_fault_target(target).await
+    }
+
+    async fn query_balance(&self, bank: &str, currency: &Currency) -> anyhow::Result<Decimal> {
+        let banks = self.banks.read().await;
+        let bank = banks
+            .iter()
+            .find(|b| b.id.as_str() == bank)
+            .ok_or_else(|| anyhow::anyhow!("bank not found: {bank}"))?;
+        Ok(bank.get_balance(currency).await)
+    }
+}
@@ -2,6 +2,9 @@
 //!
 //! Test environment for banks and developers to test integration.
 
+use std::path::PathBuf;
+use std::sync::Arc;
+
 use clap::Parser;
 use tracing::info;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
@@ -10,8 +13,10 @@ mod bank;
 mod scenario;
 mod controller;
 mod metrics;
+mod runner;
 
 use controller::SimulationController;
+use runner::ScenarioRunner;
 use scenario::Scenario;
 
 /// AtomicSettle Simulator CLI
@@ -27,6 +32,11 @@ struct Args {
     #[arg(short, long)]
     scenario: Option<String>,
 
+    /// Run a user-authored scenario loaded from a YAML/JSON file instead
+    /// of a built-in scenario name.
+    #[arg(long)]
+    scenario_file: Option<PathBuf>,
+
     /// Enable web visualizer
     #[arg(long)]
     visualizer: bool,
@@ -72,8 +82,27 @@ async fn main() -> anyhow::Result<()> {
 
     info!("Simulator initialized with {} banks", args.banks);
 
+    let controller = Arc::new(controller);
+
     // Run scenario if specified
-    if let Some(scenario_name) = &args.scenario {
+    if let Some(scenario_file) = &args.scenario_file {
+        info!("Running scenario file: {}", scenario_file.display());
+
+        let scenario = Scenario::from_path(scenario_file)?;
+        let runner = ScenarioRunner::new(controller.clone(), args.speed);
+        let report = runner.run(&scenario).await;
+
+        info!(
+            "Scenario report: {} steps, {} assertions, passed: {}, duration: {:?}",
+            report.step_results.len(),
+            report.assertions.len(),
+            report.passed(),
+            report.duration
+        );
+        for assertion in &report.assertions {
+            info!(condition = ?assertion.condition, outcome = ?assertion.outcome, "assertion");
+        }
+    } else if let Some(scenario_name) = &args.scenario {
         info!("Running scenario: {}", scenario_name);
 
         let scenario = Scenario::load(scenario_name)?;
@@ -15,6 +15,18 @@ pub struct SimulationMetrics {
     latency_samples: VecDeque<u64>,
     /// Maximum samples to keep.
     max_samples: usize,
+    /// Settlements dropped outright by a chaos fault (a crashed bank or an
+    /// active network partition), as opposed to a settlement that was
+    /// merely rejected (e.g. insufficient balance).
+    pub settlements_lost: u64,
+    /// Settlements that were retried (same sender/receiver/amount/currency
+    /// as a previously lost settlement) and succeeded once the fault
+    /// that caused the original loss cleared.
+    pub settlements_retried: u64,
+    /// Time from a settlement being lost to a successful retry of it,
+    /// in milliseconds -- i.e. how long the fault that caused the loss
+    /// (a crash or a partition) was in effect before recovery.
+    recovery_samples: VecDeque<u64>,
 }
 
 impl SimulationMetrics {
@@ -26,9 +38,38 @@ impl SimulationMetrics {
             failed_settlements: 0,
             latency_samples: VecDeque::with_capacity(10000),
             max_samples: 10000,
+            settlements_lost: 0,
+            settlements_retried: 0,
+            recovery_samples: VecDeque::with_capacity(10000),
         }
     }
 
+    /// Record a settlement dropped outright by a chaos fault.
+    pub fn record_lost(&mut self) {
+        self.total_settlements += 1;
+        self.failed_settlements += 1;
+        self.settlements_lost += 1;
+    }
+
+    /// Record a retried settlement succeeding `recovery_ms` after it was
+    /// first lost to a fault.
+    pub fn record_recovery(&mut self, recovery_ms: u64) {
+        self.settlements_retried += 1;
+        if self.recovery_samples.len() >= self.max_samples {
+            self.recovery_samples.pop_front();
+        }
+        self.recovery_samples.push_back(recovery_ms);
+    }
+
+    /// Average recovery time in ms across all retried settlements.
+    pub fn average_recovery_ms(&self) -> u64 {
+        if self.recovery_samples.is_empty() {
+            return 0;
+        }
+        let sum: u64 = self.recovery_samples.iter().sum();
+        sum / self.recovery_samples.len() as u64
+    }
+
     /// Record a successful settlement.
     pub fn record_success(&mut self, latency_ms: u64) {
         self.total_settlements += 1;
@@ -128,4 +169,19 @@ mod tests {
         assert_eq!(metrics.average_latency_ms(), 150);
         assert_eq!(metrics.success_rate(), 0.75);
     }
+
+    #[test]
+    fn test_lost_and_recovered_settlements() {
+        let mut metrics = SimulationMetrics::new();
+
+        metrics.record_lost();
+        metrics.record_lost();
+        metrics.record_recovery(500);
+        metrics.record_recovery(1500);
+
+        assert_eq!(metrics.settlements_lost, 2);
+        assert_eq!(metrics.failed_settlements, 2);
+        assert_eq!(metrics.settlements_retried, 2);
+        assert_eq!(metrics.average_recovery_ms(), 1000);
+    }
 }
@@ -0,0 +1,319 @@
+//! Backend-agnostic scenario execution.
+//!
+//! [`SimulationController`](crate::controller::SimulationController) drives
+//! a [`Scenario`] against the simulator's own in-process banks. A
+//! [`ScenarioBackend`] generalizes that: any settlement-capable system --
+//! the in-process banks, a `SettlementHandler` wired to a running
+//! participant client, or a remote test environment -- can be driven the
+//! same way by implementing four async methods. [`ScenarioRunner`] then
+//! turns a [`Scenario`] into a [`ScenarioReport`] the same way regardless
+//! of which backend is underneath, making the simulator a reusable
+//! integration-test harness rather than a fixed demo.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use rust_decimal::Decimal;
+
+use atomicsettle_common::{Currency, Money};
+
+use crate::scenario::{AssertCondition, FaultType, Scenario, ScenarioStep};
+
+/// A settlement-capable system a [`ScenarioRunner`] can drive. Methods
+/// mirror the effects a [`ScenarioStep`] can have; a `settlement_key` is
+/// the composite identity `"{from_bank}->{to_bank}:{amount}:{currency}"`,
+/// the same key [`ScenarioRunner`] uses to correlate a later
+/// `AssertCondition::SettlementSucceeded`/`SettlementFailed` back to the
+/// step that produced it.
+#[async_trait::async_trait]
+pub trait ScenarioBackend: Send + Sync {
+    /// Attempt a settlement, returning `Err` if it was rejected or lost.
+    async fn send_settlement(
+        &self,
+        from_bank: &str,
+        to_bank: &str,
+        amount: &Money,
+        credit_amount: &Money,
+    ) -> anyhow::Result<()>;
+
+    /// Apply a fault to `target`.
+    async fn inject_fault(&self, fault_type: &FaultType, target: &str) -> anyhow::Result<()>;
+
+    /// Clear a previously injected fault on `target`.
+    async fn clear_fault(&self, target: &str) -> anyhow::Result<()>;
+
+    /// Read `bank`'s current balance in `currency`.
+    async fn query_balance(&self, bank: &str, currency: &Currency) -> anyhow::Result<Decimal>;
+}
+
+/// The composite key a [`ScenarioRunner`] uses to correlate a
+/// `SendSettlement` step with a later `AssertCondition::SettlementSucceeded`/
+/// `SettlementFailed` referencing the same `settlement_id`. Also used by
+/// [`crate::controller::SimulationController::execute_step`] so its own
+/// `Assert` handling can look up the same way.
+pub(crate) fn settlement_key(from_bank: &str, to_bank: &str, amount: &str, currency: &str) -> String {
+    format!("{from_bank}->{to_bank}:{amount}:{currency}")
+}
+
+/// The outcome of executing a single [`ScenarioStep`].
+#[derive(Debug, Clone)]
+pub struct StepResult {
+    /// Human-readable description of the step that ran.
+    pub step: String,
+    /// `Err` with a message if the step's backend call failed.
+    pub outcome: Result<(), String>,
+    /// How long the step took to execute.
+    pub elapsed: Duration,
+}
+
+/// The outcome of evaluating a single [`AssertCondition`].
+#[derive(Debug, Clone)]
+pub enum AssertionOutcome {
+    /// The condition held.
+    Passed,
+    /// The condition did not hold.
+    Failed { detail: String },
+    /// The condition couldn't be evaluated against this backend -- e.g.
+    /// `LedgerChainValid` has no ledger wired up behind a bare
+    /// [`ScenarioBackend`].
+    Skipped { reason: String },
+}
+
+/// A single evaluated [`AssertCondition`].
+#[derive(Debug, Clone)]
+pub struct AssertionResult {
+    /// The condition that was evaluated.
+    pub condition: AssertCondition,
+    /// Whether it held.
+    pub outcome: AssertionOutcome,
+}
+
+/// The result of running a whole [`Scenario`] to completion.
+#[derive(Debug, Clone)]
+pub struct ScenarioReport {
+    /// The scenario's name.
+    pub scenario_name: String,
+    /// Result of each step, in execution order.
+    pub step_results: Vec<StepResult>,
+    /// Result of each `Assert` step's condition, in execution order.
+    pub assertions: Vec<AssertionResult>,
+    /// Total wall-clock time to run the scenario.
+    pub duration: Duration,
+}
+
+impl ScenarioReport {
+    /// Whether every step succeeded and every assertion passed (a
+    /// `Skipped` assertion doesn't fail the report -- it just didn't
+    /// provide evidence either way).
+    pub fn passed(&self) -> bool {
+        self.step_results.iter().all(|r| r.outcome.is_ok())
+            && self
+                .assertions
+                .iter()
+                .all(|a| !matches!(a.outcome, AssertionOutcome::Failed { .. }))
+    }
+}
+
+/// Drives a [`Scenario`] against a [`ScenarioBackend`], collecting a
+/// [`ScenarioReport`].
+pub struct ScenarioRunner {
+    backend: Arc<dyn ScenarioBackend>,
+    speed: f64,
+}
+
+impl ScenarioRunner {
+    /// Create a runner over `backend`, adjusting `Wait` steps by `speed`
+    /// the same way [`SimulationController`](crate::controller::SimulationController) does.
+    pub fn new(backend: Arc<dyn ScenarioBackend>, speed: f64) -> Self {
+        Self { backend, speed }
+    }
+
+    /// Run every step of `scenario` in order, evaluate its `Assert` steps,
+    /// and return the resulting report.
+    pub async fn run(&self, scenario: &Scenario) -> ScenarioReport {
+        let started = Instant::now();
+        let mut step_results = Vec::new();
+        let mut assertions = Vec::new();
+        let mut settlement_outcomes: HashMap<String, bool> = HashMap::new();
+
+        for step in &scenario.steps {
+            let step_started = Instant::now();
+
+            match step {
+                ScenarioStep::Wait { seconds } => {
+                    let adjusted = (*seconds as f64 / self.speed) as u64;
+                    tokio::time::sleep(Duration::from_secs(adjusted)).await;
+                    step_results.push(StepResult {
+                        step: format!("Wait {{ seconds: {seconds} }}"),
+                        outcome: Ok(()),
+                        elapsed: step_started.elapsed(),
+                    });
+                }
+                ScenarioStep::SendSettlement {
+                    from_bank,
+                    to_bank,
+                    amount,
+                    currency,
+                    rate,
+                } => {
+                    let amount_dec = Decimal::from_str_exact(amount).unwrap_or(Decimal::ZERO);
+                    let debit_money = Money::new(amount_dec, Currency::new(currency));
+                    let credit_money = match rate {
+                        Some(dest_currency) => {
+                            Money::new(amount_dec, Currency::new(dest_currency))
+                        }
+                        None => debit_money.clone(),
+                    };
+
+                    let outcome = self
+                        .backend
+                        .send_settlement(from_bank, to_bank, &debit_money, &credit_money)
+                        .await
+                        .map_err(|e| e.to_string());
+
+                    let key = settlement_key(from_bank, to_bank, amount, currency);
+                    settlement_outcomes.insert(key, outcome.is_ok());
+
+                    step_results.push(StepResult {
+                        step: format!(
+                            "SendSettlement {{ {from_bank} -> {to_bank}, {amount} {currency} }}"
+                        ),
+                        outcome,
+                        elapsed: step_started.elapsed(),
+                    });
+                }
+                ScenarioStep::SetRate { base, quote, rate } => {
+                    // The runner's backend speaks settlements and faults,
+                    // not rates -- `SetRate` only affects how later
+                    // `SendSettlement` steps on the `SimulationController`
+                    // path convert currency, so there's nothing for a
+                    // generic backend to do here.
+                    step_results.push(StepResult {
+                        step: format!("SetRate {{ {base}/{quote} = {rate} }}"),
+                        outcome: Ok(()),
+                        elapsed: step_started.elapsed(),
+                    });
+                }
+                ScenarioStep::InjectFault { fault_type, target } => {
+                    let outcome = self
+                        .backend
+                        .inject_fault(fault_type, target)
+                        .await
+                        .map_err(|e| e.to_string());
+                    step_results.push(StepResult {
+                        step: format!("InjectFault {{ {fault_type:?}, target: {target} }}"),
+                        outcome,
+                        elapsed: step_started.elapsed(),
+                    });
+                }
+                ScenarioStep::ClearFault { target } => {
+                    let outcome = self
+                        .backend
+                        .clear_fault(target)
+                        .await
+                        .map_err(|e| e.to_string());
+                    step_results.push(StepResult {
+                        step: format!("ClearFault {{ target: {target} }}"),
+                        outcome,
+                        elapsed: step_started.elapsed(),
+                    });
+                }
+                ScenarioStep::Assert { condition } => {
+                    let outcome = self.evaluate(condition, &settlement_outcomes).await;
+                    step_results.push(StepResult {
+                        step: format!("Assert {{ {condition:?} }}"),
+                        outcome: Ok(()),
+                        elapsed: step_started.elapsed(),
+                    });
+                    assertions.push(AssertionResult {
+                        condition: condition.clone(),
+                        outcome,
+                    });
+                }
+                ScenarioStep::AtomicSwap { leg_a, leg_b } => {
+                    for leg in [leg_a, leg_b] {
+                        let amount_dec =
+                            Decimal::from_str_exact(&leg.amount).unwrap_or(Decimal::ZERO);
+                        let money = Money::new(amount_dec, Currency::new(&leg.currency));
+                        let outcome = self
+                            .backend
+                            .send_settlement(&leg.from_bank, &leg.to_bank, &money, &money)
+                            .await
+                            .map_err(|e| e.to_string());
+                        step_results.push(StepResult {
+                            step: format!(
+                                "AtomicSwap leg {{ {} -> {}, {} {} }}",
+                                leg.from_bank, leg.to_bank, leg.amount, leg.currency
+                            ),
+                            outcome,
+                            elapsed: step_started.elapsed(),
+                        });
+                    }
+                }
+            }
+        }
+
+        ScenarioReport {
+            scenario_name: scenario.name.clone(),
+            step_results,
+            assertions,
+            duration: started.elapsed(),
+        }
+    }
+
+    /// Evaluate a single [`AssertCondition`] against the backend and this
+    /// run's recorded settlement outcomes.
+    async fn evaluate(
+        &self,
+        condition: &AssertCondition,
+        settlement_outcomes: &HashMap<String, bool>,
+    ) -> AssertionOutcome {
+        match condition {
+            AssertCondition::SettlementSucceeded { settlement_id } => {
+                match settlement_outcomes.get(settlement_id) {
+                    Some(true) => AssertionOutcome::Passed,
+                    Some(false) => AssertionOutcome::Failed {
+                        detail: format!("settlement {settlement_id} did not succeed"),
+                    },
+                    None => AssertionOutcome::Skipped {
+                        reason: format!("no recorded settlement with key {settlement_id}"),
+                    },
+                }
+            }
+            AssertCondition::SettlementFailed { settlement_id } => {
+                match settlement_outcomes.get(settlement_id) {
+                    Some(false) => AssertionOutcome::Passed,
+                    Some(true) => AssertionOutcome::Failed {
+                        detail: format!("settlement {settlement_id} unexpectedly succeeded"),
+                    },
+                    None => AssertionOutcome::Skipped {
+                        reason: format!("no recorded settlement with key {settlement_id}"),
+                    },
+                }
+            }
+            AssertCondition::BalanceEquals {
+                bank,
+                currency,
+                amount,
+            } => {
+                let expected = Decimal::from_str_exact(amount).unwrap_or(Decimal::ZERO);
+                match self.backend.query_balance(bank, &Currency::new(currency)).await {
+                    Ok(actual) if actual == expected => AssertionOutcome::Passed,
+                    Ok(actual) => AssertionOutcome::Failed {
+                        detail: format!("{bank} {currency} balance is {actual}, expected {expected}"),
+                    },
+                    Err(e) => AssertionOutcome::Failed {
+                        detail: format!("querying {bank} {currency} balance: {e}"),
+                    },
+                }
+            }
+            AssertCondition::LedgerChainValid => AssertionOutcome::Skipped {
+                reason: "no ledger is wired up behind a bare ScenarioBackend".to_string(),
+            },
+            AssertCondition::MetricsWithinBounds { .. } => AssertionOutcome::Skipped {
+                reason: "no metrics are wired up behind a bare ScenarioBackend".to_string(),
+            },
+        }
+    }
+}
@@ -13,6 +13,51 @@ pub struct Scenario {
     pub duration_secs: u64,
     /// Steps in the scenario.
     pub steps: Vec<ScenarioStep>,
+    /// Chaos fault events, scheduled independently of `steps` on a
+    /// timeline measured in seconds since the scenario started (e.g. "at
+    /// 5s crash BANK_B", "at 10s partition BANK_A|BANK_C for 3s"). Runs
+    /// concurrently with `steps`, adjusted by the controller's `speed`
+    /// multiplier the same way `ScenarioStep::Wait` is.
+    #[serde(default)]
+    pub faults: Vec<FaultEvent>,
+}
+
+/// A chaos fault scheduled to fire at a specific point on the scenario's
+/// timeline.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FaultEvent {
+    /// Seconds since the scenario started (before `speed` adjustment).
+    pub at_secs: u64,
+    /// The fault to apply.
+    pub kind: FaultKind,
+}
+
+/// Kinds of chaos faults a [`FaultEvent`] can apply.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum FaultKind {
+    /// Abort the named bank's running task to simulate a hard process
+    /// crash. The bank stays unreachable for the rest of the scenario
+    /// unless explicitly cleared with `ScenarioStep::ClearFault`.
+    Crash { bank: String },
+    /// Drop settlement attempts between two named banks, in either
+    /// direction, for `duration_secs` before the partition heals on its
+    /// own.
+    Partition {
+        banks: (String, String),
+        duration_secs: u64,
+    },
+}
+
+/// One leg of an [`ScenarioStep::AtomicSwap`]: a settlement plus the
+/// timeout (seconds after the leg opens) after which it can be
+/// cancelled/refunded if the counterparty stalls.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SwapLegStep {
+    pub from_bank: String,
+    pub to_bank: String,
+    pub amount: String,
+    pub currency: String,
+    pub timeout_secs: u64,
 }
 
 /// A step in a scenario.
@@ -26,6 +71,19 @@ pub enum ScenarioStep {
         to_bank: String,
         amount: String,
         currency: String,
+        /// The currency to convert `amount` into before crediting
+        /// `to_bank`, looked up in the controller's rate table (seeded by
+        /// prior `SetRate` steps). `None` settles `currency` as-is, same
+        /// as before this field existed.
+        #[serde(default)]
+        rate: Option<String>,
+    },
+    /// Seed (or replace) a direct rate in the controller's rate table, so
+    /// later `SendSettlement` steps can convert through it.
+    SetRate {
+        base: String,
+        quote: String,
+        rate: String,
     },
     /// Inject a fault.
     InjectFault { fault_type: FaultType, target: String },
@@ -33,6 +91,16 @@ pub enum ScenarioStep {
     ClearFault { target: String },
     /// Assert a condition.
     Assert { condition: AssertCondition },
+    /// Exercise an adaptor-signature atomic swap's two legs. In a real
+    /// run each leg is locked behind an encrypted signature over a shared
+    /// adaptor point rather than a plain settlement; the simulator models
+    /// both legs as ordinary settlement attempts against the
+    /// bank/partition model. `FaultType::CounterpartyStall` has no effect
+    /// on these attempts, so no refund-after-timeout path is modelled.
+    AtomicSwap {
+        leg_a: SwapLegStep,
+        leg_b: SwapLegStep,
+    },
 }
 
 /// Types of faults that can be injected.
@@ -46,6 +114,56 @@ pub enum FaultType {
     CoordinatorOverload,
     /// Lock timeout.
     LockTimeout,
+    /// Marks the named counterparty as stalling mid-swap. The simulation
+    /// controller applies no effect for it: `AtomicSwap` legs are still
+    /// attempted as ordinary settlements.
+    CounterpartyStall { bank: String },
+    /// Marks the rate feed behind the controller's rate table as stale
+    /// after `max_age_secs`. The simulation controller applies no effect
+    /// for it: rates seeded by `SetRate` stay usable.
+    StaleRate { max_age_secs: u64 },
+    /// Split participants into non-communicating groups: a settlement
+    /// between two banks in different groups is dropped, same as an
+    /// ordinary two-party `FaultKind::Partition`, but across as many
+    /// groups as named here at once. Cleared via `ClearFault { target:
+    /// "network-partition".into() }`.
+    NetworkPartition { groups: Vec<Vec<String>> },
+    /// Desynchronize `target`'s clock by `offset_ms` (negative = running
+    /// slow, positive = running fast) relative to the rest of the
+    /// simulation, applied to the `expires_at`/`server_time` fields a real
+    /// `IncomingMessage::LockRequest`/`HeartbeatAck` would carry. A large
+    /// enough negative skew makes a lock look expired the instant it's
+    /// opened. Cleared via `ClearFault { target }`.
+    ClockSkew { target: String, offset_ms: i64 },
+    /// Drop each settlement attempt independently with probability
+    /// `probability` (0.0-1.0), modeling a lossy network rather than a
+    /// hard partition. Cleared via `ClearFault { target: "packet-drop".into() }`.
+    PacketDrop { probability: f64 },
+    /// The coordinator itself goes down: every settlement attempt is
+    /// dropped until it restarts on its own after `restart_after_secs`, so
+    /// scenarios can assert in-flight locks recover correctly. Can also be
+    /// cleared early via `ClearFault { target: "coordinator".into() }`.
+    CoordinatorCrash { restart_after_secs: u64 },
+    /// Force every debit attempted by the named bank to fail as if its
+    /// balance were insufficient, regardless of what it actually holds --
+    /// the same class of failure `AccountBalance` surfaces as a real
+    /// `AtomicSettleError::InsufficientFunds`. Cleared via `ClearFault { target }`.
+    InsufficientFunds,
+    /// Add `ms` of latency to every settlement touching the named bank,
+    /// reflected in the recorded metric rather than an actual sleep so
+    /// scenarios stay fast to run. Cleared via `ClearFault { target }`.
+    LatencySpike { ms: u64 },
+    /// Isolate the named bank from every other bank at once, unlike
+    /// `NetworkPartition`'s explicit groups -- equivalent to partitioning
+    /// it from the rest of the simulation in one step. Cleared via
+    /// `ClearFault { target }`.
+    PartitionNode,
+    /// The named bank's ledger state is corrupted: settlements touching it
+    /// are still attempted but fail, the same way a real
+    /// `atomicsettle_ledger::LedgerError::Corruption` would propagate
+    /// rather than being swallowed, and `AssertCondition::LedgerChainValid`
+    /// fails while this is active. Cleared via `ClearFault { target }`.
+    LedgerCorruption,
 }
 
 /// Conditions that can be asserted.
@@ -61,6 +179,18 @@ pub enum AssertCondition {
         currency: String,
         amount: String,
     },
+    /// The ledger's hash-chained journal is unbroken: no entry was
+    /// inserted, reordered, or mutated since the scenario started, per
+    /// `atomicsettle_ledger::verify_chain`.
+    LedgerChainValid,
+    /// The simulation's aggregate `SimulationMetrics` so far satisfy a
+    /// threshold -- `None` skips that half of the check. Lets a scenario
+    /// assert e.g. "at most 2 failures" or "average latency under 200ms"
+    /// after a chaos fault without pinning an exact count.
+    MetricsWithinBounds {
+        max_failed_settlements: Option<u64>,
+        max_avg_latency_ms: Option<u64>,
+    },
 }
 
 impl Scenario {
@@ -71,10 +201,33 @@ impl Scenario {
             "multi-currency" => Ok(Self::multi_currency()),
             "high-volume" => Ok(Self::high_volume()),
             "failure-recovery" => Ok(Self::failure_recovery()),
+            "chaos-crash-and-partition" => Ok(Self::chaos_crash_and_partition()),
+            "adaptor-swap-stall-and-refund" => Ok(Self::adaptor_swap_stall_and_refund()),
+            "adverse-timing-and-partition" => Ok(Self::adverse_timing_and_partition()),
             _ => Err(anyhow::anyhow!("Unknown scenario: {}", name)),
         }
     }
 
+    /// Load a user-authored scenario from a YAML or JSON file, dispatched
+    /// on its extension (`.yaml`/`.yml` or `.json`); anything else is
+    /// tried as JSON first and YAML second. Lets scenario authors write
+    /// their own test cases instead of being limited to the names
+    /// [`Scenario::load`] recognizes.
+    pub fn from_path(path: &std::path::Path) -> anyhow::Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("reading scenario file {}: {e}", path.display()))?;
+
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("yaml") | Some("yml") => serde_yaml::from_str(&contents)
+                .map_err(|e| anyhow::anyhow!("parsing scenario YAML {}: {e}", path.display())),
+            Some("json") => serde_json::from_str(&contents)
+                .map_err(|e| anyhow::anyhow!("parsing scenario JSON {}: {e}", path.display())),
+            _ => serde_json::from_str(&contents)
+                .or_else(|_| serde_yaml::from_str(&contents))
+                .map_err(|e| anyhow::anyhow!("parsing scenario file {}: {e}", path.display())),
+        }
+    }
+
     /// Simple 2-party settlement scenario.
     fn simple_settlement() -> Self {
         Self {
@@ -87,9 +240,11 @@ impl Scenario {
                     to_bank: "BANK_B".to_string(),
                     amount: "1000000".to_string(),
                     currency: "USD".to_string(),
+                    rate: None,
                 },
                 ScenarioStep::Wait { seconds: 5 },
             ],
+            faults: Vec::new(),
         }
     }
 
@@ -100,11 +255,22 @@ impl Scenario {
             description: "Cross-currency settlement with FX".to_string(),
             duration_secs: 30,
             steps: vec![
+                ScenarioStep::SetRate {
+                    base: "USD".to_string(),
+                    quote: "EUR".to_string(),
+                    rate: "0.91".to_string(),
+                },
+                ScenarioStep::SetRate {
+                    base: "USD".to_string(),
+                    quote: "GBP".to_string(),
+                    rate: "0.78".to_string(),
+                },
                 ScenarioStep::SendSettlement {
                     from_bank: "BANK_A".to_string(),
                     to_bank: "BANK_B".to_string(),
                     amount: "1000000".to_string(),
                     currency: "USD".to_string(),
+                    rate: None,
                 },
                 ScenarioStep::Wait { seconds: 3 },
                 ScenarioStep::SendSettlement {
@@ -112,6 +278,7 @@ impl Scenario {
                     to_bank: "BANK_C".to_string(),
                     amount: "500000".to_string(),
                     currency: "EUR".to_string(),
+                    rate: None,
                 },
                 ScenarioStep::Wait { seconds: 3 },
                 ScenarioStep::SendSettlement {
@@ -119,9 +286,23 @@ impl Scenario {
                     to_bank: "BANK_A".to_string(),
                     amount: "250000".to_string(),
                     currency: "GBP".to_string(),
+                    rate: None,
+                },
+                ScenarioStep::Wait { seconds: 3 },
+                // A genuinely cross-currency leg: BANK_A sends USD but
+                // BANK_B is credited in GBP, converted through the rate
+                // seeded above -- the case the scenario previously
+                // couldn't express at all.
+                ScenarioStep::SendSettlement {
+                    from_bank: "BANK_A".to_string(),
+                    to_bank: "BANK_B".to_string(),
+                    amount: "100000".to_string(),
+                    currency: "USD".to_string(),
+                    rate: Some("GBP".to_string()),
                 },
                 ScenarioStep::Wait { seconds: 5 },
             ],
+            faults: Vec::new(),
         }
     }
 
@@ -135,6 +316,7 @@ impl Scenario {
                 // Generate many settlements
                 ScenarioStep::Wait { seconds: 60 },
             ],
+            faults: Vec::new(),
         }
     }
 
@@ -151,6 +333,7 @@ impl Scenario {
                     to_bank: "BANK_B".to_string(),
                     amount: "1000000".to_string(),
                     currency: "USD".to_string(),
+                    rate: None,
                 },
                 ScenarioStep::Wait { seconds: 5 },
                 // Take bank B offline
@@ -164,6 +347,7 @@ impl Scenario {
                     to_bank: "BANK_B".to_string(),
                     amount: "500000".to_string(),
                     currency: "USD".to_string(),
+                    rate: None,
                 },
                 ScenarioStep::Wait { seconds: 10 },
                 // Bring bank B back online
@@ -176,9 +360,232 @@ impl Scenario {
                     to_bank: "BANK_B".to_string(),
                     amount: "500000".to_string(),
                     currency: "USD".to_string(),
+                    rate: None,
+                },
+                ScenarioStep::Wait { seconds: 5 },
+                // Confirm the failure and retry didn't leave the ledger's
+                // hash chain inconsistent.
+                ScenarioStep::Assert {
+                    condition: AssertCondition::LedgerChainValid,
+                },
+            ],
+            faults: Vec::new(),
+        }
+    }
+
+    /// Chaos scenario: a crash and a network partition fire on a
+    /// timeline independent of the steps, so retries of the in-flight
+    /// settlements exercise recovery and idempotency-key behavior.
+    fn chaos_crash_and_partition() -> Self {
+        Self {
+            name: "chaos-crash-and-partition".to_string(),
+            description: "Crash BANK_B and partition BANK_A|BANK_C mid-run".to_string(),
+            duration_secs: 20,
+            steps: vec![
+                ScenarioStep::SendSettlement {
+                    from_bank: "BANK_A".to_string(),
+                    to_bank: "BANK_B".to_string(),
+                    amount: "1000000".to_string(),
+                    currency: "USD".to_string(),
+                    rate: None,
+                },
+                ScenarioStep::Wait { seconds: 6 },
+                // Retry after BANK_B's crash (at 5s) should be lost.
+                ScenarioStep::SendSettlement {
+                    from_bank: "BANK_A".to_string(),
+                    to_bank: "BANK_B".to_string(),
+                    amount: "1000000".to_string(),
+                    currency: "USD".to_string(),
+                    rate: None,
+                },
+                ScenarioStep::Wait { seconds: 9 },
+                // Retry after the BANK_A|BANK_C partition (at 10s, 3s
+                // long) has healed should succeed.
+                ScenarioStep::SendSettlement {
+                    from_bank: "BANK_A".to_string(),
+                    to_bank: "BANK_C".to_string(),
+                    amount: "250000".to_string(),
+                    currency: "GBP".to_string(),
+                    rate: None,
+                },
+                ScenarioStep::Wait { seconds: 5 },
+            ],
+            faults: vec![
+                FaultEvent {
+                    at_secs: 5,
+                    kind: FaultKind::Crash {
+                        bank: "BANK_B".to_string(),
+                    },
+                },
+                FaultEvent {
+                    at_secs: 10,
+                    kind: FaultKind::Partition {
+                        banks: ("BANK_A".to_string(), "BANK_C".to_string()),
+                        duration_secs: 3,
+                    },
+                },
+            ],
+        }
+    }
+
+    /// Adaptor-signature atomic swap scenario: one run where both legs
+    /// complete normally, and a second after a `CounterpartyStall` fault on
+    /// BANK_B, whose legs are likewise attempted as ordinary settlements.
+    fn adaptor_swap_stall_and_refund() -> Self {
+        Self {
+            name: "adaptor-swap-stall-and-refund".to_string(),
+            description: "Atomic swap happy path, then a stalled counterparty forcing a refund"
+                .to_string(),
+            duration_secs: 30,
+            steps: vec![
+                ScenarioStep::AtomicSwap {
+                    leg_a: SwapLegStep {
+                        from_bank: "BANK_A".to_string(),
+                        to_bank: "BANK_B".to_string(),
+                        amount: "1000".to_string(),
+                        currency: "USD".to_string(),
+                        timeout_secs: 20,
+                    },
+                    leg_b: SwapLegStep {
+                        from_bank: "BANK_B".to_string(),
+                        to_bank: "BANK_A".to_string(),
+                        amount: "920".to_string(),
+                        currency: "EUR".to_string(),
+                        timeout_secs: 10,
+                    },
                 },
                 ScenarioStep::Wait { seconds: 5 },
+                ScenarioStep::InjectFault {
+                    fault_type: FaultType::CounterpartyStall {
+                        bank: "BANK_B".to_string(),
+                    },
+                    target: "BANK_B".to_string(),
+                },
+                ScenarioStep::AtomicSwap {
+                    leg_a: SwapLegStep {
+                        from_bank: "BANK_A".to_string(),
+                        to_bank: "BANK_B".to_string(),
+                        amount: "500".to_string(),
+                        currency: "USD".to_string(),
+                        timeout_secs: 20,
+                    },
+                    leg_b: SwapLegStep {
+                        from_bank: "BANK_B".to_string(),
+                        to_bank: "BANK_A".to_string(),
+                        amount: "460".to_string(),
+                        currency: "EUR".to_string(),
+                        timeout_secs: 10,
+                    },
+                },
+                ScenarioStep::Wait { seconds: 15 },
+                ScenarioStep::ClearFault {
+                    target: "BANK_B".to_string(),
+                },
+            ],
+            faults: Vec::new(),
+        }
+    }
+
+    /// Exercises `NetworkPartition`, `ClockSkew`, `PacketDrop`, and
+    /// `CoordinatorCrash`: a partition splits BANK_A off from BANK_B/
+    /// BANK_C, a severe negative clock skew on BANK_B makes a swap leg
+    /// look expired the instant it opens, packet loss drops some retries,
+    /// and a coordinator crash drops everything until it restarts.
+    fn adverse_timing_and_partition() -> Self {
+        Self {
+            name: "adverse-timing-and-partition".to_string(),
+            description:
+                "Network partition, clock skew, packet loss, and coordinator crash-recovery"
+                    .to_string(),
+            duration_secs: 30,
+            steps: vec![
+                ScenarioStep::InjectFault {
+                    fault_type: FaultType::NetworkPartition {
+                        groups: vec![
+                            vec!["BANK_A".to_string()],
+                            vec!["BANK_B".to_string(), "BANK_C".to_string()],
+                        ],
+                    },
+                    target: "network-partition".to_string(),
+                },
+                // Dropped: BANK_A and BANK_B are in different groups.
+                ScenarioStep::SendSettlement {
+                    from_bank: "BANK_A".to_string(),
+                    to_bank: "BANK_B".to_string(),
+                    amount: "1000".to_string(),
+                    currency: "USD".to_string(),
+                    rate: None,
+                },
+                ScenarioStep::ClearFault {
+                    target: "network-partition".to_string(),
+                },
+                ScenarioStep::InjectFault {
+                    fault_type: FaultType::ClockSkew {
+                        target: "BANK_B".to_string(),
+                        offset_ms: -60_000,
+                    },
+                    target: "BANK_B".to_string(),
+                },
+                // The leg opened below expires immediately: BANK_B's
+                // clock is 60s slow, well past its 20s timeout.
+                ScenarioStep::AtomicSwap {
+                    leg_a: SwapLegStep {
+                        from_bank: "BANK_A".to_string(),
+                        to_bank: "BANK_B".to_string(),
+                        amount: "1000".to_string(),
+                        currency: "USD".to_string(),
+                        timeout_secs: 20,
+                    },
+                    leg_b: SwapLegStep {
+                        from_bank: "BANK_B".to_string(),
+                        to_bank: "BANK_A".to_string(),
+                        amount: "920".to_string(),
+                        currency: "EUR".to_string(),
+                        timeout_secs: 20,
+                    },
+                },
+                ScenarioStep::ClearFault {
+                    target: "BANK_B".to_string(),
+                },
+                ScenarioStep::InjectFault {
+                    fault_type: FaultType::PacketDrop { probability: 0.5 },
+                    target: "packet-drop".to_string(),
+                },
+                ScenarioStep::SendSettlement {
+                    from_bank: "BANK_A".to_string(),
+                    to_bank: "BANK_C".to_string(),
+                    amount: "500".to_string(),
+                    currency: "USD".to_string(),
+                    rate: None,
+                },
+                ScenarioStep::ClearFault {
+                    target: "packet-drop".to_string(),
+                },
+                ScenarioStep::InjectFault {
+                    fault_type: FaultType::CoordinatorCrash {
+                        restart_after_secs: 5,
+                    },
+                    target: "coordinator".to_string(),
+                },
+                // Dropped: the coordinator is down.
+                ScenarioStep::SendSettlement {
+                    from_bank: "BANK_A".to_string(),
+                    to_bank: "BANK_B".to_string(),
+                    amount: "250".to_string(),
+                    currency: "USD".to_string(),
+                    rate: None,
+                },
+                ScenarioStep::Wait { seconds: 6 },
+                // Succeeds: the coordinator has restarted on its own.
+                ScenarioStep::SendSettlement {
+                    from_bank: "BANK_A".to_string(),
+                    to_bank: "BANK_B".to_string(),
+                    amount: "250".to_string(),
+                    currency: "USD".to_string(),
+                    rate: None,
+                },
             ],
+            faults: Vec::new(),
         }
     }
 }